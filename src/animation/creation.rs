@@ -43,6 +43,10 @@ impl Animation for FadeIn {
         mobject.set_opacity(eased_t);
     }
 
+    fn introduces(&self) -> bool {
+        true
+    }
+
     fn clone_box(&self) -> Box<dyn Animation> {
         Box::new(self.clone())
     }
@@ -103,8 +107,6 @@ pub struct Create {
     target: MobjectId,
     duration: f32,
     easing: Easing,
-    /// Current progress stored for the mobject to query during draw
-    progress: f32,
 }
 
 impl Create {
@@ -113,7 +115,6 @@ impl Create {
             target,
             duration: 1.0,
             easing: Easing::Smooth,
-            progress: 0.0,
         }
     }
 
@@ -126,11 +127,6 @@ impl Create {
         self.easing = easing;
         self
     }
-
-    /// Get the current drawing progress (used by renderer)
-    pub fn progress(&self) -> f32 {
-        self.progress
-    }
 }
 
 impl Animation for Create {
@@ -142,11 +138,18 @@ impl Animation for Create {
         self.target
     }
 
-    fn apply(&self, _mobject: &mut dyn Mobject, t: f32) {
+    fn apply(&self, _mobject: &mut dyn Mobject, _t: f32) {
         // Note: Create animation works differently - the progress is used
         // during drawing rather than modifying mobject properties.
-        // The scene/renderer will pass t to the mobject's draw() method.
-        let _ = self.easing.apply(t);
+        // The scene passes draw_progress() to the mobject's draw() method.
+    }
+
+    fn draw_progress(&self, t: f32) -> Option<f32> {
+        Some(self.easing.apply(t))
+    }
+
+    fn introduces(&self) -> bool {
+        true
     }
 
     fn clone_box(&self) -> Box<dyn Animation> {
@@ -191,9 +194,12 @@ impl Animation for Uncreate {
         self.target
     }
 
-    fn apply(&self, _mobject: &mut dyn Mobject, t: f32) {
+    fn apply(&self, _mobject: &mut dyn Mobject, _t: f32) {
         // Similar to Create but in reverse - handled during draw
-        let _ = self.easing.apply(t);
+    }
+
+    fn draw_progress(&self, t: f32) -> Option<f32> {
+        Some(1.0 - self.easing.apply(t))
     }
 
     fn clone_box(&self) -> Box<dyn Animation> {
@@ -4,9 +4,9 @@ pub mod creation;
 pub mod easing;
 pub mod transform;
 
-pub use creation::{Create, FadeIn, FadeOut};
+pub use creation::{Create, FadeIn, FadeOut, Uncreate};
 pub use easing::Easing;
-pub use transform::{MoveTo, Rotate, Scale, Shift};
+pub use transform::{MorphCurve, MoveTo, Rotate, Scale, SetBandwidth, SetBounds, SetRange, Shift};
 
 /// Core trait for all animations
 pub trait Animation: Send + Sync {
@@ -15,8 +15,22 @@ pub trait Animation: Send + Sync {
 
     fn target_id(&self) -> MobjectId;
 
+    /// Set the mobject's animated properties for local progress `t` (0.0-1.0).
+    /// `mobject` is in the state left by every earlier animation, so its current
+    /// values are the animation's starting point.
     fn apply(&self, mobject: &mut dyn Mobject, t: f32);
 
+    /// Partial-drawing progress passed to `Mobject::draw`, for Create-style animations
+    fn draw_progress(&self, _t: f32) -> Option<f32> {
+        None
+    }
+
+    /// Whether this animation brings its mobject into the scene.
+    /// A mobject whose first animation introduces it stays hidden until that animation starts.
+    fn introduces(&self) -> bool {
+        false
+    }
+
     fn clone_box(&self) -> Box<dyn Animation>;
 }
 
@@ -1,5 +1,5 @@
 use super::{Animation, Easing};
-use crate::mobject::{Mobject, MobjectId};
+use crate::mobject::{AxisScale, Mobject, MobjectId};
 use macroquad::prelude::*;
 
 /// Move to an absolute position
@@ -23,6 +23,12 @@ impl MoveTo {
         }
     }
 
+    /// Explicit starting position. Defaults to where the mobject is when the animation starts.
+    pub fn from(mut self, pos: Vec2) -> Self {
+        self.start_pos = Some(pos);
+        self
+    }
+
     pub fn duration(mut self, secs: f32) -> Self {
         self.duration = secs;
         self
@@ -46,7 +52,7 @@ impl Animation for MoveTo {
     fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
         let eased_t = self.easing.apply(t);
 
-        // Capture start position on first application
+        // The timeline replays earlier animations first, so center() is the start position
         let start = self.start_pos.unwrap_or_else(|| mobject.center());
 
         let new_pos = start.lerp(self.destination, eased_t);
@@ -79,6 +85,12 @@ impl Shift {
         }
     }
 
+    /// Explicit starting position. Defaults to where the mobject is when the animation starts.
+    pub fn from(mut self, pos: Vec2) -> Self {
+        self.start_pos = Some(pos);
+        self
+    }
+
     pub fn duration(mut self, secs: f32) -> Self {
         self.duration = secs;
         self
@@ -114,12 +126,13 @@ impl Animation for Shift {
     }
 }
 
-/// Scale animation - requires mobjects to implement scaling
+/// Scale animation - multiplies the mobject's scale by `factor`,
+/// about its own center or an arbitrary pivot
 #[derive(Debug, Clone)]
 pub struct Scale {
     target: MobjectId,
-    #[allow(dead_code)]
     factor: f32,
+    pivot: Option<Vec2>,
     duration: f32,
     easing: Easing,
 }
@@ -129,11 +142,18 @@ impl Scale {
         Self {
             target,
             factor,
+            pivot: None,
             duration: 1.0,
             easing: Easing::Smooth,
         }
     }
 
+    /// Scale about a fixed point instead of the mobject's center (moves the center too)
+    pub fn about(mut self, pivot: Vec2) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+
     pub fn duration(mut self, secs: f32) -> Self {
         self.duration = secs;
         self
@@ -154,9 +174,17 @@ impl Animation for Scale {
         self.target
     }
 
-    fn apply(&self, _mobject: &mut dyn Mobject, t: f32) {
-        let _eased_t = self.easing.apply(t);
-        // 2. Interpolate from 1.0 to self.factor based on eased_t
+    fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
+        let eased_t = self.easing.apply(t);
+
+        // Relative to the scale the mobject had when the animation started
+        let ratio = 1.0 + (self.factor - 1.0) * eased_t;
+        mobject.set_scale(mobject.scale() * ratio);
+
+        if let Some(pivot) = self.pivot {
+            let center = mobject.center();
+            mobject.set_center(pivot + (center - pivot) * ratio);
+        }
     }
 
     fn clone_box(&self) -> Box<dyn Animation> {
@@ -164,12 +192,13 @@ impl Animation for Scale {
     }
 }
 
-/// Rotate animation - requires mobjects to implement rotation
+/// Rotate animation - turns the mobject counter-clockwise by `angle`,
+/// about its own center or an arbitrary pivot
 #[derive(Debug, Clone)]
 pub struct Rotate {
     target: MobjectId,
-    #[allow(dead_code)]
     angle: f32, // radians
+    pivot: Option<Vec2>,
     duration: f32,
     easing: Easing,
 }
@@ -179,6 +208,7 @@ impl Rotate {
         Self {
             target,
             angle,
+            pivot: None,
             duration: 1.0,
             easing: Easing::Smooth,
         }
@@ -189,6 +219,12 @@ impl Rotate {
         Self::new(target, degrees.to_radians())
     }
 
+    /// Rotate about a fixed point instead of the mobject's center (moves the center too)
+    pub fn about(mut self, pivot: Vec2) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+
     pub fn duration(mut self, secs: f32) -> Self {
         self.duration = secs;
         self
@@ -209,12 +245,338 @@ impl Animation for Rotate {
         self.target
     }
 
-    fn apply(&self, _mobject: &mut dyn Mobject, t: f32) {
-        let _eased_t = self.easing.apply(t);
-        // 2. Interpolate from 0.0 to self.angle based on eased_t
+    fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
+        let angle = self.angle * self.easing.apply(t);
+        mobject.set_rotate(mobject.rotation() + angle);
+
+        if let Some(pivot) = self.pivot {
+            let center = mobject.center();
+            mobject.set_center(pivot + Vec2::from_angle(angle).rotate(center - pivot));
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Animation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Animate the data ranges of a coordinate system such as `Axes2D`.
+/// Plots bound to the axes follow along.
+#[derive(Debug, Clone)]
+pub struct SetRange {
+    target: MobjectId,
+    x_range: Option<(f32, f32)>,
+    y_range: Option<(f32, f32)>,
+    duration: f32,
+    easing: Easing,
+}
+
+impl SetRange {
+    pub fn new(target: MobjectId) -> Self {
+        Self {
+            target,
+            x_range: None,
+            y_range: None,
+            duration: 1.0,
+            easing: Easing::Smooth,
+        }
+    }
+
+    pub fn x_range(mut self, min: f32, max: f32) -> Self {
+        self.x_range = Some((min, max));
+        self
+    }
+
+    pub fn y_range(mut self, min: f32, max: f32) -> Self {
+        self.y_range = Some((min, max));
+        self
+    }
+
+    pub fn duration(mut self, secs: f32) -> Self {
+        self.duration = secs;
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl Animation for SetRange {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn target_id(&self) -> MobjectId {
+        self.target
+    }
+
+    fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
+        let Some(frame) = mobject.axes_frame() else {
+            return;
+        };
+        let eased_t = self.easing.apply(t);
+        // Interpolate along the axis scale so a log axis zooms evenly per decade
+        let lerp = |scale: &AxisScale, from: (f32, f32), to: Option<(f32, f32)>| match to {
+            Some(to) => (
+                scale.lerp(from.0, to.0, eased_t),
+                scale.lerp(from.1, to.1, eased_t),
+            ),
+            None => from,
+        };
+        mobject.set_ranges(
+            lerp(&frame.x_scale, frame.x_range, self.x_range),
+            lerp(&frame.y_scale, frame.y_range, self.y_range),
+        );
+    }
+
+    fn clone_box(&self) -> Box<dyn Animation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Animate the smoothing bandwidth of a density estimate such as `KdeCurve`.
+/// The bandwidth changes geometrically, so halving and doubling take equally long.
+/// A target that is not a positive, finite bandwidth leaves the bandwidth unchanged.
+#[derive(Debug, Clone)]
+pub struct SetBandwidth {
+    target: MobjectId,
+    /// None for an unusable target
+    bandwidth: Option<f32>,
+    duration: f32,
+    easing: Easing,
+}
+
+impl SetBandwidth {
+    pub fn new(target: MobjectId, bandwidth: f32) -> Self {
+        Self {
+            target,
+            bandwidth: (bandwidth > 0.0 && bandwidth.is_finite()).then_some(bandwidth),
+            duration: 1.0,
+            easing: Easing::Smooth,
+        }
+    }
+
+    pub fn duration(mut self, secs: f32) -> Self {
+        self.duration = secs;
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl Animation for SetBandwidth {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn target_id(&self) -> MobjectId {
+        self.target
+    }
+
+    fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
+        let (Some(from), Some(to)) = (mobject.bandwidth(), self.bandwidth) else {
+            return;
+        };
+        let eased_t = self.easing.apply(t);
+        // Geometric steps need a positive start; otherwise move linearly
+        let bandwidth = if from > 0.0 {
+            from * (to / from).powf(eased_t)
+        } else {
+            from + (to - from) * eased_t
+        };
+        mobject.set_bandwidth(bandwidth);
+    }
+
+    fn clone_box(&self) -> Box<dyn Animation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Animate the x bounds of a shaded region such as `AreaUnder`, e.g. to
+/// sweep a tail probability or grow an integral. Both bounds move linearly
+/// from where they are to `a` and `b`, which should be finite.
+#[derive(Debug, Clone)]
+pub struct SetBounds {
+    target: MobjectId,
+    bounds: (f32, f32),
+    duration: f32,
+    easing: Easing,
+}
+
+impl SetBounds {
+    pub fn new(target: MobjectId, a: f32, b: f32) -> Self {
+        Self {
+            target,
+            bounds: (a, b),
+            duration: 1.0,
+            easing: Easing::Smooth,
+        }
+    }
+
+    pub fn duration(mut self, secs: f32) -> Self {
+        self.duration = secs;
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl Animation for SetBounds {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn target_id(&self) -> MobjectId {
+        self.target
+    }
+
+    fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
+        let Some((a, b)) = mobject.x_bounds() else {
+            return;
+        };
+        let eased_t = self.easing.apply(t);
+        mobject.set_x_bounds(
+            a + (self.bounds.0 - a) * eased_t,
+            b + (self.bounds.1 - b) * eased_t,
+        );
+    }
+
+    fn clone_box(&self) -> Box<dyn Animation> {
+        Box::new(self.clone())
+    }
+}
+
+/// Morph a curve through a sequence of point sets, spending an equal share of
+/// the duration on each step: e.g. the successive fits of an iterative model.
+/// Point sets may differ in length; the earlier one is resampled to match.
+#[derive(Debug, Clone)]
+pub struct MorphCurve {
+    target: MobjectId,
+    frames: Vec<Vec<Vec2>>,
+    duration: f32,
+    easing: Easing,
+}
+
+impl MorphCurve {
+    /// Morph to a single set of points
+    pub fn new(target: MobjectId, points: Vec<Vec2>) -> Self {
+        Self::through(target, vec![points])
+    }
+
+    /// Morph through each set of points in turn
+    pub fn through(target: MobjectId, frames: Vec<Vec<Vec2>>) -> Self {
+        Self {
+            target,
+            frames,
+            duration: 1.0,
+            easing: Easing::Smooth,
+        }
+    }
+
+    pub fn duration(mut self, secs: f32) -> Self {
+        self.duration = secs;
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl Animation for MorphCurve {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn target_id(&self) -> MobjectId {
+        self.target
+    }
+
+    fn apply(&self, mobject: &mut dyn Mobject, t: f32) {
+        let (Some(start), false) = (mobject.curve_points(), self.frames.is_empty()) else {
+            return;
+        };
+        let position = self.easing.apply(t) * self.frames.len() as f32;
+        let step = (position.floor() as usize).min(self.frames.len() - 1);
+        let local = position - step as f32;
+
+        let to = &self.frames[step];
+        let from = match step {
+            0 => resample(start, to.len()),
+            _ => resample(&self.frames[step - 1], to.len()),
+        };
+        let points = from
+            .iter()
+            .zip(to)
+            .map(|(a, b)| a.lerp(*b, local))
+            .collect();
+        mobject.set_curve_points(points);
     }
 
     fn clone_box(&self) -> Box<dyn Animation> {
         Box::new(self.clone())
     }
 }
+
+/// `n` points spaced evenly by index along `points`, interpolating between them
+fn resample(points: &[Vec2], n: usize) -> Vec<Vec2> {
+    if points.len() == n || points.is_empty() {
+        return points.to_vec();
+    }
+    let last = points.len() - 1;
+    (0..n)
+        .map(|i| {
+            let at = i as f32 * last as f32 / (n.max(2) - 1) as f32;
+            let k = (at.floor() as usize).min(last.saturating_sub(1));
+            match points.get(k + 1) {
+                Some(next) => points[k].lerp(*next, at - k as f32),
+                None => points[k],
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mobject::Rectangle;
+
+    #[test]
+    fn test_scale_about_pivot() {
+        let mut rect = Rectangle::new().at(vec2(10.0, 0.0));
+        rect.set_scale(2.0);
+
+        Scale::new(rect.id(), 3.0)
+            .about(Vec2::ZERO)
+            .easing(Easing::Linear)
+            .apply(&mut rect, 1.0);
+
+        assert!((rect.scale() - 6.0).abs() < 0.001);
+        assert!(rect.center().distance(vec2(30.0, 0.0)) < 0.001);
+    }
+
+    #[test]
+    fn test_rotate_interpolates_from_start() {
+        let mut rect = Rectangle::new().at(vec2(10.0, 0.0));
+        rect.set_rotate(1.0);
+
+        let quarter = std::f32::consts::FRAC_PI_2;
+        Rotate::new(rect.id(), quarter)
+            .about(Vec2::ZERO)
+            .easing(Easing::Linear)
+            .apply(&mut rect, 0.5);
+
+        assert!((rect.rotation() - (1.0 + quarter / 2.0)).abs() < 0.001);
+        let expected = Vec2::from_angle(quarter / 2.0) * 10.0;
+        assert!(rect.center().distance(expected) < 0.001);
+    }
+}
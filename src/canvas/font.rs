@@ -0,0 +1,156 @@
+use macroquad::prelude::*;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// DejaVu Sans, bundled so text renders identically on every backend (see assets/fonts)
+const DEFAULT_FONT_BYTES: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+
+/// A TrueType font shared by all canvas backends.
+/// Cloning is cheap; the parsed font is reference counted.
+#[derive(Clone)]
+pub struct Font {
+    inner: Arc<FontData>,
+}
+
+struct FontData {
+    font: fontdue::Font,
+    bytes: Vec<u8>,
+}
+
+impl Font {
+    /// Parse a font from TTF/OTF bytes
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())
+            .map_err(std::io::Error::other)?;
+        Ok(Self {
+            inner: Arc::new(FontData {
+                font,
+                bytes: bytes.to_vec(),
+            }),
+        })
+    }
+
+    /// Load a TTF/OTF font from disk
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.inner.font.name()
+    }
+
+    /// Measure a single line of text at the given pixel size
+    pub fn measure(&self, text: &str, font_size: f32) -> TextMetrics {
+        let width = self
+            .layout(text, font_size)
+            .last()
+            .map(|&(c, x)| x + self.inner.font.metrics(c, font_size).advance_width)
+            .unwrap_or(0.0);
+
+        let (ascent, descent) = self
+            .inner
+            .font
+            .horizontal_line_metrics(font_size)
+            .map(|m| (m.ascent, -m.descent))
+            .unwrap_or((font_size * 0.8, font_size * 0.2));
+
+        TextMetrics {
+            width,
+            ascent,
+            descent,
+        }
+    }
+
+    /// Height of the inked glyphs above and below the baseline (tighter than line metrics)
+    pub fn ink_extent(&self, text: &str, font_size: f32) -> (f32, f32) {
+        text.chars()
+            .map(|c| self.inner.font.metrics(c, font_size))
+            .filter(|m| m.height > 0)
+            .fold((0.0, 0.0), |(ascent, descent), m| {
+                let top = m.ymin as f32 + m.height as f32;
+                (f32::max(ascent, top), f32::max(descent, -m.ymin as f32))
+            })
+    }
+
+    /// Pen x-offset of each character on a single line, including kerning
+    pub(crate) fn layout(&self, text: &str, font_size: f32) -> Vec<(char, f32)> {
+        let mut pen = 0.0;
+        let mut prev: Option<char> = None;
+        let mut glyphs = Vec::with_capacity(text.len());
+
+        for c in text.chars() {
+            if let Some(p) = prev {
+                pen += self
+                    .inner
+                    .font
+                    .horizontal_kern(p, c, font_size)
+                    .unwrap_or(0.0);
+            }
+            glyphs.push((c, pen));
+            pen += self.inner.font.metrics(c, font_size).advance_width;
+            prev = Some(c);
+        }
+        glyphs
+    }
+
+    pub(crate) fn rasterize(&self, c: char, font_size: f32) -> (fontdue::Metrics, Vec<u8>) {
+        self.inner.font.rasterize(c, font_size)
+    }
+
+    /// Raw TTF/OTF bytes the font was parsed from
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.inner.bytes
+    }
+
+    /// Identity of the underlying font data, used by backends to cache per-font resources
+    pub(crate) fn key(&self) -> usize {
+        Arc::as_ptr(&self.inner) as usize
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        static DEFAULT: OnceLock<Font> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Font::from_bytes(DEFAULT_FONT_BYTES).expect("bundled font is valid"))
+            .clone()
+    }
+}
+
+impl std::fmt::Debug for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Font").field("name", &self.name()).finish()
+    }
+}
+
+/// Size of a line of text. `ascent` is above the baseline, `descent` below it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMetrics {
+    pub width: f32,
+    pub ascent: f32,
+    pub descent: f32,
+}
+
+impl TextMetrics {
+    pub fn height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+}
+
+/// How a run of text is drawn
+#[derive(Debug, Clone)]
+pub struct TextStyle {
+    pub font: Font,
+    pub font_size: f32,
+    pub color: Color,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font: Font::default(),
+            font_size: 20.0,
+            color: WHITE,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+pub mod font;
+pub mod raster;
+pub mod recorder;
+pub mod svg;
+pub mod transform;
+
+pub use font::{Font, TextMetrics, TextStyle};
+pub use raster::RasterCanvas;
+pub use recorder::{DrawCommand, RecordingCanvas};
+pub use svg::SvgCanvas;
+pub use transform::TransformedCanvas;
+
+/// Drawing backend for mobjects.
+/// Coordinates are in screen space: (0,0) is top-left and Y increases downward,
+/// matching macroquad's immediate-mode API.
+pub trait Canvas {
+    fn clear(&mut self, color: Color);
+
+    fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color);
+    fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color);
+
+    fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color);
+    fn draw_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color);
+
+    fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color);
+    fn draw_rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        thickness: f32,
+        color: Color,
+    );
+
+    /// Draw a single line of text with its baseline starting at (x, y)
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, style: &TextStyle);
+
+    /// Draw connected line segments through `points`
+    fn draw_polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        for pair in points.windows(2) {
+            self.draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, thickness, color);
+        }
+    }
+
+    /// Fill a simple (possibly concave) polygon
+    fn draw_polygon(&mut self, points: &[Vec2], color: Color) {
+        for [a, b, c] in triangulate(points) {
+            self.draw_triangle(points[a], points[b], points[c], color);
+        }
+    }
+}
+
+/// Canvas that forwards to macroquad's global draw functions (the live window)
+#[derive(Default)]
+pub struct MacroquadCanvas {
+    /// Fonts uploaded to macroquad, keyed by `Font::key`
+    fonts: HashMap<usize, (Font, macroquad::text::Font)>,
+}
+
+impl MacroquadCanvas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Canvas for MacroquadCanvas {
+    fn clear(&mut self, color: Color) {
+        clear_background(color);
+    }
+
+    fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        draw_line(x1, y1, x2, y2, thickness, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        draw_triangle(v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        draw_circle(x, y, r, color);
+    }
+
+    fn draw_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        draw_circle_lines(x, y, r, thickness, color);
+    }
+
+    fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        draw_rectangle(x, y, w, h, color);
+    }
+
+    fn draw_rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        draw_rectangle_lines(x, y, w, h, thickness, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, style: &TextStyle) {
+        let (_, font) = self.fonts.entry(style.font.key()).or_insert_with(|| {
+            let loaded = load_ttf_font_from_bytes(style.font.bytes())
+                .expect("font was already parsed successfully");
+            (style.font.clone(), loaded)
+        });
+
+        draw_text_ex(
+            text,
+            x,
+            y,
+            TextParams {
+                font: Some(font),
+                font_size: style.font_size.round() as u16,
+                color: style.color,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Triangulate a simple polygon by ear clipping.
+/// Returns index triples into `points`; works for either winding order.
+pub fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    // Signed area tells us the winding so convexity tests work both ways
+    let area: f32 = (0..n)
+        .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+        .sum();
+    let sign = if area >= 0.0 { 1.0 } else { -1.0 };
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut triangles = Vec::with_capacity(n - 2);
+    let mut i = 0;
+    let mut since_last_ear = 0;
+
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let (ia, ib, ic) = (
+            remaining[(i + m - 1) % m],
+            remaining[i % m],
+            remaining[(i + 1) % m],
+        );
+        let (a, b, c) = (points[ia], points[ib], points[ic]);
+
+        let convex = (b - a).perp_dot(c - b) * sign > 0.0;
+        let is_ear = convex
+            && remaining
+                .iter()
+                .all(|&j| j == ia || j == ib || j == ic || !point_in_triangle(points[j], a, b, c));
+
+        // Degenerate input (collinear or self-intersecting) may have no ears left:
+        // after a full pass without one, clip the current vertex anyway
+        if is_ear || since_last_ear > m {
+            triangles.push([ia, ib, ic]);
+            remaining.remove(i % m);
+            since_last_ear = 0;
+        } else {
+            i += 1;
+            since_last_ear += 1;
+        }
+        i %= remaining.len();
+    }
+
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+fn point_in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let d1 = (b - a).perp_dot(p - a);
+    let d2 = (c - b).perp_dot(p - b);
+    let d3 = (a - c).perp_dot(p - c);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle_area(points: &[Vec2], [a, b, c]: [usize; 3]) -> f32 {
+        ((points[b] - points[a]).perp_dot(points[c] - points[a]) / 2.0).abs()
+    }
+
+    #[test]
+    fn test_triangulate_concave_polygon() {
+        let l_shape = [
+            vec2(0.0, 0.0),
+            vec2(2.0, 0.0),
+            vec2(2.0, 1.0),
+            vec2(1.0, 1.0),
+            vec2(1.0, 2.0),
+            vec2(0.0, 2.0),
+        ];
+
+        for points in [l_shape.to_vec(), l_shape.iter().rev().copied().collect()] {
+            let triangles = triangulate(&points);
+            assert_eq!(triangles.len(), 4);
+
+            let area: f32 = triangles.iter().map(|t| triangle_area(&points, *t)).sum();
+            assert!((area - 3.0).abs() < 0.001);
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use super::{Canvas, TextStyle};
+use macroquad::prelude::*;
+use std::path::Path;
+
+/// CPU rasterizer that draws into an in-memory RGBA8 framebuffer.
+/// Needs no window or GPU, so scenes can be rendered in headless batch jobs.
+/// Pixels are sampled at their centers (no anti-aliasing).
+#[derive(Debug, Clone)]
+pub struct RasterCanvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RasterCanvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Center of the framebuffer in screen coordinates
+    pub fn screen_center(&self) -> Vec2 {
+        vec2(self.width as f32 / 2.0, self.height as f32 / 2.0)
+    }
+
+    /// Raw RGBA8 bytes, row-major from the top-left corner
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let i = self.index(x, y);
+        Color::from_rgba(
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        )
+    }
+
+    /// Write the framebuffer to a PNG file
+    pub fn save_png(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        image::save_buffer(
+            path,
+            &self.pixels,
+            self.width,
+            self.height,
+            image::ColorType::Rgba8,
+        )
+        .map_err(std::io::Error::other)
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    /// Source-over blend a color into a single pixel
+    fn blend(&mut self, x: u32, y: u32, color: Color) {
+        let i = self.index(x, y);
+        let dst = &mut self.pixels[i..i + 4];
+        let a = color.a.clamp(0.0, 1.0);
+        let dst_a = dst[3] as f32 / 255.0;
+        let out_a = a + dst_a * (1.0 - a);
+        if out_a <= 0.0 {
+            return;
+        }
+
+        let src = [color.r, color.g, color.b];
+        for c in 0..3 {
+            let d = dst[c] as f32 / 255.0;
+            let v = (src[c].clamp(0.0, 1.0) * a + d * dst_a * (1.0 - a)) / out_a;
+            dst[c] = (v * 255.0).round() as u8;
+        }
+        dst[3] = (out_a * 255.0).round() as u8;
+    }
+
+    /// Pixel index range covering [min, max) in one dimension, clipped to the framebuffer
+    fn span(min: f32, max: f32, limit: u32) -> std::ops::Range<u32> {
+        let lo = (min - 0.5).ceil().max(0.0) as u32;
+        let hi = ((max - 0.5).floor() + 1.0).clamp(0.0, limit as f32) as u32;
+        lo..hi.max(lo)
+    }
+
+    /// Fill every pixel whose center satisfies `inside`, within a bounding box
+    fn fill_where(&mut self, min: Vec2, max: Vec2, color: Color, inside: impl Fn(Vec2) -> bool) {
+        if color.a <= 0.0 {
+            return;
+        }
+        for y in Self::span(min.y, max.y, self.height) {
+            for x in Self::span(min.x, max.x, self.width) {
+                if inside(vec2(x as f32 + 0.5, y as f32 + 0.5)) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+/// Edge function: positive when `p` is to the left of a->b
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Top-left fill rule so adjacent triangles never blend a shared edge twice
+fn is_top_left(a: Vec2, b: Vec2) -> bool {
+    let d = b - a;
+    (d.y == 0.0 && d.x > 0.0) || d.y < 0.0
+}
+
+impl Canvas for RasterCanvas {
+    fn clear(&mut self, color: Color) {
+        let rgba: [u8; 4] = color.into();
+        for px in self.pixels.chunks_exact_mut(4) {
+            px.copy_from_slice(&rgba);
+        }
+    }
+
+    fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let a = vec2(x1, y1);
+        let b = vec2(x2, y2);
+        let dir = b - a;
+        if dir.length_squared() == 0.0 {
+            return;
+        }
+        let offset = vec2(-dir.y, dir.x).normalize() * thickness / 2.0;
+
+        self.draw_triangle(a + offset, b + offset, b - offset, color);
+        self.draw_triangle(a + offset, b - offset, a - offset, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        // Normalize winding so the interior has positive edge functions
+        let (v2, v3) = if edge(v1, v2, v3) < 0.0 {
+            (v3, v2)
+        } else {
+            (v2, v3)
+        };
+        if edge(v1, v2, v3) == 0.0 {
+            return;
+        }
+
+        let edges = [(v1, v2), (v2, v3), (v3, v1)];
+        let min = v1.min(v2).min(v3);
+        let max = v1.max(v2).max(v3);
+
+        self.fill_where(min, max, color, |p| {
+            edges.iter().all(|&(a, b)| {
+                let w = edge(a, b, p);
+                w > 0.0 || (w == 0.0 && !is_top_left(a, b))
+            })
+        });
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        let c = vec2(x, y);
+        self.fill_where(c - Vec2::splat(r), c + Vec2::splat(r), color, |p| {
+            p.distance_squared(c) <= r * r
+        });
+    }
+
+    fn draw_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        let c = vec2(x, y);
+        let half = thickness / 2.0;
+        let outer = Vec2::splat(r + half);
+        self.fill_where(c - outer, c + outer, color, |p| {
+            (p.distance(c) - r).abs() <= half
+        });
+    }
+
+    fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        let min = vec2(x, y);
+        let max = vec2(x + w, y + h);
+        self.fill_where(min, max, color, |_| true);
+    }
+
+    fn draw_rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        // Border drawn inside the rectangle as four non-overlapping strips
+        let t = thickness.min(w / 2.0).min(h / 2.0);
+        self.draw_rectangle(x, y, w, t, color);
+        self.draw_rectangle(x, y + h - t, w, t, color);
+        self.draw_rectangle(x, y + t, t, h - 2.0 * t, color);
+        self.draw_rectangle(x + w - t, y + t, t, h - 2.0 * t, color);
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, style: &TextStyle) {
+        for (c, pen) in style.font.layout(text, style.font_size) {
+            let (metrics, coverage) = style.font.rasterize(c, style.font_size);
+            if metrics.width == 0 {
+                continue;
+            }
+
+            // Glyph bitmaps are top-down; ymin is the offset of the bottom edge from the baseline
+            let left = (x + pen).round() as i64 + metrics.xmin as i64;
+            let top = y.round() as i64 - metrics.height as i64 - metrics.ymin as i64;
+
+            for (row, line) in coverage.chunks_exact(metrics.width).enumerate() {
+                for (col, &cov) in line.iter().enumerate() {
+                    let (px, py) = (left + col as i64, top + row as i64);
+                    if cov == 0 || px < 0 || py < 0 {
+                        continue;
+                    }
+                    if px >= self.width as i64 || py >= self.height as i64 {
+                        continue;
+                    }
+                    let mut color = style.color;
+                    color.a *= cov as f32 / 255.0;
+                    self.blend(px as u32, py as u32, color);
+                }
+            }
+        }
+    }
+
+    /// Scanline fill with the even-odd rule, so concave polygons need no triangulation
+    fn draw_polygon(&mut self, points: &[Vec2], color: Color) {
+        if points.len() < 3 || color.a <= 0.0 {
+            return;
+        }
+        let min = points
+            .iter()
+            .fold(Vec2::splat(f32::MAX), |acc, p| acc.min(*p));
+        let max = points
+            .iter()
+            .fold(Vec2::splat(f32::MIN), |acc, p| acc.max(*p));
+
+        let mut crossings = Vec::new();
+        for y in Self::span(min.y, max.y, self.height) {
+            let sy = y as f32 + 0.5;
+            crossings.clear();
+            for i in 0..points.len() {
+                let a = points[i];
+                let b = points[(i + 1) % points.len()];
+                // Half-open test so vertices shared by two edges count once
+                if (a.y <= sy) != (b.y <= sy) {
+                    crossings.push(a.x + (sy - a.y) / (b.y - a.y) * (b.x - a.x));
+                }
+            }
+            crossings.sort_by(|a, b| a.total_cmp(b));
+
+            for pair in crossings.chunks_exact(2) {
+                for x in Self::span(pair[0], pair[1], self.width) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_and_rectangle() {
+        let mut canvas = RasterCanvas::new(10, 10);
+        canvas.clear(BLACK);
+        canvas.draw_rectangle(2.0, 2.0, 4.0, 4.0, WHITE);
+
+        assert_eq!(canvas.pixel(3, 3), WHITE);
+        assert_eq!(canvas.pixel(6, 6), BLACK);
+        assert_eq!(canvas.pixel(1, 1), BLACK);
+    }
+
+    #[test]
+    fn test_shared_triangle_edge_blends_once() {
+        let mut canvas = RasterCanvas::new(8, 8);
+        canvas.clear(BLACK);
+        let half_white = Color::new(1.0, 1.0, 1.0, 0.5);
+
+        // Two triangles forming a quad share the diagonal
+        let (a, b, c, d) = (
+            vec2(0.0, 0.0),
+            vec2(8.0, 0.0),
+            vec2(8.0, 8.0),
+            vec2(0.0, 8.0),
+        );
+        canvas.draw_triangle(a, b, c, half_white);
+        canvas.draw_triangle(a, c, d, half_white);
+
+        let expected = canvas.pixel(1, 6);
+        for i in 0..8 {
+            assert_eq!(canvas.pixel(i, i), expected);
+        }
+    }
+
+    #[test]
+    fn test_concave_polygon_fill() {
+        let mut canvas = RasterCanvas::new(10, 10);
+        canvas.clear(BLACK);
+        // L-shape: the top-right quadrant is outside
+        let l_shape = [
+            vec2(0.0, 0.0),
+            vec2(5.0, 0.0),
+            vec2(5.0, 5.0),
+            vec2(10.0, 5.0),
+            vec2(10.0, 10.0),
+            vec2(0.0, 10.0),
+        ];
+        canvas.draw_polygon(&l_shape, WHITE);
+
+        assert_eq!(canvas.pixel(2, 2), WHITE);
+        assert_eq!(canvas.pixel(8, 8), WHITE);
+        assert_eq!(canvas.pixel(8, 2), BLACK);
+    }
+
+    #[test]
+    fn test_text_draws_above_baseline() {
+        let mut canvas = RasterCanvas::new(60, 40);
+        let style = TextStyle {
+            font_size: 24.0,
+            ..Default::default()
+        };
+        canvas.draw_text("Hi", 5.0, 30.0, &style);
+
+        let inked = |rows: std::ops::Range<u32>| {
+            rows.flat_map(|y| (0..60).map(move |x| (x, y)))
+                .any(|(x, y)| canvas.pixel(x, y).a > 0.0)
+        };
+        assert!(inked(10..30));
+        assert!(!inked(31..40));
+    }
+
+    #[test]
+    fn test_line_covers_pixels() {
+        let mut canvas = RasterCanvas::new(20, 20);
+        canvas.draw_line(0.0, 10.0, 20.0, 10.0, 2.0, WHITE);
+
+        assert_eq!(canvas.pixel(5, 10), WHITE);
+        assert_eq!(canvas.pixel(5, 9), WHITE);
+        assert_eq!(canvas.pixel(5, 5).a, 0.0);
+    }
+}
@@ -0,0 +1,172 @@
+use super::{Canvas, TextStyle};
+use macroquad::prelude::*;
+
+/// A single draw call captured by `RecordingCanvas`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    Clear(Color),
+    Line {
+        from: Vec2,
+        to: Vec2,
+        thickness: f32,
+        color: Color,
+    },
+    Triangle {
+        vertices: [Vec2; 3],
+        color: Color,
+    },
+    Circle {
+        center: Vec2,
+        radius: f32,
+        color: Color,
+    },
+    CircleLines {
+        center: Vec2,
+        radius: f32,
+        thickness: f32,
+        color: Color,
+    },
+    Rectangle {
+        position: Vec2,
+        size: Vec2,
+        color: Color,
+    },
+    RectangleLines {
+        position: Vec2,
+        size: Vec2,
+        thickness: f32,
+        color: Color,
+    },
+    Polyline {
+        points: Vec<Vec2>,
+        thickness: f32,
+        color: Color,
+    },
+    Polygon {
+        points: Vec<Vec2>,
+        color: Color,
+    },
+    Text {
+        text: String,
+        position: Vec2,
+        font_size: f32,
+        color: Color,
+    },
+}
+
+/// Canvas that records draw calls instead of drawing them.
+/// Useful for asserting on what a mobject draws in tests.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingCanvas {
+    commands: Vec<DrawCommand>,
+}
+
+impl RecordingCanvas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn clear_commands(&mut self) {
+        self.commands.clear();
+    }
+}
+
+impl Canvas for RecordingCanvas {
+    fn clear(&mut self, color: Color) {
+        self.commands.push(DrawCommand::Clear(color));
+    }
+
+    fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        self.commands.push(DrawCommand::Line {
+            from: vec2(x1, y1),
+            to: vec2(x2, y2),
+            thickness,
+            color,
+        });
+    }
+
+    fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        self.commands.push(DrawCommand::Triangle {
+            vertices: [v1, v2, v3],
+            color,
+        });
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.commands.push(DrawCommand::Circle {
+            center: vec2(x, y),
+            radius: r,
+            color,
+        });
+    }
+
+    fn draw_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        self.commands.push(DrawCommand::CircleLines {
+            center: vec2(x, y),
+            radius: r,
+            thickness,
+            color,
+        });
+    }
+
+    fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.commands.push(DrawCommand::Rectangle {
+            position: vec2(x, y),
+            size: vec2(w, h),
+            color,
+        });
+    }
+
+    fn draw_rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        self.commands.push(DrawCommand::RectangleLines {
+            position: vec2(x, y),
+            size: vec2(w, h),
+            thickness,
+            color,
+        });
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, style: &TextStyle) {
+        self.commands.push(DrawCommand::Text {
+            text: text.to_string(),
+            position: vec2(x, y),
+            font_size: style.font_size,
+            color: style.color,
+        });
+    }
+
+    fn draw_polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        self.commands.push(DrawCommand::Polyline {
+            points: points.to_vec(),
+            thickness,
+            color,
+        });
+    }
+
+    fn draw_polygon(&mut self, points: &[Vec2], color: Color) {
+        self.commands.push(DrawCommand::Polygon {
+            points: points.to_vec(),
+            color,
+        });
+    }
+}
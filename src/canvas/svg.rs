@@ -0,0 +1,286 @@
+use super::{Canvas, TextStyle};
+use macroquad::prelude::*;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Canvas that serializes draw calls into a standalone SVG document.
+/// Coordinates are already in screen space, so they map 1:1 onto the SVG user space.
+#[derive(Debug, Clone)]
+pub struct SvgCanvas {
+    width: u32,
+    height: u32,
+    elements: Vec<String>,
+}
+
+impl SvgCanvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            elements: Vec::new(),
+        }
+    }
+
+    /// Center of the document in screen coordinates
+    pub fn screen_center(&self) -> Vec2 {
+        vec2(self.width as f32 / 2.0, self.height as f32 / 2.0)
+    }
+
+    /// The complete SVG document
+    pub fn to_svg_string(&self) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = self.width,
+            h = self.height,
+        );
+        for element in &self.elements {
+            let _ = writeln!(svg, "  {}", element);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_svg_string())
+    }
+
+    fn push(&mut self, element: String) {
+        self.elements.push(element);
+    }
+}
+
+/// Format a coordinate compactly (at most two decimals, no trailing zeros)
+fn num(v: f32) -> String {
+    let s = format!("{:.2}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+fn rgb(color: Color) -> String {
+    let [r, g, b, _]: [u8; 4] = color.into();
+    format!("rgb({},{},{})", r, g, b)
+}
+
+fn fill(color: Color) -> String {
+    format!(
+        r#"fill="{}" fill-opacity="{}""#,
+        rgb(color),
+        num(color.a.clamp(0.0, 1.0))
+    )
+}
+
+fn stroke(color: Color, thickness: f32) -> String {
+    format!(
+        r#"stroke="{}" stroke-opacity="{}" stroke-width="{}""#,
+        rgb(color),
+        num(color.a.clamp(0.0, 1.0)),
+        num(thickness)
+    )
+}
+
+fn points_attr(points: &[Vec2]) -> String {
+    points
+        .iter()
+        .map(|p| format!("{},{}", num(p.x), num(p.y)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Canvas for SvgCanvas {
+    /// Clearing discards everything drawn so far and paints the background
+    fn clear(&mut self, color: Color) {
+        self.elements.clear();
+        if color.a > 0.0 {
+            let element = format!(
+                r#"<rect x="0" y="0" width="{}" height="{}" {}/>"#,
+                self.width,
+                self.height,
+                fill(color)
+            );
+            self.push(element);
+        }
+    }
+
+    fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        if color.a <= 0.0 {
+            return;
+        }
+        self.push(format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#,
+            num(x1),
+            num(y1),
+            num(x2),
+            num(y2),
+            stroke(color, thickness)
+        ));
+    }
+
+    fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        self.draw_polygon(&[v1, v2, v3], color);
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        if color.a <= 0.0 {
+            return;
+        }
+        self.push(format!(
+            r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+            num(x),
+            num(y),
+            num(r),
+            fill(color)
+        ));
+    }
+
+    fn draw_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        if color.a <= 0.0 {
+            return;
+        }
+        self.push(format!(
+            r#"<circle cx="{}" cy="{}" r="{}" fill="none" {}/>"#,
+            num(x),
+            num(y),
+            num(r),
+            stroke(color, thickness)
+        ));
+    }
+
+    fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        if color.a <= 0.0 {
+            return;
+        }
+        let (x, y, w, h) = normalized(x, y, w, h);
+        self.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+            num(x),
+            num(y),
+            num(w),
+            num(h),
+            fill(color)
+        ));
+    }
+
+    fn draw_rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        if color.a <= 0.0 {
+            return;
+        }
+        // Inset by half the stroke so the border stays inside the rectangle, like the
+        // raster; a stroke wider than the rectangle collapses onto its middle
+        let (x, y, w, h) = normalized(x, y, w, h);
+        let inset = vec2(
+            (thickness / 2.0).min(w / 2.0),
+            (thickness / 2.0).min(h / 2.0),
+        )
+        .max(Vec2::ZERO);
+        self.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" {}/>"#,
+            num(x + inset.x),
+            num(y + inset.y),
+            num(w - 2.0 * inset.x),
+            num(h - 2.0 * inset.y),
+            stroke(color, thickness)
+        ));
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, style: &TextStyle) {
+        if style.color.a <= 0.0 || text.is_empty() {
+            return;
+        }
+        let family = style.font.name().unwrap_or("sans-serif");
+        self.push(format!(
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" {} xml:space="preserve">{}</text>"#,
+            num(x),
+            num(y),
+            escape(family),
+            num(style.font_size),
+            fill(style.color),
+            escape(text)
+        ));
+    }
+
+    fn draw_polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        if color.a <= 0.0 || points.len() < 2 {
+            return;
+        }
+        self.push(format!(
+            r#"<polyline points="{}" fill="none" stroke-linejoin="round" {}/>"#,
+            points_attr(points),
+            stroke(color, thickness)
+        ));
+    }
+
+    fn draw_polygon(&mut self, points: &[Vec2], color: Color) {
+        if color.a <= 0.0 || points.len() < 3 {
+            return;
+        }
+        self.push(format!(
+            r#"<polygon points="{}" fill-rule="evenodd" {}/>"#,
+            points_attr(points),
+            fill(color)
+        ));
+    }
+}
+
+/// A rectangle as its top left corner and non-negative size
+fn normalized(x: f32, y: f32, w: f32, h: f32) -> (f32, f32, f32, f32) {
+    (x.min(x + w), y.min(y + h), w.abs(), h.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_document() {
+        let mut canvas = SvgCanvas::new(100, 50);
+        canvas.clear(BLACK);
+        canvas.draw_circle(10.0, 20.0, 5.5, Color::new(1.0, 0.0, 0.0, 0.5));
+        canvas.draw_text("a < b", 0.0, 40.0, &TextStyle::default());
+
+        let svg = canvas.to_svg_string();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100""#));
+        assert!(svg.contains(
+            r#"<circle cx="10" cy="20" r="5.5" fill="rgb(255,0,0)" fill-opacity="0.5"/>"#
+        ));
+        assert!(svg.contains(">a &lt; b</text>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_transparent_shapes_are_skipped() {
+        let mut canvas = SvgCanvas::new(10, 10);
+        canvas.draw_line(0.0, 0.0, 5.0, 5.0, 1.0, Color::new(1.0, 1.0, 1.0, 0.0));
+        assert!(!canvas.to_svg_string().contains("<line"));
+    }
+
+    #[test]
+    fn test_rectangles_have_non_negative_sizes() {
+        let mut canvas = SvgCanvas::new(10, 10);
+        canvas.draw_rectangle(5.0, 6.0, -2.0, -3.0, WHITE);
+        canvas.draw_rectangle_lines(1.0, 1.0, 1.0, 1.0, 4.0, WHITE);
+        let svg = canvas.to_svg_string();
+        assert!(svg.contains(r#"<rect x="3" y="3" width="2" height="3""#));
+        assert!(svg.contains(r#"<rect x="1.5" y="1.5" width="0" height="0" fill="none""#));
+    }
+}
@@ -0,0 +1,215 @@
+use super::{Canvas, TextStyle};
+use macroquad::prelude::*;
+
+/// Number of segments used when a circle has to be turned into a polygon
+const CIRCLE_SEGMENTS: usize = 64;
+
+/// Canvas adaptor that applies an affine transform (in screen space) to everything
+/// drawn through it before forwarding to the wrapped canvas.
+/// Adaptors nest, so transforms compose from the innermost outwards.
+pub struct TransformedCanvas<'a> {
+    inner: &'a mut dyn Canvas,
+    transform: Affine2,
+}
+
+impl<'a> TransformedCanvas<'a> {
+    pub fn new(inner: &'a mut dyn Canvas, transform: Affine2) -> Self {
+        Self { inner, transform }
+    }
+
+    /// Scale by `scale` and rotate by `angle` (radians, counter-clockwise on screen)
+    /// around `pivot`, all in screen coordinates
+    pub fn about(inner: &'a mut dyn Canvas, pivot: Vec2, scale: f32, angle: f32) -> Self {
+        Self::new(inner, Self::transform_about(pivot, scale, angle))
+    }
+
+    /// The transform used by `about`, for composing with other transforms
+    pub fn transform_about(pivot: Vec2, scale: f32, angle: f32) -> Affine2 {
+        // Screen Y points down, so a visually counter-clockwise turn is a negative angle
+        Affine2::from_translation(pivot)
+            * Affine2::from_scale_angle_translation(Vec2::splat(scale), -angle, Vec2::ZERO)
+            * Affine2::from_translation(-pivot)
+    }
+
+    pub fn transform(&self) -> Affine2 {
+        self.transform
+    }
+
+    fn map(&self, p: Vec2) -> Vec2 {
+        self.transform.transform_point2(p)
+    }
+
+    /// Length scale factor (exact for similarity transforms)
+    fn length_scale(&self) -> f32 {
+        self.transform.matrix2.determinant().abs().sqrt()
+    }
+
+    /// Uniform scale + rotation, so circles stay circles
+    fn is_similarity(&self) -> bool {
+        let m = self.transform.matrix2;
+        (m.x_axis.length() - m.y_axis.length()).abs() < 1e-4 && m.x_axis.dot(m.y_axis).abs() < 1e-4
+    }
+
+    /// No rotation or shear, so axis-aligned rectangles stay axis-aligned
+    fn is_axis_aligned(&self) -> bool {
+        let m = self.transform.matrix2;
+        m.x_axis.y.abs() < 1e-6 && m.y_axis.x.abs() < 1e-6
+    }
+
+    fn circle_points(&self, center: Vec2, r: f32) -> Vec<Vec2> {
+        (0..CIRCLE_SEGMENTS)
+            .map(|i| {
+                let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+                self.map(center + vec2(angle.cos(), angle.sin()) * r)
+            })
+            .collect()
+    }
+
+    fn rect_points(&self, x: f32, y: f32, w: f32, h: f32) -> Vec<Vec2> {
+        [
+            vec2(x, y),
+            vec2(x + w, y),
+            vec2(x + w, y + h),
+            vec2(x, y + h),
+        ]
+        .into_iter()
+        .map(|p| self.map(p))
+        .collect()
+    }
+}
+
+impl Canvas for TransformedCanvas<'_> {
+    fn clear(&mut self, color: Color) {
+        self.inner.clear(color);
+    }
+
+    fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let a = self.map(vec2(x1, y1));
+        let b = self.map(vec2(x2, y2));
+        let thickness = thickness * self.length_scale();
+        self.inner.draw_line(a.x, a.y, b.x, b.y, thickness, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vec2, v2: Vec2, v3: Vec2, color: Color) {
+        let (v1, v2, v3) = (self.map(v1), self.map(v2), self.map(v3));
+        self.inner.draw_triangle(v1, v2, v3, color);
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        if self.is_similarity() {
+            let c = self.map(vec2(x, y));
+            self.inner
+                .draw_circle(c.x, c.y, r * self.length_scale(), color);
+        } else {
+            let points = self.circle_points(vec2(x, y), r);
+            self.inner.draw_polygon(&points, color);
+        }
+    }
+
+    fn draw_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        let s = self.length_scale();
+        if self.is_similarity() {
+            let c = self.map(vec2(x, y));
+            self.inner
+                .draw_circle_lines(c.x, c.y, r * s, thickness * s, color);
+        } else {
+            let mut points = self.circle_points(vec2(x, y), r);
+            points.push(points[0]);
+            self.inner.draw_polyline(&points, thickness * s, color);
+        }
+    }
+
+    fn draw_rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        let points = self.rect_points(x, y, w, h);
+        if self.is_axis_aligned() {
+            let min = points[0].min(points[2]);
+            let size = (points[2] - points[0]).abs();
+            self.inner
+                .draw_rectangle(min.x, min.y, size.x, size.y, color);
+        } else {
+            self.inner.draw_polygon(&points, color);
+        }
+    }
+
+    fn draw_rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        let mut points = self.rect_points(x, y, w, h);
+        let thickness = thickness * self.length_scale();
+        if self.is_axis_aligned() {
+            let min = points[0].min(points[2]);
+            let size = (points[2] - points[0]).abs();
+            self.inner
+                .draw_rectangle_lines(min.x, min.y, size.x, size.y, thickness, color);
+        } else {
+            points.push(points[0]);
+            self.inner.draw_polyline(&points, thickness, color);
+        }
+    }
+
+    /// Text is repositioned and resized but always drawn upright
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, style: &TextStyle) {
+        let p = self.map(vec2(x, y));
+        let style = TextStyle {
+            font_size: style.font_size * self.length_scale(),
+            ..style.clone()
+        };
+        self.inner.draw_text(text, p.x, p.y, &style);
+    }
+
+    fn draw_polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        let points: Vec<Vec2> = points.iter().map(|p| self.map(*p)).collect();
+        let thickness = thickness * self.length_scale();
+        self.inner.draw_polyline(&points, thickness, color);
+    }
+
+    fn draw_polygon(&mut self, points: &[Vec2], color: Color) {
+        let points: Vec<Vec2> = points.iter().map(|p| self.map(*p)).collect();
+        self.inner.draw_polygon(&points, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::{DrawCommand, RecordingCanvas};
+
+    #[test]
+    fn test_scale_about_pivot() {
+        let mut recorder = RecordingCanvas::new();
+        {
+            let mut canvas = TransformedCanvas::about(&mut recorder, vec2(10.0, 10.0), 2.0, 0.0);
+            canvas.draw_circle(15.0, 10.0, 3.0, WHITE);
+        }
+
+        match &recorder.commands()[0] {
+            DrawCommand::Circle { center, radius, .. } => {
+                assert!(center.distance(vec2(20.0, 10.0)) < 0.001);
+                assert!((radius - 6.0).abs() < 0.001);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rotation_is_counter_clockwise_on_screen() {
+        let mut recorder = RecordingCanvas::new();
+        {
+            let angle = std::f32::consts::FRAC_PI_2;
+            let mut canvas = TransformedCanvas::about(&mut recorder, Vec2::ZERO, 1.0, angle);
+            canvas.draw_line(0.0, 0.0, 10.0, 0.0, 1.0, WHITE);
+        }
+
+        // Pointing right, a quarter turn counter-clockwise points up (negative screen y)
+        match &recorder.commands()[0] {
+            DrawCommand::Line { to, .. } => assert!(to.distance(vec2(0.0, -10.0)) < 0.001),
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Delimited text: CSV and TSV with RFC 4180 quoting
+
+use super::{Cell, Table};
+use std::io;
+
+/// Split delimited text into a table. Fields may be quoted with `"` (doubling
+/// `""` for a literal quote), and quoted fields may span lines.
+pub(crate) fn parse(text: &str, delimiter: char, header: bool) -> io::Result<Table> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut was_quoted = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    let end_field = |field: &mut String, was_quoted: &mut bool, fields: &mut Vec<Cell>| {
+        let text = std::mem::take(field);
+        let text = if *was_quoted {
+            text.as_str()
+        } else {
+            text.trim()
+        };
+        fields.push(if text.is_empty() && !*was_quoted {
+            Cell::Missing
+        } else {
+            Cell::Text(text.to_string())
+        });
+        *was_quoted = false;
+    };
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+                was_quoted = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                end_field(&mut field, &mut was_quoted, &mut fields);
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+            c if c == delimiter => end_field(&mut field, &mut was_quoted, &mut fields),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {}: unterminated quoted field", record_line),
+        ));
+    }
+    if !field.is_empty() || !fields.is_empty() || was_quoted {
+        end_field(&mut field, &mut was_quoted, &mut fields);
+        records.push((record_line, fields));
+    }
+
+    // Blank lines separate nothing
+    records.retain(|(_, fields)| !matches!(fields.as_slice(), [Cell::Missing]));
+    let mut records = records.into_iter();
+    let headers = if header {
+        records.next().map(|(_, fields)| {
+            fields
+                .into_iter()
+                .map(|cell| match cell {
+                    Cell::Text(name) => name,
+                    Cell::Bool(value) => value.to_string(),
+                    Cell::Number(value) => value.to_string(),
+                    Cell::Missing => String::new(),
+                })
+                .collect()
+        })
+    } else {
+        None
+    };
+    Ok(Table {
+        headers,
+        rows: records.collect(),
+    })
+}
@@ -0,0 +1,276 @@
+//! JSON tables: an array of records or an object of columns
+
+use super::{Cell, Table};
+use std::io;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Keys in document order, so columns can also be picked by index
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn into_cell(self) -> Cell {
+        match self {
+            Value::Number(n) => Cell::Number(n),
+            Value::Bool(b) => Cell::Bool(b),
+            Value::String(s) => Cell::Text(s),
+            _ => Cell::Missing,
+        }
+    }
+}
+
+/// Read a table from JSON in either of the layouts data tools commonly write:
+///
+/// - records, `[{"x": 1, "y": 2}, ...]` (pandas `orient="records"`, jsonlite's default)
+/// - columns, `{"x": [1, ...], "y": [2, ...]}` (pandas `orient="list"`)
+///
+/// Columns are named by the keys, in the order they first appear.
+pub(crate) fn parse(text: &str) -> io::Result<Table> {
+    let mut parser = Parser {
+        text,
+        chars: text.char_indices().peekable(),
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if let Some(&(at, _)) = parser.chars.peek() {
+        return Err(parser.error(at, "unexpected text after the table"));
+    }
+
+    match value {
+        Value::Array(records) => {
+            let mut headers: Vec<String> = Vec::new();
+            let mut rows = Vec::new();
+            for (index, record) in records.into_iter().enumerate() {
+                let Value::Object(fields) = record else {
+                    return Err(invalid(format!("record {} is not an object", index + 1)));
+                };
+                let mut row = vec![Cell::Missing; headers.len()];
+                for (key, value) in fields {
+                    let column = match headers.iter().position(|h| *h == key) {
+                        Some(column) => column,
+                        None => {
+                            headers.push(key);
+                            row.push(Cell::Missing);
+                            headers.len() - 1
+                        }
+                    };
+                    row[column] = value.into_cell();
+                }
+                rows.push((index + 1, row));
+            }
+            Ok(Table {
+                headers: Some(headers),
+                rows,
+            })
+        }
+        Value::Object(columns) => {
+            let length = columns
+                .iter()
+                .map(|(_, column)| match column {
+                    Value::Array(values) => values.len(),
+                    _ => 1,
+                })
+                .max()
+                .unwrap_or(0);
+            let mut rows: Vec<(usize, Vec<Cell>)> =
+                (0..length).map(|i| (i + 1, Vec::new())).collect();
+            let mut headers = Vec::new();
+            for (key, column) in columns {
+                let mut values = match column {
+                    Value::Array(values) => values.into_iter(),
+                    _ => return Err(invalid(format!("column \"{}\" is not an array", key))),
+                };
+                headers.push(key);
+                for (_, row) in &mut rows {
+                    row.push(values.next().map_or(Cell::Missing, Value::into_cell));
+                }
+            }
+            Ok(Table {
+                headers: Some(headers),
+                rows,
+            })
+        }
+        _ => Err(invalid(
+            "expected an array of records or an object of columns".to_string(),
+        )),
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn error(&self, at: usize, message: &str) -> io::Error {
+        let line = self.text[..at].matches('\n').count() + 1;
+        invalid(format!("line {}: {}", line, message))
+    }
+
+    fn end(&self) -> io::Error {
+        self.error(self.text.len(), "unexpected end of input")
+    }
+
+    fn peek(&mut self) -> io::Result<(usize, char)> {
+        let next = self.chars.peek().copied();
+        next.ok_or_else(|| self.end())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> io::Result<()> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((at, _)) => Err(self.error(at, &format!("expected '{}'", expected))),
+            None => Err(self.end()),
+        }
+    }
+
+    fn value(&mut self) -> io::Result<Value> {
+        self.skip_whitespace();
+        let (at, c) = self.peek()?;
+        match c {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' => self.string().map(Value::String),
+            't' => self.literal("true", Value::Bool(true)),
+            'f' => self.literal("false", Value::Bool(false)),
+            'n' => self.literal("null", Value::Null),
+            // Not JSON, but written by some tools for missing numbers
+            'N' => self.literal("NaN", Value::Null),
+            '-' | '0'..='9' => self.number(),
+            _ => Err(self.error(at, "expected a value")),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> io::Result<Value> {
+        let (at, _) = self.peek()?;
+        if self.text[at..].starts_with(word) {
+            for _ in 0..word.len() {
+                self.chars.next();
+            }
+            Ok(value)
+        } else {
+            Err(self.error(at, "expected a value"))
+        }
+    }
+
+    fn number(&mut self) -> io::Result<Value> {
+        let (start, _) = self.peek()?;
+        let mut end = start;
+        while let Some((at, c)) = self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            end = at + c.len_utf8();
+        }
+        self.text[start..end]
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| self.error(start, "invalid number"))
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            let (at, c) = self.chars.next().ok_or_else(|| self.end())?;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let (_, escape) = self.chars.next().ok_or_else(|| self.end())?;
+                    match escape {
+                        'n' => s.push('\n'),
+                        't' => s.push('\t'),
+                        'r' => s.push('\r'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'u' => {
+                            let high = self.hex(at)?;
+                            let code = if (0xD800..0xDC00).contains(&high) {
+                                // A surrogate pair encodes one character beyond the BMP
+                                self.expect('\\')?;
+                                self.expect('u')?;
+                                let low = self.hex(at)?;
+                                0x10000
+                                    + ((high - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                high
+                            };
+                            s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        other => s.push(other),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn hex(&mut self, at: usize) -> io::Result<u32> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let (_, c) = self.chars.next().ok_or_else(|| self.end())?;
+            let digit = c
+                .to_digit(16)
+                .ok_or_else(|| self.error(at, "invalid \\u escape"))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn array(&mut self) -> io::Result<Value> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => {}
+                Some((_, ']')) => return Ok(Value::Array(values)),
+                Some((at, _)) => return Err(self.error(at, "expected ',' or ']'")),
+                None => return Err(self.end()),
+            }
+        }
+    }
+
+    fn object(&mut self) -> io::Result<Value> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => {}
+                Some((_, '}')) => return Ok(Value::Object(fields)),
+                Some((at, _)) => return Err(self.error(at, "expected ',' or '}'")),
+                None => return Err(self.end()),
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Reading CSV, TSV and JSON files into a `Dataset`, and its typed columns
+
+pub(crate) mod csv;
+pub(crate) mod json;
+mod values;
+
+pub use values::{ColumnData, Value};
+
+use crate::state::Dataset;
+use macroquad::prelude::*;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Text read as a missing value (besides an empty field), as written by R, pandas and SAS
+const MISSING_TOKENS: [&str; 8] = ["NA", "N/A", "NaN", "nan", "null", "NULL", "None", "."];
+
+/// A column chosen by position (0 is the first) or by header name
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Self {
+        Column::Name(name)
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Column::Index(index) => write!(f, "column {}", index),
+            Column::Name(name) => write!(f, "column \"{}\"", name),
+        }
+    }
+}
+
+/// What to do with a row whose selected columns hold an empty field, `NA`,
+/// `null` or another missing marker
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Missing {
+    /// Leave the row out
+    #[default]
+    DropRow,
+    /// Use this value in its place
+    Fill(f32),
+    /// Fail with an error naming the line
+    Error,
+}
+
+/// Reads x, y and optionally z columns of a table file into a `Dataset`.
+/// `Dataset::from_csv`, `from_tsv` and `from_json` cover the common case.
+///
+/// ```ignore
+/// let data = DataReader::new("dose", "response")
+///     .z(3)
+///     .missing(Missing::Fill(0.0))
+///     .csv("results/dose_response.csv")?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataReader {
+    x: Column,
+    y: Column,
+    z: Option<Column>,
+    header: bool,
+    missing: Missing,
+}
+
+impl DataReader {
+    pub fn new(x: impl Into<Column>, y: impl Into<Column>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            z: None,
+            header: true,
+            missing: Missing::default(),
+        }
+    }
+
+    /// Column for the z coordinate; without one z is 0
+    pub fn z(mut self, column: impl Into<Column>) -> Self {
+        self.z = Some(column.into());
+        self
+    }
+
+    /// Whether the first line of a CSV or TSV file names the columns (default true).
+    /// Without a header, columns can only be chosen by index.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    pub fn missing(mut self, policy: Missing) -> Self {
+        self.missing = policy;
+        self
+    }
+
+    pub fn csv(&self, path: impl AsRef<Path>) -> io::Result<Dataset> {
+        self.parse_csv(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn tsv(&self, path: impl AsRef<Path>) -> io::Result<Dataset> {
+        self.parse_tsv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Read an array of records or an object of column arrays
+    pub fn json(&self, path: impl AsRef<Path>) -> io::Result<Dataset> {
+        self.parse_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse_csv(&self, text: &str) -> io::Result<Dataset> {
+        self.extract(csv::parse(text, ',', self.header)?)
+    }
+
+    pub fn parse_tsv(&self, text: &str) -> io::Result<Dataset> {
+        self.extract(csv::parse(text, '\t', self.header)?)
+    }
+
+    pub fn parse_json(&self, text: &str) -> io::Result<Dataset> {
+        self.extract(json::parse(text)?)
+    }
+
+    fn extract(&self, table: Table) -> io::Result<Dataset> {
+        let x = table.column_index(&self.x)?;
+        let y = table.column_index(&self.y)?;
+        let z = self
+            .z
+            .as_ref()
+            .map(|column| table.column_index(column))
+            .transpose()?;
+
+        let mut points = Vec::with_capacity(table.rows.len());
+        'rows: for (line, row) in &table.rows {
+            let mut point = Vec3::ZERO;
+            for (axis, index) in [Some(x), Some(y), z].into_iter().enumerate() {
+                let Some(index) = index else { continue };
+                let cell = row.get(index).unwrap_or(&Cell::Missing);
+                let value = match cell.number() {
+                    Ok(Some(value)) => value as f32,
+                    Ok(None) => match self.missing {
+                        Missing::DropRow => continue 'rows,
+                        Missing::Fill(value) => value,
+                        Missing::Error => {
+                            return Err(invalid(format!(
+                                "line {}: missing value in {}",
+                                line,
+                                table.describe(index)
+                            )));
+                        }
+                    },
+                    Err(text) => {
+                        return Err(invalid(format!(
+                            "line {}: {} holds \"{}\", which is not a number",
+                            line,
+                            table.describe(index),
+                            text
+                        )));
+                    }
+                };
+                point[axis] = value;
+            }
+            points.push(point);
+        }
+        Ok(Dataset::from_points(points))
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// One field of a parsed table
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Cell {
+    Missing,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl Cell {
+    /// The field as a number: `Ok(None)` if missing, `Err` with the text if it is not numeric
+    pub(crate) fn number(&self) -> Result<Option<f64>, &str> {
+        match self {
+            Cell::Missing => Ok(None),
+            Cell::Bool(b) => Ok(Some(if *b { 1.0 } else { 0.0 })),
+            Cell::Number(n) => Ok(n.is_finite().then_some(*n)),
+            Cell::Text(text) => {
+                let text = text.trim();
+                if text.is_empty() || MISSING_TOKENS.contains(&text) {
+                    return Ok(None);
+                }
+                match text.parse::<f64>() {
+                    Ok(value) if value.is_finite() => Ok(Some(value)),
+                    Ok(_) => Ok(None),
+                    Err(_) => Err(text),
+                }
+            }
+        }
+    }
+}
+
+/// Rows of fields, each with the line (or record) number it came from
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Table {
+    pub(crate) headers: Option<Vec<String>>,
+    pub(crate) rows: Vec<(usize, Vec<Cell>)>,
+}
+
+impl Table {
+    fn column_index(&self, column: &Column) -> io::Result<usize> {
+        let width = self
+            .headers
+            .as_ref()
+            .map(Vec::len)
+            .into_iter()
+            .chain(self.rows.iter().map(|(_, row)| row.len()))
+            .max()
+            .unwrap_or(0);
+        let found = match column {
+            Column::Index(index) => (*index < width).then_some(*index),
+            Column::Name(name) => self
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.iter().position(|h| h == name)),
+        };
+        found.ok_or_else(|| {
+            let reason = match (&self.headers, column) {
+                (None, Column::Name(_)) => "the file has no header row",
+                _ => "no such column",
+            };
+            io::Error::new(io::ErrorKind::NotFound, format!("{}: {}", column, reason))
+        })
+    }
+
+    /// Every column, typed by `ColumnData::infer`. Columns without a header
+    /// are named by position and repeated names get a `.1`, `.2`... suffix.
+    pub(crate) fn into_dataset(self) -> Dataset {
+        let width = self
+            .rows
+            .iter()
+            .map(|(_, row)| row.len())
+            .max()
+            .unwrap_or(0);
+        let width = width.max(self.headers.as_ref().map_or(0, Vec::len));
+        let mut data = Dataset::new();
+        for index in 0..width {
+            let cells: Vec<&Cell> = self
+                .rows
+                .iter()
+                .map(|(_, row)| row.get(index).unwrap_or(&Cell::Missing))
+                .collect();
+            let base = match self.headers.as_ref().and_then(|h| h.get(index)) {
+                Some(name) if !name.is_empty() => name.clone(),
+                _ => index.to_string(),
+            };
+            let mut name = base.clone();
+            let mut copy = 0;
+            while data.column_names().contains(&name) {
+                copy += 1;
+                name = format!("{}.{}", base, copy);
+            }
+            data = data.with_column(name, ColumnData::infer(&cells));
+        }
+        data
+    }
+
+    /// How to refer to column `index` in messages
+    fn describe(&self, index: usize) -> Column {
+        match self.headers.as_ref().and_then(|headers| headers.get(index)) {
+            Some(name) => Column::Name(name.clone()),
+            None => Column::Index(index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_quotes_missing_values_and_columns() {
+        let text = "id,\"dose, mg\",response,note\r\n\
+                    1,0.5,2.0,\"said \"\"hi\"\"\"\r\n\
+                    2,1.0,NA,\"two\nlines\"\n\
+                    \n\
+                    3,,4.5,x\n\
+                    4,2.0,5.0,";
+        let reader = DataReader::new("dose, mg", 2);
+        let points = reader.parse_csv(text).unwrap().points();
+        assert_eq!(points, vec![vec3(0.5, 2.0, 0.0), vec3(2.0, 5.0, 0.0)]);
+
+        let filled = reader.clone().z("id").missing(Missing::Fill(-1.0));
+        let points = filled.parse_csv(text).unwrap().points();
+        assert_eq!(points[1], vec3(1.0, -1.0, 2.0));
+        assert_eq!(points[2], vec3(-1.0, 4.5, 3.0));
+
+        let error = reader.missing(Missing::Error).parse_csv(text).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: missing value in column \"response\""
+        );
+        let error = DataReader::new(0, "note").parse_csv(text).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("line 2: column \"note\" holds \"said \"hi\"\"")
+        );
+        let error = DataReader::new("dose", 1)
+            .header(false)
+            .parse_tsv("1\t2")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "column \"dose\": the file has no header row"
+        );
+        let tsv = DataReader::new(1, 0)
+            .header(false)
+            .parse_tsv("1\t2\n3\t4\n")
+            .unwrap();
+        assert_eq!(tsv.as_2d(), vec![vec2(2.0, 1.0), vec2(4.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_json_records_and_columns() {
+        let records = r#"[
+            {"t": 0, "value": 1.5, "label": "café"},
+            {"t": 1, "value": null},
+            {"value": -2e-1, "t": 2, "extra": [1, {"nested": true}]}
+        ]"#;
+        let data = DataReader::new("t", "value").parse_json(records).unwrap();
+        assert_eq!(data.as_2d(), vec![vec2(0.0, 1.5), vec2(2.0, -0.2)]);
+        // Keys are numbered in order of first appearance
+        let data = DataReader::new(1, 0).parse_json(records).unwrap();
+        assert_eq!(data.as_2d()[1], vec2(-0.2, 2.0));
+
+        let columns = r#"{"x": [1, 2, 3], "y": [true, false, "7"]}"#;
+        let data = DataReader::new("x", "y").parse_json(columns).unwrap();
+        assert_eq!(
+            data.as_2d(),
+            vec![vec2(1.0, 1.0), vec2(2.0, 0.0), vec2(3.0, 7.0)]
+        );
+
+        let error = DataReader::new(0, 1)
+            .parse_json("[\n{\"a\": 1,}\n]")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected '\"'");
+    }
+
+    #[test]
+    fn test_whole_table_column_types() {
+        let text = "id,weight,smoker,group,,id\n\
+                    1,61.5,TRUE,control,a,7\n\
+                    2,NA,FALSE,treated,b,8\n\
+                    3,70,false,,c,9\n";
+        let data = Dataset::parse_csv(text).unwrap();
+        assert_eq!(
+            data.column_names(),
+            ["id", "weight", "smoker", "group", "4", "id.1"]
+        );
+        assert_eq!(data.column("id"), Some(&ColumnData::Int(vec![1, 2, 3])));
+        assert!(matches!(data.column("weight"), Some(ColumnData::Float(w)) if w[1].is_nan()));
+        assert_eq!(
+            data.column("smoker"),
+            Some(&ColumnData::Bool(vec![true, false, false]))
+        );
+        let levels = data.column("group").and_then(ColumnData::levels).unwrap();
+        assert_eq!(levels, ["control", "treated", "NA"]);
+
+        let json = r#"{"dose": [0.5, 1, 2], "arm": ["a", "b", "a"]}"#;
+        let data = Dataset::parse_json(json).unwrap();
+        assert_eq!(
+            data.group_by("arm")[0].1.values("dose"),
+            Some(vec![0.5, 2.0])
+        );
+    }
+}
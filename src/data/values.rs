@@ -0,0 +1,216 @@
+//! Typed column storage for `Dataset`
+
+use super::Cell;
+use std::fmt;
+
+/// The values of one dataset column
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    /// Real numbers; NaN marks a missing value
+    Float(Vec<f64>),
+    Int(Vec<i64>),
+    Bool(Vec<bool>),
+    /// A categorical variable: each row holds an index into `levels`
+    Categorical {
+        levels: Vec<String>,
+        codes: Vec<u32>,
+    },
+}
+
+/// One value of a column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value<'a> {
+    Float(f64),
+    Int(i64),
+    Bool(bool),
+    Category(&'a str),
+}
+
+impl Value<'_> {
+    /// The value as a number; booleans are 0 or 1 and categories have none
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Float(v) => Some(v),
+            Value::Int(v) => Some(v as f64),
+            Value::Bool(v) => Some(if v { 1.0 } else { 0.0 }),
+            Value::Category(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Category(v) => f.write_str(v),
+        }
+    }
+}
+
+impl ColumnData {
+    /// A categorical column with levels in order of first appearance
+    pub fn categorical<S: AsRef<str>>(values: &[S]) -> Self {
+        let mut levels: Vec<String> = Vec::new();
+        let codes = values
+            .iter()
+            .map(|value| {
+                let value = value.as_ref();
+                match levels.iter().position(|level| level == value) {
+                    Some(code) => code as u32,
+                    None => {
+                        levels.push(value.to_string());
+                        (levels.len() - 1) as u32
+                    }
+                }
+            })
+            .collect();
+        ColumnData::Categorical { levels, codes }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Float(v) => v.len(),
+            ColumnData::Int(v) => v.len(),
+            ColumnData::Bool(v) => v.len(),
+            ColumnData::Categorical { codes, .. } => codes.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, row: usize) -> Option<Value<'_>> {
+        Some(match self {
+            ColumnData::Float(v) => Value::Float(*v.get(row)?),
+            ColumnData::Int(v) => Value::Int(*v.get(row)?),
+            ColumnData::Bool(v) => Value::Bool(*v.get(row)?),
+            ColumnData::Categorical { levels, codes } => {
+                Value::Category(&levels[*codes.get(row)? as usize])
+            }
+        })
+    }
+
+    /// The column as numbers for plotting: booleans become 0 or 1 and
+    /// categories their level index, e.g. for `ScatterPlot::color_by`
+    pub fn to_f32(&self) -> Vec<f32> {
+        match self {
+            ColumnData::Float(v) => v.iter().map(|&v| v as f32).collect(),
+            ColumnData::Int(v) => v.iter().map(|&v| v as f32).collect(),
+            ColumnData::Bool(v) => v.iter().map(|&v| if v { 1.0 } else { 0.0 }).collect(),
+            ColumnData::Categorical { codes, .. } => codes.iter().map(|&c| c as f32).collect(),
+        }
+    }
+
+    /// Level names of a categorical column
+    pub fn levels(&self) -> Option<&[String]> {
+        match self {
+            ColumnData::Categorical { levels, .. } => Some(levels),
+            _ => None,
+        }
+    }
+
+    /// The rows at `indices`, in that order; categorical levels are kept
+    pub(crate) fn take(&self, indices: &[usize]) -> Self {
+        fn pick<T: Copy>(values: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().map(|&i| values[i]).collect()
+        }
+        match self {
+            ColumnData::Float(v) => ColumnData::Float(pick(v, indices)),
+            ColumnData::Int(v) => ColumnData::Int(pick(v, indices)),
+            ColumnData::Bool(v) => ColumnData::Bool(pick(v, indices)),
+            ColumnData::Categorical { levels, codes } => ColumnData::Categorical {
+                levels: levels.clone(),
+                codes: pick(codes, indices),
+            },
+        }
+    }
+
+    /// The narrowest type that holds every cell: booleans, then integers, then
+    /// floats (missing values allowed, as NaN), and otherwise categories
+    /// (missing values become the level "NA")
+    pub(crate) fn infer(cells: &[&Cell]) -> Self {
+        fn as_bool(cell: &Cell) -> Option<bool> {
+            match cell {
+                Cell::Bool(b) => Some(*b),
+                Cell::Text(t) => match t.trim() {
+                    "true" | "TRUE" | "True" => Some(true),
+                    "false" | "FALSE" | "False" => Some(false),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        fn as_int(cell: &Cell) -> Option<i64> {
+            match cell {
+                Cell::Number(n) if n.fract() == 0.0 && n.abs() < 9.0e15 => Some(*n as i64),
+                Cell::Text(t) => t.trim().parse().ok(),
+                _ => None,
+            }
+        }
+
+        if !cells.is_empty() && cells.iter().all(|c| as_bool(c).is_some()) {
+            return ColumnData::Bool(cells.iter().filter_map(|c| as_bool(c)).collect());
+        }
+        if !cells.is_empty() && cells.iter().all(|c| as_int(c).is_some()) {
+            return ColumnData::Int(cells.iter().filter_map(|c| as_int(c)).collect());
+        }
+        if cells.iter().all(|c| c.number().is_ok()) {
+            let floats = cells
+                .iter()
+                .map(|c| c.number().ok().flatten().unwrap_or(f64::NAN))
+                .collect();
+            return ColumnData::Float(floats);
+        }
+
+        let labels: Vec<String> = cells
+            .iter()
+            .map(|c| match c {
+                Cell::Missing => "NA".to_string(),
+                Cell::Bool(b) => b.to_string(),
+                Cell::Number(n) => n.to_string(),
+                Cell::Text(t) if t.trim().is_empty() => "NA".to_string(),
+                Cell::Text(t) => t.clone(),
+            })
+            .collect();
+        ColumnData::categorical(&labels)
+    }
+}
+
+impl From<Vec<f64>> for ColumnData {
+    fn from(values: Vec<f64>) -> Self {
+        ColumnData::Float(values)
+    }
+}
+
+impl From<Vec<f32>> for ColumnData {
+    fn from(values: Vec<f32>) -> Self {
+        ColumnData::Float(values.into_iter().map(f64::from).collect())
+    }
+}
+
+impl From<Vec<i64>> for ColumnData {
+    fn from(values: Vec<i64>) -> Self {
+        ColumnData::Int(values)
+    }
+}
+
+impl From<Vec<bool>> for ColumnData {
+    fn from(values: Vec<bool>) -> Self {
+        ColumnData::Bool(values)
+    }
+}
+
+impl From<Vec<&str>> for ColumnData {
+    fn from(values: Vec<&str>) -> Self {
+        ColumnData::categorical(&values)
+    }
+}
+
+impl From<Vec<String>> for ColumnData {
+    fn from(values: Vec<String>) -> Self {
+        ColumnData::categorical(&values)
+    }
+}
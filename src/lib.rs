@@ -17,25 +17,38 @@ scene.play(MoveTo::new(circle_id, vec2(100.0, 50.0)).duration(1.0));
 */
 
 pub mod animation;
+pub mod canvas;
+pub mod data;
 pub mod mobject;
 pub mod render;
 pub mod scene;
 pub mod state;
+pub mod stats;
 pub mod timeline;
 
 pub mod prelude {
     pub use macroquad::prelude::*;
 
     // Mobjects
+    pub use crate::mobject::{Anchor, MathTex, Text, TextAlign};
+    pub use crate::mobject::{AreaUnder, StemPlot};
     pub use crate::mobject::{Arrow, Circle, Line, Rectangle};
-    pub use crate::mobject::{Axes2D, Axes3D};
+    pub use crate::mobject::{Axes2D, Axes3D, AxesFrame, AxisScale, PlotSpace, TickFormat};
+    pub use crate::mobject::{Bandwidth, Binning, Histogram, KdeCurve, Kernel, Normalization};
     pub use crate::mobject::{BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+    pub use crate::mobject::{BoxPlot, ViolinPlot, Whiskers};
     pub use crate::mobject::{ConfidenceBand, Curve, MarkerShape, ScatterPlot};
 
+    // Drawing backends
+    pub use crate::canvas::{Canvas, MacroquadCanvas, RasterCanvas, RecordingCanvas, SvgCanvas};
+    pub use crate::canvas::{Font, TextMetrics, TextStyle, TransformedCanvas};
+
     // Animations
     pub use crate::animation::{Animation, Easing};
-    pub use crate::animation::{Create, FadeIn, FadeOut};
-    pub use crate::animation::{MoveTo, Rotate, Scale, Shift};
+    pub use crate::animation::{Create, FadeIn, FadeOut, Uncreate};
+    pub use crate::animation::{
+        MorphCurve, MoveTo, Rotate, Scale, SetBandwidth, SetBounds, SetRange, Shift,
+    };
 
     // Scene and Timeline
     pub use crate::scene::{Camera, Scene};
@@ -45,7 +58,9 @@ pub mod prelude {
     pub use crate::render::{FrameInfo, OutputFormat, RenderConfig, Renderable, Renderer};
 
     // Data
-    pub use crate::state::Dataset;
+    pub use crate::data::{Column, ColumnData, DataReader, Missing, Value};
+    pub use crate::state::{Dataset, Row};
+    pub use crate::stats::Rng;
 }
 
 // Re-export key types at crate root
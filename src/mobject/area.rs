@@ -0,0 +1,377 @@
+use super::curve::interpolate;
+use super::{
+    Axes2D, AxesFrame, BoundingRect, Curve, Mobject, MobjectId, MobjectStyle, PlotSpace, to_screen,
+};
+use crate::canvas::Canvas;
+use macroquad::prelude::*;
+
+/// What the region is filled down (or up) to
+#[derive(Debug, Clone, PartialEq)]
+enum Lower {
+    /// A horizontal line at this height
+    Baseline(f32),
+    /// A second curve, ordered by x
+    Curve(Vec<Vec2>),
+}
+
+/// The region between a curve and a horizontal baseline, or between two
+/// curves, clipped to `a <= x <= b`. Where the curves cross, the region is
+/// split so each part fills cleanly. `SetBounds` sweeps the clip bounds,
+/// e.g. to grow an integral or slide a rejection region; with `Create` the
+/// fill sweeps in from the left.
+///
+/// ```ignore
+/// let density = Curve::from_function(|x| (-x * x / 2.0).exp() / 2.5066, -4.0, 4.0, 200).on_axes(&axes);
+/// let tail = scene.add(AreaUnder::new(&density).bounds(3.0, 4.0));
+/// scene.add(density);
+/// scene.play(SetBounds::new(tail, 1.0, 4.0).duration(2.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AreaUnder {
+    id: MobjectId,
+    center: Vec2,
+    upper: Vec<Vec2>,
+    lower: Lower,
+    /// Requested clip bounds; None for the curves' whole extent
+    x_bounds: Option<(f32, f32)>,
+    style: MobjectStyle,
+    space: PlotSpace,
+    scale: f32,
+    rotation: f32,
+}
+
+impl AreaUnder {
+    fn empty() -> Self {
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            upper: Vec::new(),
+            lower: Lower::Baseline(0.0),
+            x_bounds: None,
+            style: MobjectStyle {
+                stroke_color: Color::new(0.0, 0.0, 0.0, 0.0),
+                fill_color: Color::new(0.3, 0.6, 1.0, 0.4),
+                stroke_weight: 0.0,
+                opacity: 1.0,
+            },
+            space: PlotSpace::Pixels,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// The region under `curve`, down to `y = 0`, in the same coordinates as the curve
+    pub fn new(curve: &Curve) -> Self {
+        let mut area = Self::from_points(curve.get_points().to_vec());
+        area.space = curve.space().clone();
+        area
+    }
+
+    /// The region under a polyline ordered by x, down to `y = 0`
+    pub fn from_points(points: Vec<Vec2>) -> Self {
+        let mut area = Self::empty();
+        area.upper = points;
+        area
+    }
+
+    /// The region between two curves, in the coordinates of `upper`. Only the
+    /// x range both curves cover is filled.
+    pub fn between(upper: &Curve, lower: &Curve) -> Self {
+        Self::new(upper).lower_curve(lower.get_points().to_vec())
+    }
+
+    /// Fill to a second polyline, ordered by x, instead of the baseline
+    pub fn lower_curve(mut self, points: Vec<Vec2>) -> Self {
+        self.lower = Lower::Curve(points);
+        self
+    }
+
+    /// Fill to the horizontal line at `y` (default 0)
+    pub fn baseline(mut self, y: f32) -> Self {
+        self.lower = Lower::Baseline(y);
+        self
+    }
+
+    /// Clip the region to `a <= x <= b`. Infinite bounds reach the ends of the curves.
+    pub fn bounds(mut self, a: f32, b: f32) -> Self {
+        self.x_bounds = Some((a, b));
+        self
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
+    pub fn fill(mut self, color: Color) -> Self {
+        self.style.fill_color = color;
+        self
+    }
+
+    /// Outline color; transparent by default
+    pub fn stroke_color(mut self, color: Color) -> Self {
+        self.style.stroke_color = color;
+        self
+    }
+
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.style.stroke_weight = weight;
+        self
+    }
+
+    /// The x range actually filled: the requested bounds within the curves'
+    /// extent, or None if nothing is filled
+    pub fn get_bounds(&self) -> Option<(f32, f32)> {
+        let (first, last) = (self.upper.first()?, self.upper.last()?);
+        let (mut low, mut high) = (first.x.min(last.x), first.x.max(last.x));
+        if let Lower::Curve(points) = &self.lower {
+            let (first, last) = (points.first()?, points.last()?);
+            low = low.max(first.x.min(last.x));
+            high = high.min(first.x.max(last.x));
+        }
+        if let Some((a, b)) = self.x_bounds {
+            low = low.max(a.min(b));
+            high = high.min(a.max(b));
+        }
+        (low <= high).then_some((low, high))
+    }
+
+    /// Signed area between the curves over the bounds: the integral of the
+    /// upper curve minus the lower one (trapezoid rule over the curve points)
+    pub fn integral(&self) -> f32 {
+        self.regions(1.0)
+            .iter()
+            .map(|region| {
+                let half = region.len() / 2;
+                let (top, bottom) = region.split_at(half);
+                top.windows(2)
+                    .zip(bottom.windows(2).rev())
+                    .map(|(u, l)| {
+                        let (u0, u1, l0, l1) = (u[0], u[1], l[1], l[0]);
+                        (u1.x - u0.x) * ((u0.y - l0.y) + (u1.y - l1.y)) / 2.0
+                    })
+                    .sum::<f32>()
+            })
+            .sum()
+    }
+
+    fn lower_at(&self, x: f32) -> f32 {
+        match &self.lower {
+            Lower::Baseline(y) => *y,
+            Lower::Curve(points) => interpolate(points, x).unwrap_or(0.0),
+        }
+    }
+
+    /// Outlines to fill, in data coordinates: each runs forward along the upper
+    /// curve and back along the lower one, with `progress` of the x range
+    /// revealed from the left. A new outline starts wherever the curves cross
+    /// or touch.
+    fn regions(&self, progress: f32) -> Vec<Vec<Vec2>> {
+        let Some((a, b)) = self.get_bounds() else {
+            return Vec::new();
+        };
+        let b = a + (b - a) * progress.clamp(0.0, 1.0);
+        if b <= a {
+            return Vec::new();
+        }
+
+        let lower_xs = match &self.lower {
+            Lower::Baseline(_) => Vec::new(),
+            Lower::Curve(points) => points.iter().map(|p| p.x).collect(),
+        };
+        let mut xs: Vec<f32> = self
+            .upper
+            .iter()
+            .map(|p| p.x)
+            .chain(lower_xs)
+            .filter(|&x| x > a && x < b)
+            .chain([a, b])
+            .collect();
+        xs.sort_by(f32::total_cmp);
+        xs.dedup();
+
+        let gap = |x: f32| interpolate(&self.upper, x).unwrap_or(0.0) - self.lower_at(x);
+        let mut regions = Vec::new();
+        let mut run: Vec<f32> = Vec::new();
+        for (i, &x) in xs.iter().enumerate() {
+            if i > 0 {
+                let previous = xs[i - 1];
+                let (d0, d1) = (gap(previous), gap(x));
+                if d0 * d1 < 0.0 {
+                    let crossing = previous + (x - previous) * d0 / (d0 - d1);
+                    run.push(crossing);
+                    regions.push(std::mem::take(&mut run));
+                    run.push(crossing);
+                }
+            }
+            run.push(x);
+            // The curves touch exactly at a knot: start afresh there too
+            if i > 0 && i + 1 < xs.len() && gap(x) == 0.0 {
+                regions.push(std::mem::take(&mut run));
+                run.push(x);
+            }
+        }
+        regions.push(run);
+
+        regions
+            .into_iter()
+            .filter(|run| run.len() >= 2)
+            .map(|run| {
+                let top = run
+                    .iter()
+                    .map(|&x| vec2(x, interpolate(&self.upper, x).unwrap_or(0.0)));
+                let bottom = run.iter().rev().map(|&x| vec2(x, self.lower_at(x)));
+                top.chain(bottom).collect()
+            })
+            .collect()
+    }
+}
+
+impl Mobject for AreaUnder {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+        let style = self.style.with_opacity(self.style.opacity);
+
+        for region in self.regions(t) {
+            let outline: Vec<Vec2> = region
+                .iter()
+                .map(|p| to_screen(self.space.place(self.center, *p), screen_center))
+                .collect();
+            if style.fill_color.a > 0.0 {
+                canvas.draw_polygon(&outline, style.fill_color);
+            }
+            if style.stroke_color.a > 0.0 && style.stroke_weight > 0.0 {
+                let mut closed = outline.clone();
+                closed.push(outline[0]);
+                canvas.draw_polyline(&closed, style.stroke_weight, style.stroke_color);
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        let points: Vec<Vec2> = self.regions(1.0).into_iter().flatten().collect();
+        if points.is_empty() {
+            return BoundingRect::from_xy_wh(self.center, Vec2::ZERO);
+        }
+        let (min, max) = points.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), p| (min.min(*p), max.max(*p)),
+        );
+        self.space
+            .bounds(min, max, self.center, self.scale, self.rotation)
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
+    fn x_bounds(&self) -> Option<(f32, f32)> {
+        self.get_bounds()
+    }
+
+    fn set_x_bounds(&mut self, a: f32, b: f32) {
+        self.x_bounds = Some((a, b));
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Animation, Easing, SetBounds};
+
+    #[test]
+    fn test_bounds_crossings_and_sweep() {
+        let line = Curve::from_function(|x| x, -2.0, 2.0, 5);
+        let area = AreaUnder::new(&line).bounds(0.5, f32::INFINITY);
+        assert_eq!(area.get_bounds(), Some((0.5, 2.0)));
+        assert!((area.integral() - (4.0 - 0.25) / 2.0).abs() < 1e-6);
+
+        // y = x against y = 0 crosses at 0: two regions whose signed areas cancel
+        let whole = AreaUnder::new(&line);
+        assert_eq!(whole.regions(1.0).len(), 2);
+        assert!(whole.integral().abs() < 1e-6);
+        // Between two curves, only their shared x range is filled
+        let flat = Curve::from_function(|_| 1.0, -1.0, 3.0, 3);
+        let between = AreaUnder::between(&flat, &line);
+        assert_eq!(between.get_bounds(), Some((-1.0, 2.0)));
+        assert!((between.integral() - 1.5).abs() < 1e-6);
+
+        let mut swept = area.clone();
+        let sweep = SetBounds::new(swept.id(), -2.0, 2.0).easing(Easing::Linear);
+        sweep.apply(&mut swept, 0.5);
+        assert_eq!(swept.get_bounds(), Some((-0.75, 2.0)));
+        // Create reveals the fill from the left
+        let half = area.regions(0.5);
+        assert_eq!(half[0].iter().map(|p| p.x).fold(0.0, f32::max), 1.25);
+    }
+}
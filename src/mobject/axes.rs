@@ -1,20 +1,64 @@
-use super::{BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+use super::{
+    Anchor, AxesFrame, AxisScale, BoundingRect, MathTex, Mobject, MobjectId, MobjectStyle,
+    TickFormat, Ticks, to_screen,
+};
+use crate::canvas::{Canvas, Font, TextStyle, TransformedCanvas};
+use crate::state::Dataset;
 use macroquad::prelude::*;
 
-/// 2D coordinate axes with configurable ranges and styling
+/// Length of tick marks on either side of an axis, in pixels
+const TICK_SIZE: f32 = 5.0;
+/// Gap between tick marks, labels and titles, in pixels
+const LABEL_PADDING: f32 = 4.0;
+/// Preferred distance between automatic major ticks, in pixels
+const TICK_DENSITY: f32 = 80.0;
+/// Plot area of axes fitted to data, in pixels
+const FIT_SIZE: Vec2 = vec2(640.0, 400.0);
+
+/// 2D coordinate axes with configurable ranges and styling.
+/// The center is the data origin, or the middle of the plot area for axes
+/// with a fixed `size` or `center_on_ranges`; the axes cross at the origin,
+/// or at the nearest corner when the origin is outside the ranges.
 #[derive(Debug, Clone)]
 pub struct Axes2D {
     id: MobjectId,
     center: Vec2,
     x_range: (f32, f32),
     y_range: (f32, f32),
-    scale: f32,
+    x_scale: AxisScale,
+    y_scale: AxisScale,
+    /// Pixels per unit along x and y, measured after the axis scales
+    unit: Vec2,
+    /// Fixed plot area in pixels; when set, `unit` follows the ranges
+    size: Option<Vec2>,
+    /// The center is the middle of the ranges instead of the data origin
+    centered: bool,
     show_ticks: bool,
-    tick_spacing: f32,
+    /// Major tick spacing, or None for automatic "nice" ticks
+    tick_spacing: Option<f32>,
+    minor_ticks: bool,
+    /// The x-axis holds named categories at 0, 1, 2, ... instead of numbers
+    categorical_x: bool,
+    show_labels: bool,
+    x_format: TickFormat,
+    y_format: TickFormat,
+    x_title: Option<String>,
+    y_title: Option<String>,
+    font: Font,
+    label_size: f32,
     style: MobjectStyle,
     rotation: f32,
 }
 
+/// A tick label laid out in center-origin coordinates
+#[derive(Debug, Clone)]
+struct TickLabel {
+    text: String,
+    /// Left end of the baseline
+    baseline: Vec2,
+    rect: BoundingRect,
+}
+
 impl Axes2D {
     pub fn new() -> Self {
         Self {
@@ -22,9 +66,22 @@ impl Axes2D {
             center: Vec2::ZERO,
             x_range: (-5.0, 5.0),
             y_range: (-3.0, 3.0),
-            scale: 50.0,
+            x_scale: AxisScale::Linear,
+            y_scale: AxisScale::Linear,
+            unit: Vec2::splat(50.0),
+            size: None,
+            centered: false,
             show_ticks: true,
-            tick_spacing: 1.0,
+            tick_spacing: None,
+            minor_ticks: true,
+            categorical_x: false,
+            show_labels: true,
+            x_format: TickFormat::Auto,
+            y_format: TickFormat::Auto,
+            x_title: None,
+            y_title: None,
+            font: Font::default(),
+            label_size: 14.0,
             style: MobjectStyle {
                 stroke_color: Color::new(0.7, 0.7, 0.7, 1.0),
                 stroke_weight: 2.0,
@@ -34,18 +91,69 @@ impl Axes2D {
         }
     }
 
+    /// Axes fitted to the (x, y) columns of a dataset, see `fit_to_points`
+    pub fn fit_to(data: &Dataset) -> Self {
+        Self::fit_to_points(&data.as_2d())
+    }
+
+    /// Axes whose ranges cover `points` with a little padding, rounded out to
+    /// half a tick step, in a plot area of 640x400 pixels (change with `size`)
+    pub fn fit_to_points(points: &[Vec2]) -> Self {
+        let (x_min, x_max) = padded_range(points.iter().map(|p| p.x));
+        let (y_min, y_max) = padded_range(points.iter().map(|p| p.y));
+        Self::new()
+            .x_range(x_min, x_max)
+            .y_range(y_min, y_max)
+            .size(FIT_SIZE.x, FIT_SIZE.y)
+    }
+
     pub fn x_range(mut self, min: f32, max: f32) -> Self {
         self.x_range = (min, max);
+        self.sync_unit();
         self
     }
 
     pub fn y_range(mut self, min: f32, max: f32) -> Self {
         self.y_range = (min, max);
+        self.sync_unit();
+        self
+    }
+
+    /// How x values are spaced along the axis, e.g. `AxisScale::Log10`.
+    /// The range must lie inside the scale's domain.
+    pub fn x_scale(mut self, scale: AxisScale) -> Self {
+        self.x_scale = scale;
+        self.sync_unit();
+        self
+    }
+
+    pub fn y_scale(mut self, scale: AxisScale) -> Self {
+        self.y_scale = scale;
+        self.sync_unit();
         self
     }
 
+    /// Pixels per data unit on both axes (per decade on log axes; clears any fixed `size`)
     pub fn scale(mut self, scale: f32) -> Self {
-        self.scale = scale;
+        self.unit = Vec2::splat(scale);
+        self.size = None;
+        self
+    }
+
+    /// Fix the plot area to `width` x `height` pixels, centered on the axes'
+    /// position and stretching each axis to fit its range. Animating the
+    /// ranges then zooms instead of resizing the axes.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(vec2(width, height));
+        self.centered = true;
+        self.sync_unit();
+        self
+    }
+
+    /// Place the middle of the ranges, rather than the data origin, at the
+    /// axes' position
+    pub fn center_on_ranges(mut self) -> Self {
+        self.centered = true;
         self
     }
 
@@ -64,22 +172,331 @@ impl Axes2D {
         self
     }
 
+    /// Fixed major tick spacing on both axes instead of automatic ticks
     pub fn tick_spacing(mut self, spacing: f32) -> Self {
-        self.tick_spacing = spacing;
+        self.tick_spacing = Some(spacing);
+        self
+    }
+
+    /// Show minor ticks between automatic major ticks (on by default)
+    pub fn minor_ticks(mut self, show: bool) -> Self {
+        self.minor_ticks = show;
+        self
+    }
+
+    /// Show numeric labels next to the ticks (on by default)
+    pub fn show_labels(mut self, show: bool) -> Self {
+        self.show_labels = show;
+        self
+    }
+
+    /// Number format for the tick labels of both axes
+    pub fn tick_format(mut self, format: TickFormat) -> Self {
+        self.x_format = format.clone();
+        self.y_format = format;
+        self
+    }
+
+    pub fn x_tick_format(mut self, format: TickFormat) -> Self {
+        self.x_format = format;
+        self
+    }
+
+    pub fn y_tick_format(mut self, format: TickFormat) -> Self {
+        self.y_format = format;
+        self
+    }
+
+    /// Turn the x-axis into named slots at 0, 1, 2, ..., as used by box and violin plots.
+    /// The x range gets half a slot of room on either side.
+    pub fn x_categories<S: Into<String>>(mut self, names: impl IntoIterator<Item = S>) -> Self {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        self.x_range = (-0.5, names.len() as f32 - 0.5);
+        self.x_scale = AxisScale::Linear;
+        self.categorical_x = true;
+        self.x_format = TickFormat::custom(move |x| {
+            let index = x.round();
+            if index < 0.0 {
+                return String::new();
+            }
+            names.get(index as usize).cloned().unwrap_or_default()
+        });
+        self.sync_unit();
+        self
+    }
+
+    /// Title under the x-axis; `$...$` segments are rendered as TeX math
+    pub fn x_label(mut self, title: impl Into<String>) -> Self {
+        self.x_title = Some(title.into());
+        self
+    }
+
+    /// Title above the y-axis; `$...$` segments are rendered as TeX math
+    pub fn y_label(mut self, title: impl Into<String>) -> Self {
+        self.y_title = Some(title.into());
+        self
+    }
+
+    /// Font for tick labels and titles
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Tick label size in pixels; titles are drawn slightly larger
+    pub fn label_font_size(mut self, size: f32) -> Self {
+        self.label_size = size;
         self
     }
 
     /// Convert a point from coordinate space to screen space
     pub fn to_screen(&self, point: Vec2) -> Vec2 {
-        self.center + point * self.scale
+        self.frame().to_scene(point)
     }
 
     /// Convert a point from screen space to coordinate space
     pub fn from_screen(&self, point: Vec2) -> Vec2 {
-        (point - self.center) / self.scale
+        self.frame().from_scene(point)
+    }
+
+    /// Current mapping from data coordinates into the scene
+    pub fn frame(&self) -> AxesFrame {
+        AxesFrame {
+            center: self.center,
+            x_range: self.x_range,
+            y_range: self.y_range,
+            x_scale: self.x_scale.clone(),
+            y_scale: self.y_scale.clone(),
+            scale: self.unit.x,
+            aspect: self.unit.y / self.unit.x,
+            centered: self.centered,
+            rotation: self.rotation,
+        }
+    }
+
+    /// Recompute pixels per unit from a fixed plot size
+    fn sync_unit(&mut self) {
+        let Some(size) = self.size else {
+            return;
+        };
+        let span = vec2(
+            self.x_scale.forward(self.x_range.1) - self.x_scale.forward(self.x_range.0),
+            self.y_scale.forward(self.y_range.1) - self.y_scale.forward(self.y_range.0),
+        );
+        if span.x != 0.0 && span.y != 0.0 {
+            self.unit = size / span;
+        }
+    }
+
+    /// Where the axes cross, in coordinate space: the origin, clamped into the ranges.
+    /// Categorical axes cross at the bottom left corner.
+    fn crossing(&self) -> Vec2 {
+        if self.categorical_x {
+            return vec2(self.x_range.0, self.y_range.0);
+        }
+        vec2(
+            0f32.clamp(self.x_range.0, self.x_range.1),
+            0f32.clamp(self.y_range.0, self.y_range.1),
+        )
+    }
+
+    pub fn x_ticks(&self) -> Ticks {
+        if self.categorical_x {
+            return Ticks::fixed(self.x_range, 1.0);
+        }
+        self.ticks(self.x_range, &self.x_scale, self.unit.x)
+    }
+
+    pub fn y_ticks(&self) -> Ticks {
+        self.ticks(self.y_range, &self.y_scale, self.unit.y)
+    }
+
+    /// Fixed-spacing ticks, or ticks chosen by the axis scale about `TICK_DENSITY` pixels apart
+    fn ticks(&self, range: (f32, f32), scale: &AxisScale, unit: f32) -> Ticks {
+        let mut ticks = match self.tick_spacing {
+            Some(spacing) => Ticks::fixed(range, spacing),
+            None => {
+                let pixels = ((scale.forward(range.1) - scale.forward(range.0)) * unit).abs();
+                let target = (pixels / TICK_DENSITY).round().clamp(2.0, 10.0) as usize;
+                scale.ticks(range, target)
+            }
+        };
+        if !self.minor_ticks {
+            ticks.minor.clear();
+        }
+        ticks
+    }
+
+    fn label_style(&self, color: Color) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: self.label_size,
+            color,
+        }
+    }
+
+    /// Tick labels for both axes, thinned out so that none of them overlap
+    fn tick_labels(&self) -> (Vec<TickLabel>, Vec<TickLabel>) {
+        let crossing = self.crossing();
+        let padding = self.label_size * 0.5;
+
+        // A label at the crossing would sit on the other axis if that axis continues past it
+        let x_ticks = self.x_ticks();
+        let x_values: Vec<f32> = x_ticks
+            .major
+            .into_iter()
+            .filter(|x| (x - crossing.x).abs() > 1e-3 || crossing.y <= self.y_range.0)
+            .collect();
+        let x_labels: Vec<TickLabel> = x_values
+            .iter()
+            .map(|&x| {
+                let text = self
+                    .x_format
+                    .format(x, self.x_scale.label_step(x, x_ticks.step));
+                let metrics = self.font.measure(&text, self.label_size);
+                let anchor = self.to_screen(vec2(x, crossing.y));
+                let top = anchor.y - TICK_SIZE - LABEL_PADDING;
+                let left = anchor.x - metrics.width / 2.0;
+                TickLabel {
+                    baseline: vec2(left, top - metrics.ascent),
+                    rect: BoundingRect::from_corners(
+                        vec2(left, top - metrics.height()),
+                        vec2(left + metrics.width, top),
+                    ),
+                    text,
+                }
+            })
+            .collect();
+        let x_labels = thin_out(x_labels, |r| (r.x, r.x + r.w), padding);
+
+        let y_ticks = self.y_ticks();
+        let y_values: Vec<f32> = y_ticks
+            .major
+            .into_iter()
+            .filter(|y| (y - crossing.y).abs() > 1e-3 || crossing.x <= self.x_range.0)
+            .collect();
+        let y_labels: Vec<TickLabel> = y_values
+            .iter()
+            .map(|&y| {
+                let text = self
+                    .y_format
+                    .format(y, self.y_scale.label_step(y, y_ticks.step));
+                let metrics = self.font.measure(&text, self.label_size);
+                let anchor = self.to_screen(vec2(crossing.x, y));
+                let right = anchor.x - TICK_SIZE - LABEL_PADDING;
+                let top = anchor.y + metrics.height() / 2.0;
+                TickLabel {
+                    baseline: vec2(right - metrics.width, top - metrics.ascent),
+                    rect: BoundingRect::from_corners(
+                        vec2(right - metrics.width, top - metrics.height()),
+                        vec2(right, top),
+                    ),
+                    text,
+                }
+            })
+            .collect();
+        let y_labels = thin_out(y_labels, |r| (r.y, r.y + r.h), padding / 2.0);
+
+        // Near the corner the two sets can still collide; the x labels win
+        let y_labels = y_labels
+            .into_iter()
+            .filter(|y| !x_labels.iter().any(|x| x.rect.intersects(&y.rect)))
+            .collect();
+
+        (x_labels, y_labels)
+    }
+
+    /// Axis titles as math mobjects positioned around the axes and their labels
+    fn titles(&self, labels: &(Vec<TickLabel>, Vec<TickLabel>), color: Color) -> Vec<MathTex> {
+        let crossing = self.crossing();
+        let title_size = self.label_size * 1.2;
+        let mut titles = Vec::new();
+
+        if let Some(title) = &self.x_title {
+            // Below everything, so it clears a y-axis that extends under the x-axis
+            let bottom = self.to_screen(vec2(crossing.x, self.y_range.0)).y;
+            let below = labels
+                .0
+                .iter()
+                .chain(&labels.1)
+                .map(|l| l.rect.y)
+                .fold(bottom.min(self.to_screen(crossing).y - TICK_SIZE), f32::min);
+            let x_mid = (self.to_screen(vec2(self.x_range.0, 0.0)).x
+                + self.to_screen(vec2(self.x_range.1, 0.0)).x)
+                / 2.0;
+            titles.push(
+                MathTex::mixed(title.as_str())
+                    .font(self.font.clone())
+                    .font_size(title_size)
+                    .color(color)
+                    .anchor(Anchor::Top)
+                    .at(vec2(x_mid, below - LABEL_PADDING)),
+            );
+        }
+
+        if let Some(title) = &self.y_title {
+            let top = self.to_screen(vec2(crossing.x, self.y_range.1));
+            let above = labels
+                .1
+                .iter()
+                .map(|l| l.rect.y + l.rect.h)
+                .fold(top.y, f32::max);
+            titles.push(
+                MathTex::mixed(title.as_str())
+                    .font(self.font.clone())
+                    .font_size(title_size)
+                    .color(color)
+                    .anchor(Anchor::Bottom)
+                    .at(vec2(top.x, above + 2.0 * LABEL_PADDING)),
+            );
+        }
+        titles
     }
 }
 
+/// Data extent of `values` padded by 5% and rounded outward to half a nice tick step
+fn padded_range(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if min > max {
+        return (-1.0, 1.0);
+    }
+    // A single value still gets a visible range around it
+    let span = if max > min {
+        max - min
+    } else {
+        max.abs().max(1.0)
+    };
+    let (lo, hi) = (min - span * 0.05, max + span * 0.05);
+    let half_step = super::ticks::nice_step(hi - lo, 6) / 2.0;
+    (
+        (lo / half_step).floor() * half_step,
+        (hi / half_step).ceil() * half_step,
+    )
+}
+
+/// Keep every k-th label, for the smallest k that leaves `padding` between
+/// neighbours; `extent` gives a label's span along the axis
+fn thin_out(
+    labels: Vec<TickLabel>,
+    extent: impl Fn(&BoundingRect) -> (f32, f32),
+    padding: f32,
+) -> Vec<TickLabel> {
+    let spans: Vec<(f32, f32)> = labels.iter().map(|l| extent(&l.rect)).collect();
+    let step = (1..labels.len().max(1))
+        .find(|&k| {
+            spans
+                .iter()
+                .step_by(k)
+                .collect::<Vec<_>>()
+                .windows(2)
+                .all(|w| w[0].1 + padding <= w[1].0)
+        })
+        .unwrap_or(labels.len().max(1));
+    labels.into_iter().step_by(step).collect()
+}
+
 impl Default for Axes2D {
     fn default() -> Self {
         Self::new()
@@ -87,18 +504,22 @@ impl Default for Axes2D {
 }
 
 impl Mobject for Axes2D {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        // Axes scale natively (units to pixels), so only rotation goes through the canvas
+        let pivot = to_screen(self.center, screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, 1.0, self.rotation);
         let style = self.style.with_opacity(self.style.opacity);
+        let crossing = self.crossing();
 
         // X-axis (draw from negative to positive)
-        let x_start = self.center + vec2(self.x_range.0 * self.scale, 0.0);
-        let x_end_full = self.center + vec2(self.x_range.1 * self.scale, 0.0);
+        let x_start = self.to_screen(vec2(self.x_range.0, crossing.y));
+        let x_end_full = self.to_screen(vec2(self.x_range.1, crossing.y));
         let x_end = x_start.lerp(x_end_full, t);
 
         let screen_x_start = to_screen(x_start, screen_center);
         let screen_x_end = to_screen(x_end, screen_center);
 
-        draw_line(
+        canvas.draw_line(
             screen_x_start.x,
             screen_x_start.y,
             screen_x_end.x,
@@ -108,14 +529,14 @@ impl Mobject for Axes2D {
         );
 
         // Y-axis
-        let y_start = self.center + vec2(0.0, self.y_range.0 * self.scale);
-        let y_end_full = self.center + vec2(0.0, self.y_range.1 * self.scale);
+        let y_start = self.to_screen(vec2(crossing.x, self.y_range.0));
+        let y_end_full = self.to_screen(vec2(crossing.x, self.y_range.1));
         let y_end = y_start.lerp(y_end_full, t);
 
         let screen_y_start = to_screen(y_start, screen_center);
         let screen_y_end = to_screen(y_end, screen_center);
 
-        draw_line(
+        canvas.draw_line(
             screen_y_start.x,
             screen_y_start.y,
             screen_y_end.x,
@@ -124,60 +545,82 @@ impl Mobject for Axes2D {
             style.stroke_color,
         );
 
-        // Draw ticks if enabled and t is far enough along
-        if self.show_ticks && t > 0.5 {
-            let tick_opacity = ((t - 0.5) * 2.0).min(1.0);
-            let tick_color = Color::new(
-                style.stroke_color.r,
-                style.stroke_color.g,
-                style.stroke_color.b,
-                style.stroke_color.a * tick_opacity,
-            );
-            let tick_size = 5.0;
+        // Ticks, labels and titles fade in over the second half of the animation
+        if t <= 0.5 {
+            return;
+        }
+        let tick_opacity = ((t - 0.5) * 2.0).min(1.0);
+        let tick_color = Color::new(
+            style.stroke_color.r,
+            style.stroke_color.g,
+            style.stroke_color.b,
+            style.stroke_color.a * tick_opacity,
+        );
 
-            // X-axis ticks
-            let mut x = self.x_range.0;
-            while x <= self.x_range.1 {
-                if x.abs() > 0.001 {
-                    let pos = self.center + vec2(x * self.scale, 0.0);
+        if self.show_ticks {
+            let (x_ticks, y_ticks) = (self.x_ticks(), self.y_ticks());
+            let minor = TICK_SIZE / 2.0;
+
+            // X-axis ticks (none where the y-axis crosses)
+            let x_marks = x_ticks.major.iter().map(|&x| (x, TICK_SIZE));
+            for (x, size) in x_marks.chain(x_ticks.minor.iter().map(|&x| (x, minor))) {
+                if (x - crossing.x).abs() > 0.001 {
+                    let pos = self.to_screen(vec2(x, crossing.y));
                     let screen_pos = to_screen(pos, screen_center);
                     // Tick is vertical, so we flip the y offset
-                    draw_line(
+                    canvas.draw_line(
                         screen_pos.x,
-                        screen_pos.y - tick_size,
+                        screen_pos.y - size,
                         screen_pos.x,
-                        screen_pos.y + tick_size,
+                        screen_pos.y + size,
                         1.0,
                         tick_color,
                     );
                 }
-                x += self.tick_spacing;
             }
 
             // Y-axis ticks
-            let mut y = self.y_range.0;
-            while y <= self.y_range.1 {
-                if y.abs() > 0.001 {
-                    let pos = self.center + vec2(0.0, y * self.scale);
+            let y_marks = y_ticks.major.iter().map(|&y| (y, TICK_SIZE));
+            for (y, size) in y_marks.chain(y_ticks.minor.iter().map(|&y| (y, minor))) {
+                if (y - crossing.y).abs() > 0.001 {
+                    let pos = self.to_screen(vec2(crossing.x, y));
                     let screen_pos = to_screen(pos, screen_center);
-                    draw_line(
-                        screen_pos.x - tick_size,
+                    canvas.draw_line(
+                        screen_pos.x - size,
                         screen_pos.y,
-                        screen_pos.x + tick_size,
+                        screen_pos.x + size,
                         screen_pos.y,
                         1.0,
                         tick_color,
                     );
                 }
-                y += self.tick_spacing;
             }
         }
+
+        let labels = if self.show_labels {
+            self.tick_labels()
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let label_style = self.label_style(tick_color);
+        for label in labels.0.iter().chain(&labels.1) {
+            let pos = to_screen(label.baseline, screen_center);
+            canvas.draw_text(&label.text, pos.x, pos.y, &label_style);
+        }
+
+        for title in self.titles(&labels, tick_color) {
+            title.draw(&mut canvas, 1.0, screen_center);
+        }
     }
 
     fn bounding_box(&self) -> BoundingRect {
-        let min = self.center + vec2(self.x_range.0 * self.scale, self.y_range.0 * self.scale);
-        let max = self.center + vec2(self.x_range.1 * self.scale, self.y_range.1 * self.scale);
-        BoundingRect::from_corners(min, max)
+        let a = self.to_screen(vec2(self.x_range.0, self.y_range.0));
+        let b = self.to_screen(vec2(self.x_range.1, self.y_range.1));
+        BoundingRect::from_corners(a.min(b), a.max(b)).transformed_about(
+            self.center,
+            1.0,
+            self.rotation,
+        )
     }
 
     fn center(&self) -> Vec2 {
@@ -196,12 +639,19 @@ impl Mobject for Axes2D {
         self.style.opacity = opacity;
     }
 
+    /// Pixels per x unit; setting it resizes both axes proportionally
     fn scale(&self) -> f32 {
-        self.scale
+        self.unit.x
     }
 
     fn set_scale(&mut self, scale: f32) {
-        self.scale = scale;
+        let ratio = scale / self.unit.x;
+        self.unit *= ratio;
+        self.size = self.size.map(|size| size * ratio);
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
     }
 
     fn rotate(&mut self, angle: f32) {
@@ -219,6 +669,16 @@ impl Mobject for Axes2D {
     fn clone_box(&self) -> Box<dyn Mobject> {
         Box::new(self.clone())
     }
+
+    fn axes_frame(&self) -> Option<AxesFrame> {
+        Some(self.frame())
+    }
+
+    fn set_ranges(&mut self, x_range: (f32, f32), y_range: (f32, f32)) {
+        self.x_range = x_range;
+        self.y_range = y_range;
+        self.sync_unit();
+    }
 }
 
 /// 3D coordinate axes with isometric projection
@@ -312,7 +772,7 @@ impl Default for Axes3D {
 }
 
 impl Mobject for Axes3D {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
         let style = self.style.with_opacity(self.style.opacity);
 
         let x_color = Color::new(1.0, 0.3, 0.3, style.stroke_color.a); // Red-ish
@@ -327,7 +787,7 @@ impl Mobject for Axes3D {
         let screen_x_start = to_screen(x_start, screen_center);
         let screen_x_end = to_screen(x_end, screen_center);
 
-        draw_line(
+        canvas.draw_line(
             screen_x_start.x,
             screen_x_start.y,
             screen_x_end.x,
@@ -344,7 +804,7 @@ impl Mobject for Axes3D {
         let screen_y_start = to_screen(y_start, screen_center);
         let screen_y_end = to_screen(y_end, screen_center);
 
-        draw_line(
+        canvas.draw_line(
             screen_y_start.x,
             screen_y_start.y,
             screen_y_end.x,
@@ -361,7 +821,7 @@ impl Mobject for Axes3D {
         let screen_z_start = to_screen(z_start, screen_center);
         let screen_z_end = to_screen(z_end, screen_center);
 
-        draw_line(
+        canvas.draw_line(
             screen_z_start.x,
             screen_z_start.y,
             screen_z_end.x,
@@ -416,6 +876,10 @@ impl Mobject for Axes3D {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -432,3 +896,55 @@ impl Mobject for Axes3D {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_labels_skip_origin_and_format() {
+        let axes = Axes2D::new().x_range(-2.0, 2.0).y_range(-1.0, 1.0);
+        let (x_labels, y_labels) = axes.tick_labels();
+
+        let x_texts: Vec<&str> = x_labels.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(x_texts, ["−2", "−1", "1", "2"]);
+        assert_eq!(y_labels.len(), 2);
+    }
+
+    #[test]
+    fn test_crowded_labels_are_thinned() {
+        let axes = Axes2D::new()
+            .x_range(0.0, 10.0)
+            .y_range(0.0, 10.0)
+            .scale(8.0)
+            .tick_format(TickFormat::Fixed(2));
+        let (x_labels, y_labels) = axes.tick_labels();
+
+        assert!(x_labels.len() < 11);
+        for pair in x_labels.windows(2) {
+            assert!(!pair[0].rect.intersects(&pair[1].rect));
+        }
+        for y in &y_labels {
+            assert!(x_labels.iter().all(|x| !x.rect.intersects(&y.rect)));
+        }
+    }
+
+    #[test]
+    fn test_fit_to_points_pads_and_rounds_ranges() {
+        let points = [vec2(0.3, 12.0), vec2(9.6, 48.0), vec2(4.0, 30.0)];
+        let axes = Axes2D::fit_to_points(&points);
+
+        assert_eq!(axes.x_range, (-1.0, 11.0));
+        assert_eq!(axes.y_range, (10.0, 50.0));
+        let bounds = axes.bounding_box();
+        assert!((bounds.w - 640.0).abs() < 0.01 && (bounds.h - 400.0).abs() < 0.01);
+        // Fitted axes are centered on their ranges; plain axes on the data origin
+        assert_eq!(axes.to_screen(vec2(5.0, 30.0)), Vec2::ZERO);
+        assert_eq!(Axes2D::new().to_screen(vec2(1.0, 2.0)), vec2(50.0, 100.0));
+
+        let ticks = axes.x_ticks();
+        assert_eq!(ticks.step, 2.0);
+        assert_eq!(ticks.major, [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert!(!ticks.minor.is_empty());
+    }
+}
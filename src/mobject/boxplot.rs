@@ -0,0 +1,422 @@
+use super::{
+    Axes2D, AxesFrame, BoundingRect, MarkerShape, Mobject, MobjectId, MobjectStyle, PlotSpace,
+    to_screen,
+};
+use crate::canvas::Canvas;
+use crate::data::Column;
+use crate::state::Dataset;
+use crate::stats::describe::{self, QuantileMethod};
+use macroquad::prelude::*;
+
+/// Where box plot whiskers end
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Whiskers {
+    /// At the furthest values within `k`·IQR of the box (Tukey uses k = 1.5);
+    /// anything beyond is drawn as an outlier
+    Tukey(f32),
+    /// At the minimum and maximum, with no outliers
+    MinMax,
+}
+
+impl Default for Whiskers {
+    fn default() -> Self {
+        Whiskers::Tukey(1.5)
+    }
+}
+
+/// Five-number summary of one group, as drawn by a box plot
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxStats {
+    pub q1: f32,
+    pub median: f32,
+    pub q3: f32,
+    /// Whisker ends
+    pub low: f32,
+    pub high: f32,
+    pub outliers: Vec<f32>,
+}
+
+impl BoxStats {
+    /// Summary of `values` (any order); None when there are no finite values
+    pub fn from_values(values: &[f32], whiskers: Whiskers) -> Option<Self> {
+        let finite: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        if finite.is_empty() {
+            return None;
+        }
+        let quartiles = describe::quantiles(&finite, &[0.25, 0.5, 0.75], QuantileMethod::Linear);
+        let (q1, median, q3) = (quartiles[0], quartiles[1], quartiles[2]);
+        let (fence_low, fence_high) = match whiskers {
+            Whiskers::Tukey(k) => (q1 - k * (q3 - q1), q3 + k * (q3 - q1)),
+            Whiskers::MinMax => (f32::MIN, f32::MAX),
+        };
+        let inside = || {
+            finite
+                .iter()
+                .filter(|&&v| v >= fence_low && v <= fence_high)
+        };
+        Some(Self {
+            q1,
+            median,
+            q3,
+            low: inside().copied().fold(q1, f32::min),
+            high: inside().copied().fold(q3, f32::max),
+            outliers: finite
+                .iter()
+                .copied()
+                .filter(|&v| v < fence_low || v > fence_high)
+                .collect(),
+        })
+    }
+}
+
+/// Box-and-whisker plots of several groups, placed at x = 0, 1, 2, ... so they
+/// line up with `Axes2D::x_categories`. Values are along y.
+#[derive(Debug, Clone)]
+pub struct BoxPlot {
+    id: MobjectId,
+    center: Vec2,
+    names: Vec<String>,
+    groups: Vec<Vec<f32>>,
+    whiskers: Whiskers,
+    /// Summary of each group, kept in step with `groups` and `whiskers`
+    stats: Vec<Option<BoxStats>>,
+    /// Box width in x units (one unit per group)
+    box_width: f32,
+    marker: MarkerShape,
+    marker_radius: f32,
+    group_colors: Option<Vec<Color>>,
+    style: MobjectStyle,
+    space: PlotSpace,
+    scale: f32,
+    rotation: f32,
+}
+
+impl BoxPlot {
+    pub fn new() -> Self {
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            names: Vec::new(),
+            groups: Vec::new(),
+            whiskers: Whiskers::default(),
+            stats: Vec::new(),
+            box_width: 0.5,
+            marker: MarkerShape::Circle,
+            marker_radius: 3.0,
+            group_colors: None,
+            style: MobjectStyle {
+                stroke_color: Color::new(0.9, 0.9, 0.9, 1.0),
+                fill_color: Color::new(0.3, 0.6, 1.0, 0.5),
+                stroke_weight: 1.5,
+                opacity: 1.0,
+            },
+            space: PlotSpace::Pixels,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// One group per value of the `group` column, holding its rows' `value` column
+    pub fn from_dataset(
+        data: &Dataset,
+        value: impl Into<Column>,
+        group: impl Into<Column>,
+    ) -> Self {
+        let value = value.into();
+        Self::from_groups(data.group_by(group).into_iter().map(|(name, rows)| {
+            let values = rows.values(value.clone()).unwrap_or_default();
+            (name, values)
+        }))
+    }
+
+    pub fn from_groups<S: Into<String>>(groups: impl IntoIterator<Item = (S, Vec<f32>)>) -> Self {
+        groups
+            .into_iter()
+            .fold(Self::new(), |plot, (name, values)| {
+                plot.group(name, &values)
+            })
+    }
+
+    /// Add a group in the next slot
+    pub fn group(mut self, name: impl Into<String>, values: &[f32]) -> Self {
+        self.names.push(name.into());
+        self.groups.push(values.to_vec());
+        self.stats
+            .push(BoxStats::from_values(values, self.whiskers));
+        self
+    }
+
+    pub fn whiskers(mut self, whiskers: Whiskers) -> Self {
+        self.whiskers = whiskers;
+        self.stats = self
+            .groups
+            .iter()
+            .map(|values| BoxStats::from_values(values, whiskers))
+            .collect();
+        self
+    }
+
+    /// Box width in x units, where neighbouring groups are one unit apart
+    pub fn box_width(mut self, width: f32) -> Self {
+        self.box_width = width;
+        self
+    }
+
+    /// Marker for outliers
+    pub fn marker(mut self, shape: MarkerShape) -> Self {
+        self.marker = shape;
+        self
+    }
+
+    pub fn marker_radius(mut self, radius: f32) -> Self {
+        self.marker_radius = radius;
+        self
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
+    /// Box fill color
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.fill_color = color;
+        self
+    }
+
+    /// One fill color per group, cycling if there are fewer colors than groups
+    pub fn group_colors(mut self, colors: Vec<Color>) -> Self {
+        self.group_colors = Some(colors);
+        self
+    }
+
+    /// Color of outlines, medians, whiskers and outliers
+    pub fn stroke_color(mut self, color: Color) -> Self {
+        self.style.stroke_color = color;
+        self
+    }
+
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.style.stroke_weight = weight;
+        self
+    }
+
+    /// Group names in slot order, for `Axes2D::x_categories`
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Summary statistics of group `index`
+    pub fn stats(&self, index: usize) -> Option<&BoxStats> {
+        self.stats.get(index)?.as_ref()
+    }
+}
+
+impl Default for BoxPlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fill color of group `index`, from the per-group list when there is one
+pub(crate) fn group_color(colors: &Option<Vec<Color>>, index: usize, fallback: Color) -> Color {
+    match colors {
+        Some(colors) if !colors.is_empty() => colors[index % colors.len()],
+        _ => fallback,
+    }
+}
+
+impl Mobject for BoxPlot {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+        let style = self.style.with_opacity(self.style.opacity);
+        let point =
+            |x: f32, y: f32| to_screen(self.space.place(self.center, vec2(x, y)), screen_center);
+
+        for (index, stats) in self.stats.iter().enumerate() {
+            let Some(stats) = stats else {
+                continue;
+            };
+            // Boxes and whiskers open out from the median; outliers fade in at the end
+            let grow = |v: f32| stats.median + (v - stats.median) * t;
+            let x = index as f32;
+            let half = self.box_width / 2.0;
+
+            // Whiskers with caps
+            let stroke = style.stroke_color;
+            for (from, to) in [(stats.q1, stats.low), (stats.q3, stats.high)] {
+                let (a, b) = (point(x, grow(from)), point(x, grow(to)));
+                canvas.draw_line(a.x, a.y, b.x, b.y, style.stroke_weight, stroke);
+                let (l, r) = (
+                    point(x - half / 2.0, grow(to)),
+                    point(x + half / 2.0, grow(to)),
+                );
+                canvas.draw_line(l.x, l.y, r.x, r.y, style.stroke_weight, stroke);
+            }
+
+            // Box from Q1 to Q3
+            let (a, b) = (
+                point(x - half, grow(stats.q1)),
+                point(x + half, grow(stats.q3)),
+            );
+            let (min, size) = (a.min(b), (b - a).abs());
+            let mut fill = group_color(&self.group_colors, index, self.style.fill_color);
+            fill.a *= style.opacity;
+            if fill.a > 0.0 {
+                canvas.draw_rectangle(min.x, min.y, size.x, size.y, fill);
+            }
+            canvas.draw_rectangle_lines(min.x, min.y, size.x, size.y, style.stroke_weight, stroke);
+
+            // Median
+            let (l, r) = (point(x - half, stats.median), point(x + half, stats.median));
+            canvas.draw_line(l.x, l.y, r.x, r.y, style.stroke_weight * 1.5, stroke);
+
+            let fade = ((t - 0.7) / 0.3).clamp(0.0, 1.0);
+            if fade > 0.0 {
+                let color = Color::new(stroke.r, stroke.g, stroke.b, stroke.a * fade);
+                for &outlier in &stats.outliers {
+                    self.marker.draw(
+                        &mut canvas,
+                        point(x, outlier),
+                        self.marker_radius,
+                        Color::new(0.0, 0.0, 0.0, 0.0),
+                        color,
+                        style.stroke_weight,
+                    );
+                }
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        let (low, high) = self
+            .stats
+            .iter()
+            .flatten()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), s| {
+                let values = s.outliers.iter().chain([&s.low, &s.high]);
+                values.fold((lo, hi), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+            });
+        if low > high {
+            return BoundingRect::from_xy_wh(self.center, Vec2::ZERO);
+        }
+        let half = self.box_width / 2.0;
+        self.space.bounds(
+            vec2(-half, low),
+            vec2(self.groups.len() as f32 - 1.0 + half, high),
+            self.center,
+            self.scale,
+            self.rotation,
+        )
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_stats_and_outliers() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 30.0];
+        let stats = BoxStats::from_values(&values, Whiskers::default()).unwrap();
+        assert_eq!((stats.q1, stats.median, stats.q3), (3.0, 5.0, 7.0));
+        assert_eq!((stats.low, stats.high), (1.0, 8.0));
+        assert_eq!(stats.outliers, [30.0]);
+
+        let stats = BoxStats::from_values(&values, Whiskers::MinMax).unwrap();
+        assert_eq!(stats.high, 30.0);
+        assert!(stats.outliers.is_empty());
+        assert!(BoxStats::from_values(&[], Whiskers::MinMax).is_none());
+    }
+
+    #[test]
+    fn test_groups_fill_category_slots() {
+        let plot = BoxPlot::new()
+            .group("a", &[1.0, 2.0, 3.0])
+            .group("b", &[2.0, 4.0, 6.0]);
+        let axes = Axes2D::new()
+            .x_categories(plot.names().to_vec())
+            .y_range(0.0, 8.0)
+            .scale(40.0);
+        let plot = plot.on_axes(&axes);
+
+        assert_eq!(axes.x_ticks().major, [0.0, 1.0]);
+        let bounds = plot.bounding_box();
+        assert_eq!(bounds.w, 1.5 * 40.0);
+        assert_eq!(bounds.h, 5.0 * 40.0);
+    }
+}
@@ -0,0 +1,197 @@
+use super::{AxisScale, BoundingRect, MobjectId, to_screen};
+use crate::canvas::{Canvas, TransformedCanvas};
+use macroquad::prelude::*;
+
+/// Snapshot of how an `Axes2D` maps data coordinates into the scene
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxesFrame {
+    /// Scene position (center-origin, Y up) of the data origin, or of the
+    /// middle of the ranges when `centered`
+    pub center: Vec2,
+    pub x_range: (f32, f32),
+    pub y_range: (f32, f32),
+    pub x_scale: AxisScale,
+    pub y_scale: AxisScale,
+    /// Pixels per data unit, measured after the axis scales
+    pub scale: f32,
+    /// Pixels per y unit relative to `scale`; 1 unless the axes have a fixed size
+    pub aspect: f32,
+    /// Whether `center` is the middle of the ranges instead of the data origin
+    pub centered: bool,
+    /// Rotation of the axes about `center`, in radians counter-clockwise
+    pub rotation: f32,
+}
+
+impl AxesFrame {
+    /// Data point to scene coordinates, before the axes' rotation is applied
+    pub fn to_scene(&self, point: Vec2) -> Vec2 {
+        self.center + (self.forward(point) - self.anchor()) * self.unit()
+    }
+
+    /// Scene coordinates (before rotation) back to a data point
+    pub fn from_scene(&self, point: Vec2) -> Vec2 {
+        let scaled = (point - self.center) / self.unit() + self.anchor();
+        vec2(
+            self.x_scale.inverse(scaled.x),
+            self.y_scale.inverse(scaled.y),
+        )
+    }
+
+    /// Data point through the axis scales
+    fn forward(&self, point: Vec2) -> Vec2 {
+        vec2(self.x_scale.forward(point.x), self.y_scale.forward(point.y))
+    }
+
+    /// Pixels per unit along x and y
+    fn unit(&self) -> Vec2 {
+        vec2(self.scale, self.scale * self.aspect)
+    }
+
+    /// Data point (after the axis scales) that sits at `center`
+    fn anchor(&self) -> Vec2 {
+        if !self.centered {
+            return Vec2::ZERO;
+        }
+        let lo = self.forward(vec2(self.x_range.0, self.y_range.0));
+        let hi = self.forward(vec2(self.x_range.1, self.y_range.1));
+        (lo + hi) / 2.0
+    }
+}
+
+/// Coordinate space that a plot mobject's points are expressed in.
+///
+/// Plot mobjects start out in pixels around their center. Their `on_axes(&axes)`
+/// builder puts the points in the data coordinates of an `Axes2D` instead, and
+/// in a scene the mobject then follows the axes as they move, rescale or change
+/// range. `on_axes_id` does the same for axes known only by id, whose frame the
+/// scene supplies. Either way the mobject's own center, set with `at`, stays an
+/// extra pixel offset.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum PlotSpace {
+    /// Pixels relative to the mobject's center
+    #[default]
+    Pixels,
+    /// Data coordinates of an `Axes2D`. The frame is refreshed by the scene from
+    /// the axes' animated state every frame; until then the points are drawn as pixels.
+    Axes {
+        id: MobjectId,
+        frame: Option<AxesFrame>,
+    },
+}
+
+impl PlotSpace {
+    pub fn axes_id(&self) -> Option<MobjectId> {
+        match self {
+            PlotSpace::Pixels => None,
+            PlotSpace::Axes { id, .. } => Some(*id),
+        }
+    }
+
+    pub fn set_frame(&mut self, new_frame: AxesFrame) {
+        if let PlotSpace::Axes { frame, .. } = self {
+            *frame = Some(new_frame);
+        }
+    }
+
+    fn frame(&self) -> Option<&AxesFrame> {
+        match self {
+            PlotSpace::Axes { frame, .. } => frame.as_ref(),
+            PlotSpace::Pixels => None,
+        }
+    }
+
+    /// Scene position of `point` for a mobject at `center`.
+    /// On axes, the mobject's center acts as an extra pixel offset.
+    pub fn place(&self, center: Vec2, point: Vec2) -> Vec2 {
+        match self.frame() {
+            Some(frame) => frame.to_scene(point) + center,
+            None => center + point,
+        }
+    }
+
+    /// Point the mobject scales and rotates about
+    pub fn pivot(&self, center: Vec2) -> Vec2 {
+        match self.frame() {
+            Some(frame) => frame.center + center,
+            None => center,
+        }
+    }
+
+    /// Canvas that applies the mobject's own scale and rotation and then,
+    /// on axes, the rotation of the axes themselves
+    pub fn canvas<'a>(
+        &self,
+        canvas: &'a mut dyn Canvas,
+        screen_center: Vec2,
+        center: Vec2,
+        scale: f32,
+        rotation: f32,
+    ) -> TransformedCanvas<'a> {
+        let pivot = to_screen(self.pivot(center), screen_center);
+        let own = TransformedCanvas::transform_about(pivot, scale, rotation);
+        let transform = match self.frame() {
+            Some(frame) => {
+                let axes_pivot = to_screen(frame.center, screen_center);
+                TransformedCanvas::transform_about(axes_pivot, 1.0, frame.rotation) * own
+            }
+            None => own,
+        };
+        TransformedCanvas::new(canvas, transform)
+    }
+
+    /// Scene bounding box of points spanning `min..max`, after all transforms
+    pub fn bounds(
+        &self,
+        min: Vec2,
+        max: Vec2,
+        center: Vec2,
+        scale: f32,
+        rotation: f32,
+    ) -> BoundingRect {
+        let (a, b) = (self.place(center, min), self.place(center, max));
+        let rect = BoundingRect::from_corners(a.min(b), a.max(b));
+        let rect = rect.transformed_about(self.pivot(center), scale, rotation);
+        match self.frame() {
+            Some(frame) => rect.transformed_about(frame.center, 1.0, frame.rotation),
+            None => rect,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axes_space_places_data_points() {
+        let frame = AxesFrame {
+            center: vec2(100.0, -50.0),
+            x_range: (0.0, 10.0),
+            y_range: (0.0, 5.0),
+            x_scale: AxisScale::Linear,
+            y_scale: AxisScale::Linear,
+            scale: 20.0,
+            aspect: 1.0,
+            centered: false,
+            rotation: 0.0,
+        };
+        let mut space = PlotSpace::Axes {
+            id: MobjectId::new(),
+            frame: None,
+        };
+        assert_eq!(space.place(Vec2::ZERO, vec2(1.0, 2.0)), vec2(1.0, 2.0));
+
+        space.set_frame(frame.clone());
+        assert_eq!(space.place(Vec2::ZERO, vec2(1.0, 2.0)), vec2(120.0, -10.0));
+        assert_eq!(frame.from_scene(vec2(120.0, -10.0)), vec2(1.0, 2.0));
+
+        // On a log axis the same pixel distance covers a decade
+        space.set_frame(AxesFrame {
+            x_range: (1.0, 1000.0),
+            x_scale: AxisScale::Log10,
+            ..frame
+        });
+        assert_eq!(space.place(Vec2::ZERO, vec2(10.0, 0.0)).x, 120.0);
+        assert_eq!(space.place(Vec2::ZERO, vec2(100.0, 0.0)).x, 140.0);
+    }
+}
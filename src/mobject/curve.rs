@@ -1,4 +1,7 @@
-use super::{BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+use super::{
+    Axes2D, AxesFrame, BoundingRect, Mobject, MobjectId, MobjectStyle, PlotSpace, to_screen,
+};
+use crate::canvas::Canvas;
 use macroquad::prelude::*;
 
 /// A curve mobject for drawing smooth lines through points.
@@ -9,6 +12,7 @@ pub struct Curve {
     center: Vec2,
     points: Vec<Vec2>,
     style: MobjectStyle,
+    space: PlotSpace,
     scale: f32,
     rotation: f32,
 }
@@ -25,6 +29,7 @@ impl Curve {
                 stroke_weight: 2.5,
                 opacity: 1.0,
             },
+            space: PlotSpace::Pixels,
             scale: 1.0,
             rotation: 0.0,
         }
@@ -53,12 +58,32 @@ impl Curve {
         self
     }
 
-    /// Set the center offset (useful with Axes2D)
+    /// Replace the points of an existing curve, e.g. when recomputing a fit
+    pub(crate) fn set_points(&mut self, points: Vec<Vec2>) {
+        self.points = points;
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
     pub fn at(mut self, pos: Vec2) -> Self {
         self.center = pos;
         self
     }
 
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
     pub fn color(mut self, color: Color) -> Self {
         self.style.stroke_color = color;
         self
@@ -80,6 +105,42 @@ impl Curve {
     pub fn get_points(&self) -> &[Vec2] {
         &self.points
     }
+
+    /// Height of the curve at `x`, interpolating linearly between points (which
+    /// should be ordered by x). Beyond either end the end point's height is used.
+    pub fn value_at(&self, x: f32) -> Option<f32> {
+        interpolate(&self.points, x)
+    }
+
+    /// Coordinate space of the points, so mobjects built from the curve can share it
+    pub(crate) fn space(&self) -> &PlotSpace {
+        &self.space
+    }
+}
+
+/// Height at `x` of a polyline ordered by x, interpolating linearly; beyond
+/// either end the end point's height is used
+pub(crate) fn interpolate(points: &[Vec2], x: f32) -> Option<f32> {
+    let (first, last) = (points.first()?, points.last()?);
+    let inside = points.windows(2).find_map(|w| {
+        let (a, b) = (w[0], w[1]);
+        let (lo, hi) = (a.x.min(b.x), a.x.max(b.x));
+        (lo <= x && x <= hi).then(|| {
+            if hi > lo {
+                a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x)
+            } else {
+                a.y
+            }
+        })
+    });
+    let nearest_end = || {
+        if (x - first.x).abs() <= (x - last.x).abs() {
+            first.y
+        } else {
+            last.y
+        }
+    };
+    Some(inside.unwrap_or_else(nearest_end))
 }
 
 impl Default for Curve {
@@ -89,7 +150,15 @@ impl Default for Curve {
 }
 
 impl Mobject for Curve {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+
         if self.points.len() < 2 {
             return;
         }
@@ -100,22 +169,13 @@ impl Mobject for Curve {
         let points_to_draw = ((self.points.len() as f32) * t).ceil() as usize;
         let points_to_draw = points_to_draw.max(2).min(self.points.len());
 
-        // Convert points to screen coordinates and draw line segments
+        // Convert points to screen coordinates and draw as a polyline
         let screen_points: Vec<Vec2> = self.points[..points_to_draw]
             .iter()
-            .map(|p| to_screen(self.center + *p, screen_center))
+            .map(|p| to_screen(self.space.place(self.center, *p), screen_center))
             .collect();
 
-        for i in 0..screen_points.len().saturating_sub(1) {
-            draw_line(
-                screen_points[i].x,
-                screen_points[i].y,
-                screen_points[i + 1].x,
-                screen_points[i + 1].y,
-                style.stroke_weight,
-                style.stroke_color,
-            );
-        }
+        canvas.draw_polyline(&screen_points, style.stroke_weight, style.stroke_color);
     }
 
     fn bounding_box(&self) -> BoundingRect {
@@ -132,7 +192,8 @@ impl Mobject for Curve {
             .iter()
             .fold(Vec2::splat(f32::MIN), |acc, p| acc.max(*p));
 
-        BoundingRect::from_corners(self.center + min, self.center + max)
+        self.space
+            .bounds(min, max, self.center, self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -159,6 +220,10 @@ impl Mobject for Curve {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -171,6 +236,22 @@ impl Mobject for Curve {
         self.id
     }
 
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
+    fn curve_points(&self) -> Option<&[Vec2]> {
+        Some(&self.points)
+    }
+
+    fn set_curve_points(&mut self, points: Vec<Vec2>) {
+        self.points = points;
+    }
+
     fn clone_box(&self) -> Box<dyn Mobject> {
         Box::new(self.clone())
     }
@@ -185,6 +266,7 @@ pub struct ConfidenceBand {
     upper: Vec<Vec2>,
     lower: Vec<Vec2>,
     style: MobjectStyle,
+    space: PlotSpace,
     scale: f32,
     rotation: f32,
 }
@@ -202,6 +284,7 @@ impl ConfidenceBand {
                 stroke_weight: 0.0,
                 opacity: 1.0,
             },
+            space: PlotSpace::Pixels,
             scale: 1.0,
             rotation: 0.0,
         }
@@ -235,12 +318,37 @@ impl ConfidenceBand {
         self
     }
 
-    /// Set the center offset (useful with Axes2D)
+    pub(crate) fn set_bounds(&mut self, lower: Vec<Vec2>, upper: Vec<Vec2>) {
+        self.lower = lower;
+        self.upper = upper;
+    }
+
+    /// Lower and upper bound points
+    pub fn get_bounds(&self) -> (&[Vec2], &[Vec2]) {
+        (&self.lower, &self.upper)
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
     pub fn at(mut self, pos: Vec2) -> Self {
         self.center = pos;
         self
     }
 
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
     pub fn fill(mut self, color: Color) -> Self {
         self.style.fill_color = color;
         self
@@ -264,7 +372,15 @@ impl Default for ConfidenceBand {
 }
 
 impl Mobject for ConfidenceBand {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+
         if self.lower.len() < 2 || self.upper.len() < 2 {
             return;
         }
@@ -276,48 +392,24 @@ impl Mobject for ConfidenceBand {
         let points_to_draw = ((n as f32) * t).ceil() as usize;
         let points_to_draw = points_to_draw.max(2).min(n);
 
-        // Draw fill as triangles (triangle strip approach)
-        if style.fill_color.a > 0.0 && points_to_draw >= 2 {
-            for i in 0..points_to_draw - 1 {
-                let lower_left = to_screen(self.center + self.lower[i], screen_center);
-                let lower_right = to_screen(self.center + self.lower[i + 1], screen_center);
-                let upper_left = to_screen(self.center + self.upper[i], screen_center);
-                let upper_right = to_screen(self.center + self.upper[i + 1], screen_center);
-
-                // Draw two triangles to form a quad
-                draw_triangle(lower_left, lower_right, upper_left, style.fill_color);
-                draw_triangle(upper_left, lower_right, upper_right, style.fill_color);
-            }
+        // Draw fill as a single polygon: upper bound forward, lower bound back
+        if style.fill_color.a > 0.0 {
+            let outline: Vec<Vec2> = self.upper[..points_to_draw]
+                .iter()
+                .chain(self.lower[..points_to_draw].iter().rev())
+                .map(|p| to_screen(self.space.place(self.center, *p), screen_center))
+                .collect();
+            canvas.draw_polygon(&outline, style.fill_color);
         }
 
         // Draw stroke outlines if needed
         if style.stroke_color.a > 0.0 {
-            // Lower bound line
-            for i in 0..points_to_draw.saturating_sub(1) {
-                let p1 = to_screen(self.center + self.lower[i], screen_center);
-                let p2 = to_screen(self.center + self.lower[i + 1], screen_center);
-                draw_line(
-                    p1.x,
-                    p1.y,
-                    p2.x,
-                    p2.y,
-                    style.stroke_weight,
-                    style.stroke_color,
-                );
-            }
-
-            // Upper bound line
-            for i in 0..points_to_draw.saturating_sub(1) {
-                let p1 = to_screen(self.center + self.upper[i], screen_center);
-                let p2 = to_screen(self.center + self.upper[i + 1], screen_center);
-                draw_line(
-                    p1.x,
-                    p1.y,
-                    p2.x,
-                    p2.y,
-                    style.stroke_weight,
-                    style.stroke_color,
-                );
+            for bound in [&self.lower, &self.upper] {
+                let screen_points: Vec<Vec2> = bound[..points_to_draw]
+                    .iter()
+                    .map(|p| to_screen(self.space.place(self.center, *p), screen_center))
+                    .collect();
+                canvas.draw_polyline(&screen_points, style.stroke_weight, style.stroke_color);
             }
         }
     }
@@ -333,7 +425,8 @@ impl Mobject for ConfidenceBand {
             .fold(Vec2::splat(f32::MAX), |acc, p| acc.min(*p));
         let max = all_points.fold(Vec2::splat(f32::MIN), |acc, p| acc.max(*p));
 
-        BoundingRect::from_corners(self.center + min, self.center + max)
+        self.space
+            .bounds(min, max, self.center, self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -360,6 +453,10 @@ impl Mobject for ConfidenceBand {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -372,6 +469,14 @@ impl Mobject for ConfidenceBand {
         self.id
     }
 
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
     fn clone_box(&self) -> Box<dyn Mobject> {
         Box::new(self.clone())
     }
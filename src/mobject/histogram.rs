@@ -0,0 +1,431 @@
+use super::{
+    Axes2D, AxesFrame, BoundingRect, Mobject, MobjectId, MobjectStyle, PlotSpace, staggered_growth,
+    to_screen,
+};
+use crate::canvas::Canvas;
+use crate::data::Column;
+use crate::state::Dataset;
+use crate::stats::describe::{self, QuantileMethod};
+use macroquad::prelude::*;
+
+/// Upper limit on the number of bins any rule may produce
+const MAX_BINS: usize = 10_000;
+
+/// How a histogram's value range is split into bins
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Binning {
+    /// A fixed number of equal-width bins
+    Count(usize),
+    /// Bins of a fixed width, starting at the low end of the range
+    Width(f32),
+    /// `⌈log₂ n⌉ + 1` bins; suits small, roughly normal samples
+    #[default]
+    Sturges,
+    /// Width `2·IQR·n^(-1/3)`; robust to outliers and heavy tails
+    FreedmanDiaconis,
+    /// Width `3.49·σ·n^(-1/3)`; optimal for normal data
+    Scott,
+}
+
+/// What the bar heights measure
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Normalization {
+    /// Number of values in each bin
+    #[default]
+    Count,
+    /// Fraction of all values in each bin; heights sum to 1
+    Probability,
+    /// Fraction per unit width; bar areas sum to 1, comparable with a density curve
+    Density,
+}
+
+/// A histogram of 1-D values drawn as bars from a zero baseline
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    id: MobjectId,
+    center: Vec2,
+    values: Vec<f32>,
+    binning: Binning,
+    normalization: Normalization,
+    /// Value range to bin, or None for the data's extent
+    range: Option<(f32, f32)>,
+    edges: Vec<f32>,
+    heights: Vec<f32>,
+    /// Empty space between neighbouring bars, as a fraction of the bin width
+    bar_gap: f32,
+    style: MobjectStyle,
+    space: PlotSpace,
+    scale: f32,
+    rotation: f32,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            values: Vec::new(),
+            binning: Binning::default(),
+            normalization: Normalization::default(),
+            range: None,
+            edges: Vec::new(),
+            heights: Vec::new(),
+            bar_gap: 0.0,
+            style: MobjectStyle {
+                stroke_color: Color::new(0.3, 0.6, 1.0, 1.0),
+                fill_color: Color::new(0.3, 0.6, 1.0, 0.5),
+                stroke_weight: 1.0,
+                opacity: 1.0,
+            },
+            space: PlotSpace::Pixels,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// Histogram of `values`; non-finite values are ignored
+    pub fn from_values(values: &[f32]) -> Self {
+        let mut histogram = Self::new();
+        histogram.values = values.iter().copied().filter(|v| v.is_finite()).collect();
+        histogram.rebin();
+        histogram
+    }
+
+    /// Histogram of a dataset column, by name or position
+    pub fn from_dataset(data: &Dataset, column: impl Into<Column>) -> Self {
+        Self::from_values(&data.values(column).unwrap_or_default())
+    }
+
+    pub fn binning(mut self, binning: Binning) -> Self {
+        self.binning = binning;
+        self.rebin();
+        self
+    }
+
+    /// Shorthand for `binning(Binning::Count(count))`
+    pub fn bins(self, count: usize) -> Self {
+        self.binning(Binning::Count(count))
+    }
+
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self.rebin();
+        self
+    }
+
+    /// Bin only values within `min..=max` instead of the data's extent
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min.min(max), min.max(max)));
+        self.rebin();
+        self
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
+    /// Gap between bars as a fraction (0-1) of the bin width
+    pub fn bar_gap(mut self, gap: f32) -> Self {
+        self.bar_gap = gap.clamp(0.0, 0.95);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.fill_color = color;
+        self
+    }
+
+    pub fn stroke_color(mut self, color: Color) -> Self {
+        self.style.stroke_color = color;
+        self
+    }
+
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.style.stroke_weight = weight;
+        self
+    }
+
+    /// Bin boundaries, one more than the number of bins
+    pub fn edges(&self) -> &[f32] {
+        &self.edges
+    }
+
+    /// Bar heights in the chosen normalization
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    pub fn bin_count(&self) -> usize {
+        self.heights.len()
+    }
+
+    /// Recompute edges and heights after the values or settings changed
+    fn rebin(&mut self) {
+        let (lo, hi) = match self.range {
+            Some(range) => range,
+            None => self
+                .values
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v))),
+        };
+        if lo > hi {
+            self.edges.clear();
+            self.heights.clear();
+            return;
+        }
+        // All values equal: a unit-wide bin around them
+        let (lo, hi) = if hi > lo {
+            (lo, hi)
+        } else {
+            (lo - 0.5, hi + 0.5)
+        };
+
+        let count = bin_count(self.binning, &self.values, lo, hi);
+        let width = match self.binning {
+            Binning::Width(width) if width > 0.0 && count < MAX_BINS => width,
+            _ => (hi - lo) / count as f32,
+        };
+        self.edges = (0..=count).map(|i| lo + i as f32 * width).collect();
+
+        let mut counts = vec![0usize; count];
+        for &v in &self.values {
+            if v < lo || v > hi {
+                continue;
+            }
+            // The last bin is closed so the maximum is counted
+            let bin = (((v - lo) / width) as usize).min(count - 1);
+            counts[bin] += 1;
+        }
+
+        let total: usize = counts.iter().sum();
+        let total = total.max(1) as f32;
+        self.heights = counts
+            .into_iter()
+            .map(|c| match self.normalization {
+                Normalization::Count => c as f32,
+                Normalization::Probability => c as f32 / total,
+                Normalization::Density => c as f32 / (total * width),
+            })
+            .collect();
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Number of bins `binning` gives for `values` over `lo..hi` (with `hi > lo`)
+fn bin_count(binning: Binning, values: &[f32], lo: f32, hi: f32) -> usize {
+    let span = hi - lo;
+    let n = values.len().max(1) as f32;
+    let sturges = n.log2().ceil() as usize + 1;
+    let from_width = |width: f32| {
+        if width > 0.0 && width.is_finite() {
+            (span / width).ceil() as usize
+        } else {
+            sturges
+        }
+    };
+    let count = match binning {
+        Binning::Count(count) => count,
+        Binning::Width(width) => from_width(width),
+        Binning::Sturges => sturges,
+        Binning::FreedmanDiaconis => {
+            let iqr = describe::iqr(values, QuantileMethod::Linear);
+            from_width(2.0 * iqr * n.powf(-1.0 / 3.0))
+        }
+        Binning::Scott => from_width(3.49 * describe::sd(values) * n.powf(-1.0 / 3.0)),
+    };
+    count.clamp(1, MAX_BINS)
+}
+
+impl Mobject for Histogram {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+        let style = self.style.with_opacity(self.style.opacity);
+
+        // Bars grow from the baseline
+        let n = self.heights.len();
+        for (i, &height) in self.heights.iter().enumerate() {
+            let growth = staggered_growth(i, n, t);
+            if growth <= 0.0 || height == 0.0 {
+                continue;
+            }
+
+            let inset = (self.edges[i + 1] - self.edges[i]) * self.bar_gap / 2.0;
+            let a = self
+                .space
+                .place(self.center, vec2(self.edges[i] + inset, 0.0));
+            let b = self.space.place(
+                self.center,
+                vec2(self.edges[i + 1] - inset, height * growth),
+            );
+            let (a, b) = (to_screen(a, screen_center), to_screen(b, screen_center));
+            let (min, size) = (a.min(b), (b - a).abs());
+
+            if style.fill_color.a > 0.0 {
+                canvas.draw_rectangle(min.x, min.y, size.x, size.y, style.fill_color);
+            }
+            if style.stroke_color.a > 0.0 && style.stroke_weight > 0.0 {
+                canvas.draw_rectangle_lines(
+                    min.x,
+                    min.y,
+                    size.x,
+                    size.y,
+                    style.stroke_weight,
+                    style.stroke_color,
+                );
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        let (Some(&first), Some(&last)) = (self.edges.first(), self.edges.last()) else {
+            return BoundingRect::from_xy_wh(self.center, Vec2::ZERO);
+        };
+        let top = self.heights.iter().fold(0f32, |acc, &h| acc.max(h));
+        self.space.bounds(
+            vec2(first, 0.0),
+            vec2(last, top),
+            self.center,
+            self.scale,
+            self.rotation,
+        )
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binning_rules_and_normalization() {
+        let values: Vec<f32> = (0..100).map(|i| i as f32 / 10.0).collect();
+
+        let histogram = Histogram::from_values(&values).bins(5);
+        assert_eq!(histogram.edges().len(), 6);
+        assert_eq!(histogram.heights(), [20.0; 5]);
+
+        let sturges = Histogram::from_values(&values);
+        assert_eq!(sturges.bin_count(), 8);
+
+        let by_width = Histogram::from_values(&values).binning(Binning::Width(2.5));
+        assert_eq!(by_width.edges(), [0.0, 2.5, 5.0, 7.5, 10.0]);
+
+        let density = Histogram::from_values(&values)
+            .binning(Binning::FreedmanDiaconis)
+            .normalization(Normalization::Density);
+        let area: f32 = density
+            .heights()
+            .iter()
+            .zip(density.edges().windows(2))
+            .map(|(h, e)| h * (e[1] - e[0]))
+            .sum();
+        assert!((area - 1.0).abs() < 1e-4);
+
+        let probability = Histogram::from_values(&values)
+            .binning(Binning::Scott)
+            .normalization(Normalization::Probability);
+        assert!((probability.heights().iter().sum::<f32>() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_bars_grow_from_baseline() {
+        use crate::canvas::{DrawCommand, RecordingCanvas};
+
+        let histogram = Histogram::from_values(&[1.0, 2.0, 2.0]).bins(2);
+        let bar_heights = |t| {
+            let mut canvas = RecordingCanvas::new();
+            histogram.draw(&mut canvas, t, Vec2::ZERO);
+            canvas
+                .commands()
+                .iter()
+                .filter_map(|c| match c {
+                    DrawCommand::Rectangle { size, .. } => Some(size.y),
+                    _ => None,
+                })
+                .collect::<Vec<f32>>()
+        };
+        assert!(bar_heights(0.0).is_empty());
+        assert_eq!(bar_heights(1.0), [1.0, 2.0]);
+        let halfway = bar_heights(0.5);
+        assert!(halfway[0] > 0.5 && halfway[0] < 1.0);
+    }
+}
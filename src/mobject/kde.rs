@@ -0,0 +1,356 @@
+use super::{Axes2D, AxesFrame, BoundingRect, ConfidenceBand, Curve, Mobject, MobjectId};
+use crate::canvas::Canvas;
+use crate::data::Column;
+use crate::state::Dataset;
+use crate::stats::describe::{self, QuantileMethod};
+use macroquad::prelude::*;
+
+/// Kernel functions for density estimation, each integrating to 1
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Kernel {
+    #[default]
+    Gaussian,
+    /// `¾(1 - u²)` on -1..1; the most efficient kernel
+    Epanechnikov,
+    /// Flat on -1..1
+    Uniform,
+    /// `1 - |u|` on -1..1
+    Triangular,
+    /// `15/16 (1 - u²)²` on -1..1
+    Biweight,
+    /// `π/4 cos(πu/2)` on -1..1
+    Cosine,
+}
+
+impl Kernel {
+    pub fn weight(&self, u: f32) -> f32 {
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+        if !matches!(self, Kernel::Gaussian) && u.abs() > 1.0 {
+            return 0.0;
+        }
+        match self {
+            Kernel::Gaussian => (-0.5 * u * u).exp() / (2.0 * PI).sqrt(),
+            Kernel::Epanechnikov => 0.75 * (1.0 - u * u),
+            Kernel::Uniform => 0.5,
+            Kernel::Triangular => 1.0 - u.abs(),
+            Kernel::Biweight => 15.0 / 16.0 * (1.0 - u * u).powi(2),
+            Kernel::Cosine => FRAC_PI_4 * (FRAC_PI_2 * u).cos(),
+        }
+    }
+
+    /// Distance in bandwidths beyond which the kernel is (practically) zero
+    pub(crate) fn reach(&self) -> f32 {
+        match self {
+            Kernel::Gaussian => 3.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// How the kernel width is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Bandwidth {
+    /// Silverman's rule of thumb `0.9·min(σ, IQR/1.34)·n^(-1/5)`
+    #[default]
+    Silverman,
+    /// Scott's rule `1.06·σ·n^(-1/5)`
+    Scott,
+    /// A fixed bandwidth in data units
+    Manual(f32),
+}
+
+impl Bandwidth {
+    /// Bandwidth in data units for `values`; falls back to 1 for degenerate data
+    pub fn resolve(&self, values: &[f32]) -> f32 {
+        let n = values.len() as f32;
+        let spread = || describe::sd(values);
+        let h = match self {
+            Bandwidth::Manual(h) => *h,
+            Bandwidth::Scott => 1.06 * spread() * n.powf(-0.2),
+            Bandwidth::Silverman => {
+                let iqr = describe::iqr(values, QuantileMethod::Linear);
+                let sigma = spread();
+                let scale = if iqr > 0.0 {
+                    sigma.min(iqr / 1.34)
+                } else {
+                    sigma
+                };
+                0.9 * scale * n.powf(-0.2)
+            }
+        };
+        if h > 0.0 && h.is_finite() { h } else { 1.0 }
+    }
+}
+
+/// A kernel density estimate of 1-D data, drawn as a `Curve` and optionally
+/// filled down to zero. The bandwidth can be animated with `SetBandwidth`.
+#[derive(Debug, Clone)]
+pub struct KdeCurve {
+    values: Vec<f32>,
+    kernel: Kernel,
+    bandwidth: Bandwidth,
+    samples: usize,
+    /// Evaluation range, or None for the data's extent widened by the kernel's reach
+    range: Option<(f32, f32)>,
+    curve: Curve,
+    /// Area under the curve; transparent unless `fill` is set
+    area: ConfidenceBand,
+}
+
+impl KdeCurve {
+    /// Density estimate of `values`; non-finite values are ignored
+    pub fn from_values(values: &[f32]) -> Self {
+        let mut kde = Self {
+            values: values.iter().copied().filter(|v| v.is_finite()).collect(),
+            kernel: Kernel::default(),
+            bandwidth: Bandwidth::default(),
+            samples: 200,
+            range: None,
+            curve: Curve::new(),
+            area: ConfidenceBand::new().fill(Color::new(0.0, 0.0, 0.0, 0.0)),
+        };
+        kde.refresh();
+        kde
+    }
+
+    /// Density estimate of a dataset column, by name or position
+    pub fn from_dataset(data: &Dataset, column: impl Into<Column>) -> Self {
+        Self::from_values(&data.values(column).unwrap_or_default())
+    }
+
+    pub fn kernel(mut self, kernel: Kernel) -> Self {
+        self.kernel = kernel;
+        self.refresh();
+        self
+    }
+
+    pub fn bandwidth(mut self, bandwidth: Bandwidth) -> Self {
+        self.bandwidth = bandwidth;
+        self.refresh();
+        self
+    }
+
+    /// Number of points the density is evaluated at
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(2);
+        self.refresh();
+        self
+    }
+
+    /// Evaluate the density over `min..max` instead of around the data
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self.refresh();
+        self
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`](super::PlotSpace)
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.curve = self.curve.at(pos);
+        self.area = self.area.at(pos);
+        self
+    }
+
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`](super::PlotSpace)
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.curve = self.curve.on_axes(axes);
+        self.area = self.area.on_axes(axes);
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`](super::PlotSpace)
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.curve = self.curve.on_axes_id(id);
+        self.area = self.area.on_axes_id(id);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.curve = self.curve.color(color);
+        self
+    }
+
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.curve = self.curve.stroke_weight(weight);
+        self
+    }
+
+    /// Fill the area under the density
+    pub fn fill(mut self, color: Color) -> Self {
+        self.area = self.area.fill(color);
+        self
+    }
+
+    /// Estimated density at `x`
+    pub fn density_at(&self, x: f32) -> f32 {
+        let h = self.bandwidth.resolve(&self.values);
+        density(&self.values, self.kernel, h, x)
+    }
+
+    /// Bandwidth in data units after applying the selection rule
+    pub fn bandwidth_value(&self) -> f32 {
+        self.bandwidth.resolve(&self.values)
+    }
+
+    pub fn get_points(&self) -> &[Vec2] {
+        self.curve.get_points()
+    }
+
+    /// Re-evaluate the density after the data or settings changed
+    fn refresh(&mut self) {
+        let h = self.bandwidth.resolve(&self.values);
+        let (lo, hi) = self.range.unwrap_or_else(|| {
+            let (lo, hi) = self
+                .values
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+            let reach = self.kernel.reach() * h;
+            if lo > hi {
+                (-reach, reach)
+            } else {
+                (lo - reach, hi + reach)
+            }
+        });
+
+        let points: Vec<Vec2> = (0..self.samples)
+            .map(|i| {
+                let x = lo + (hi - lo) * i as f32 / (self.samples - 1) as f32;
+                vec2(x, density(&self.values, self.kernel, h, x))
+            })
+            .collect();
+        let baseline = points.iter().map(|p| vec2(p.x, 0.0)).collect();
+        self.area.set_bounds(baseline, points.clone());
+        self.curve.set_points(points);
+    }
+}
+
+/// Kernel density of `values` at `x` with bandwidth `h`
+pub(crate) fn density(values: &[f32], kernel: Kernel, h: f32, x: f32) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = values.iter().map(|v| kernel.weight((x - v) / h)).sum();
+    sum / (values.len() as f32 * h)
+}
+
+impl Mobject for KdeCurve {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        self.area.draw(canvas, t, screen_center);
+        self.curve.draw(canvas, t, screen_center);
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        // The area spans the curve and its baseline
+        self.area.bounding_box()
+    }
+
+    fn center(&self) -> Vec2 {
+        self.curve.center()
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.curve.set_center(pos);
+        self.area.set_center(pos);
+    }
+
+    fn opacity(&self) -> f32 {
+        self.curve.opacity()
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.curve.set_opacity(opacity);
+        self.area.set_opacity(opacity);
+    }
+
+    fn scale(&self) -> f32 {
+        self.curve.scale()
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.curve.set_scale(scale);
+        self.area.set_scale(scale);
+    }
+
+    fn rotation(&self) -> f32 {
+        self.curve.rotation()
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.curve.rotate(angle);
+        self.area.rotate(angle);
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.curve.set_rotate(angle);
+        self.area.set_rotate(angle);
+    }
+
+    fn id(&self) -> MobjectId {
+        self.curve.id()
+    }
+
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.curve.bound_axes()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.area.set_axes_frame(frame.clone());
+        self.curve.set_axes_frame(frame);
+    }
+
+    fn bandwidth(&self) -> Option<f32> {
+        Some(self.bandwidth_value())
+    }
+
+    fn set_bandwidth(&mut self, bandwidth: f32) {
+        self.bandwidth = Bandwidth::Manual(bandwidth);
+        self.refresh();
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Animation, SetBandwidth};
+
+    fn area(kde: &KdeCurve) -> f32 {
+        kde.get_points()
+            .windows(2)
+            .map(|w| (w[1].x - w[0].x) * (w[0].y + w[1].y) / 2.0)
+            .sum()
+    }
+
+    #[test]
+    fn test_density_integrates_to_one() {
+        let values = [-1.2, -0.4, 0.0, 0.3, 0.35, 0.9, 2.1];
+        for kernel in [Kernel::Gaussian, Kernel::Epanechnikov, Kernel::Biweight] {
+            let kde = KdeCurve::from_values(&values).kernel(kernel).samples(2000);
+            assert!((area(&kde) - 1.0).abs() < 0.01, "{:?}", kernel);
+        }
+
+        // Silverman for these values: 0.9 · min(σ, IQR/1.34) · 7^(-1/5)
+        let h = KdeCurve::from_values(&values).bandwidth_value();
+        assert!((h - 0.3755).abs() < 1e-3, "{}", h);
+    }
+
+    #[test]
+    fn test_set_bandwidth_reevaluates() {
+        let mut kde = KdeCurve::from_values(&[0.0, 1.0]).bandwidth(Bandwidth::Manual(0.1));
+        let peak = kde.density_at(0.0);
+        kde.set_bandwidth(1.0);
+        assert_eq!(Mobject::bandwidth(&kde), Some(1.0));
+        // A non-positive target is ignored rather than turning the bandwidth into NaN
+        SetBandwidth::new(kde.id(), 0.0).apply(&mut kde, 0.5);
+        assert_eq!(Mobject::bandwidth(&kde), Some(1.0));
+        assert!(kde.density_at(0.0) < peak);
+        assert!(
+            kde.get_points()
+                .iter()
+                .any(|p| (p.y - kde.density_at(p.x)).abs() < 1e-6)
+        );
+    }
+}
@@ -0,0 +1,1116 @@
+use super::{Anchor, BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+use crate::canvas::{Canvas, Font, TextStyle, TransformedCanvas};
+use macroquad::prelude::*;
+
+/// Size of scripts and limits relative to their base
+const SCRIPT_RATIO: f32 = 0.7;
+/// Size of fraction numerators and denominators relative to the surrounding text
+const FRACTION_RATIO: f32 = 0.85;
+/// Size of big operators (sums, products, integrals) relative to the surrounding text
+const BIG_OP_RATIO: f32 = 1.4;
+/// Thickness of fraction bars, radicals and accents, in em
+const RULE_THICKNESS: f32 = 0.05;
+
+/// A formula rendered from a practical subset of TeX math, laid out with the
+/// bundled font so no TeX installation is needed.
+///
+/// Supported: `^` and `_` scripts, `{}` groups, `\frac`, `\sqrt`, `\hat`, `\bar`,
+/// `\overline`, `\tilde`, `\dot`, `\vec`, `\sum`/`\prod`/`\int` with limits,
+/// Greek letters, common relations and operators, `\text{..}`/`\mathrm{..}`,
+/// named functions like `\log` and the spacing commands `\,` `\;` `\quad`.
+/// Unicode input such as `ŷ = β₀ + β₁x` is passed through as is.
+/// Unknown commands are drawn literally rather than rejected.
+#[derive(Debug, Clone)]
+pub struct MathTex {
+    id: MobjectId,
+    center: Vec2,
+    source: String,
+    nodes: Vec<Node>,
+    font: Font,
+    font_size: f32,
+    anchor: Anchor,
+    style: MobjectStyle,
+    scale: f32,
+    rotation: f32,
+}
+
+impl MathTex {
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            nodes: Parser::new(&source).parse(),
+            source,
+            font: Font::default(),
+            font_size: 32.0,
+            anchor: Anchor::Center,
+            style: MobjectStyle {
+                fill_color: WHITE,
+                ..Default::default()
+            },
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// Plain text with `$...$` math segments, like a matplotlib label: `"Residual $e_i$"`
+    pub fn mixed(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let nodes = source
+            .split('$')
+            .enumerate()
+            .filter(|(_, segment)| !segment.is_empty())
+            .map(|(i, segment)| {
+                if i % 2 == 0 {
+                    Node::Text(segment.to_string())
+                } else {
+                    Node::Group(Parser::new(segment).parse())
+                }
+            })
+            .collect();
+        Self {
+            nodes,
+            ..Self::new(source)
+        }
+    }
+
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.fill_color = color;
+        self
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Width and height of the laid out formula
+    pub fn size(&self) -> Vec2 {
+        let layout = self.layout();
+        vec2(layout.width, layout.height())
+    }
+
+    fn layout(&self) -> MathBox {
+        Layout {
+            font: &self.font,
+            base_size: self.font_size,
+        }
+        .list(&self.nodes, self.font_size)
+    }
+
+    /// Left end of the baseline in center-origin coordinates (Y up)
+    fn origin(&self, layout: &MathBox) -> Vec2 {
+        let offset = self.anchor.fraction() * vec2(layout.width, layout.height());
+        vec2(
+            self.center.x - offset.x,
+            self.center.y + offset.y - layout.ascent,
+        )
+    }
+}
+
+impl Mobject for MathTex {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let pivot = to_screen(self.center, screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, self.scale, self.rotation);
+
+        let style = self.style.with_opacity(self.style.opacity);
+        if style.fill_color.a <= 0.0 {
+            return;
+        }
+        let color = style.fill_color;
+
+        let layout = self.layout();
+        let origin = self.origin(&layout);
+        let screen = |p: Vec2| to_screen(origin + p, screen_center);
+
+        // Create reveals the formula piece by piece in reading order
+        let visible = ((layout.items.len() as f32) * t).ceil() as usize;
+
+        for item in layout.items.iter().take(visible) {
+            match item {
+                MathItem::Glyphs {
+                    text,
+                    position,
+                    size,
+                } => {
+                    let p = screen(*position);
+                    let text_style = TextStyle {
+                        font: self.font.clone(),
+                        font_size: *size,
+                        color,
+                    };
+                    canvas.draw_text(text, p.x, p.y, &text_style);
+                }
+                MathItem::Stroke { points, thickness } => {
+                    let points: Vec<Vec2> = points.iter().map(|p| screen(*p)).collect();
+                    canvas.draw_polyline(&points, *thickness, color);
+                }
+                MathItem::Dot { center, radius } => {
+                    let c = screen(*center);
+                    canvas.draw_circle(c.x, c.y, *radius, color);
+                }
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        let layout = self.layout();
+        let origin = self.origin(&layout);
+        let rect = BoundingRect::from_corners(
+            origin - vec2(0.0, layout.descent),
+            origin + vec2(layout.width, layout.ascent),
+        );
+        rect.transformed_about(self.center, self.scale, self.rotation)
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Spacing class of a symbol, following TeX's atom types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Ord,
+    /// Binary operator: medium space on both sides unless used as a prefix
+    Bin,
+    /// Relation: thick space on both sides
+    Rel,
+    /// Punctuation: thin space after
+    Punct,
+    /// Named function like `\log`: thin space after
+    Op,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Accent {
+    Hat,
+    Bar,
+    Overline,
+    Tilde,
+    Dot,
+    Vec,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Symbol(String, Class),
+    /// Upright text that keeps its spaces
+    Text(String),
+    /// Horizontal space in em
+    Space(f32),
+    Group(Vec<Node>),
+    Scripts {
+        base: Box<Node>,
+        sub: Option<Box<Node>>,
+        sup: Option<Box<Node>>,
+    },
+    Frac(Box<Node>, Box<Node>),
+    Sqrt(Box<Node>),
+    Accent(Accent, Box<Node>),
+    /// Sum-like operator; with `limits` the scripts go below and above
+    BigOp {
+        symbol: char,
+        limits: bool,
+        sub: Option<Box<Node>>,
+        sup: Option<Box<Node>>,
+    },
+}
+
+/// Recursive-descent parser that never fails: unbalanced braces are closed
+/// implicitly and unknown commands become literal symbols
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn parse(mut self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        // Stray closing braces at the top level are skipped
+        while self.peek().is_some() {
+            nodes.extend(self.list());
+            self.next();
+        }
+        nodes
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Nodes up to (not including) the next unmatched '}' or the end
+    fn list(&mut self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some('}') => break,
+                Some(c @ ('^' | '_')) => {
+                    self.next();
+                    let arg = self.argument();
+                    attach_script(&mut nodes, c == '^', arg);
+                }
+                Some(_) => {
+                    let atom = self.atom();
+                    nodes.push(atom);
+                }
+            }
+        }
+        nodes
+    }
+
+    fn atom(&mut self) -> Node {
+        match self.next() {
+            Some('{') => self.group(),
+            Some('\\') => self.command(),
+            Some(c) => char_symbol(c),
+            None => Node::Group(Vec::new()),
+        }
+    }
+
+    /// Body of a `{...}` group whose opening brace was already consumed
+    fn group(&mut self) -> Node {
+        let nodes = self.list();
+        self.next();
+        Node::Group(nodes)
+    }
+
+    /// Argument of a command or script: a group or a single atom
+    fn argument(&mut self) -> Node {
+        self.skip_whitespace();
+        self.atom()
+    }
+
+    /// Raw contents of a `{...}` group, for text-mode commands
+    fn raw_argument(&mut self) -> String {
+        self.skip_whitespace();
+        if self.peek() != Some('{') {
+            return self.next().map(String::from).unwrap_or_default();
+        }
+        self.next();
+        let mut depth = 0;
+        let mut text = String::new();
+        while let Some(c) = self.next() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            text.push(c);
+        }
+        text
+    }
+
+    fn command(&mut self) -> Node {
+        let Some(first) = self.next() else {
+            return Node::Symbol("\\".to_string(), Class::Ord);
+        };
+        if !first.is_ascii_alphabetic() {
+            return match first {
+                ',' => Node::Space(0.17),
+                ':' => Node::Space(0.22),
+                ';' => Node::Space(0.28),
+                '!' => Node::Space(-0.17),
+                ' ' => Node::Space(0.33),
+                '|' => Node::Symbol("‖".to_string(), Class::Ord),
+                c => Node::Symbol(c.to_string(), Class::Ord),
+            };
+        }
+
+        let mut name = String::from(first);
+        while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+            name.push(c);
+            self.pos += 1;
+        }
+
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                Node::Frac(Box::new(numerator), Box::new(denominator))
+            }
+            "sqrt" => {
+                // An optional root index `[n]` is accepted but not drawn
+                self.skip_whitespace();
+                if self.peek() == Some('[') {
+                    while self.next().is_some_and(|c| c != ']') {}
+                }
+                Node::Sqrt(Box::new(self.argument()))
+            }
+            "hat" | "widehat" => Node::Accent(Accent::Hat, Box::new(self.argument())),
+            "bar" => Node::Accent(Accent::Bar, Box::new(self.argument())),
+            "overline" => Node::Accent(Accent::Overline, Box::new(self.argument())),
+            "tilde" | "widetilde" => Node::Accent(Accent::Tilde, Box::new(self.argument())),
+            "dot" => Node::Accent(Accent::Dot, Box::new(self.argument())),
+            "vec" | "overrightarrow" => Node::Accent(Accent::Vec, Box::new(self.argument())),
+            "text" | "textrm" | "mathrm" | "mathit" | "mathbf" | "mathsf" | "operatorname" => {
+                Node::Text(self.raw_argument())
+            }
+            "sum" => big_op('∑', true),
+            "prod" => big_op('∏', true),
+            "bigcup" => big_op('⋃', true),
+            "bigcap" => big_op('⋂', true),
+            "int" => big_op('∫', false),
+            "oint" => big_op('∮', false),
+            "quad" => Node::Space(1.0),
+            "qquad" => Node::Space(2.0),
+            // Delimiter sizing is not supported; the delimiter itself is kept
+            "left" | "right" | "big" | "Big" | "bigl" | "bigr" | "Bigl" | "Bigr" => {
+                self.skip_whitespace();
+                if self.peek() == Some('.') {
+                    self.next();
+                }
+                Node::Group(Vec::new())
+            }
+            "sin" | "cos" | "tan" | "log" | "ln" | "exp" | "max" | "min" | "lim" | "sup"
+            | "inf" | "arg" | "det" | "Pr" | "Var" | "Cov" => Node::Symbol(name, Class::Op),
+            _ => match named_symbol(&name) {
+                Some((symbol, class)) => Node::Symbol(symbol.to_string(), class),
+                None => Node::Symbol(format!("\\{}", name), Class::Ord),
+            },
+        }
+    }
+}
+
+fn big_op(symbol: char, limits: bool) -> Node {
+    Node::BigOp {
+        symbol,
+        limits,
+        sub: None,
+        sup: None,
+    }
+}
+
+/// Attach a sub/superscript to the last node, starting an empty base if there is none
+fn attach_script(nodes: &mut Vec<Node>, is_sup: bool, arg: Node) {
+    let arg = Some(Box::new(arg));
+    let last = nodes.pop().unwrap_or(Node::Group(Vec::new()));
+    let node = match last {
+        Node::BigOp {
+            symbol,
+            limits,
+            mut sub,
+            mut sup,
+        } => {
+            if is_sup {
+                sup = arg;
+            } else {
+                sub = arg;
+            }
+            Node::BigOp {
+                symbol,
+                limits,
+                sub,
+                sup,
+            }
+        }
+        Node::Scripts { base, sub, sup }
+            if (is_sup && sup.is_none()) || (!is_sup && sub.is_none()) =>
+        {
+            if is_sup {
+                Node::Scripts {
+                    base,
+                    sub,
+                    sup: arg,
+                }
+            } else {
+                Node::Scripts {
+                    base,
+                    sub: arg,
+                    sup,
+                }
+            }
+        }
+        base => {
+            let base = Box::new(base);
+            if is_sup {
+                Node::Scripts {
+                    base,
+                    sub: None,
+                    sup: arg,
+                }
+            } else {
+                Node::Scripts {
+                    base,
+                    sub: arg,
+                    sup: None,
+                }
+            }
+        }
+    };
+    nodes.push(node);
+}
+
+fn char_symbol(c: char) -> Node {
+    let (text, class) = match c {
+        '-' => ("−".to_string(), Class::Bin),
+        '*' => ("∗".to_string(), Class::Bin),
+        '\'' => ("′".to_string(), Class::Ord),
+        '+' | '±' | '×' | '·' | '÷' => (c.to_string(), Class::Bin),
+        '=' | '<' | '>' | ':' | '≤' | '≥' | '≠' | '≈' | '∼' | '→' => {
+            (c.to_string(), Class::Rel)
+        }
+        ',' | ';' => (c.to_string(), Class::Punct),
+        _ => (c.to_string(), Class::Ord),
+    };
+    Node::Symbol(text, class)
+}
+
+fn named_symbol(name: &str) -> Option<(&'static str, Class)> {
+    use Class::*;
+    let symbol = match name {
+        "alpha" => ("α", Ord),
+        "beta" => ("β", Ord),
+        "gamma" => ("γ", Ord),
+        "delta" => ("δ", Ord),
+        "epsilon" => ("ϵ", Ord),
+        "varepsilon" => ("ε", Ord),
+        "zeta" => ("ζ", Ord),
+        "eta" => ("η", Ord),
+        "theta" => ("θ", Ord),
+        "vartheta" => ("ϑ", Ord),
+        "iota" => ("ι", Ord),
+        "kappa" => ("κ", Ord),
+        "lambda" => ("λ", Ord),
+        "mu" => ("μ", Ord),
+        "nu" => ("ν", Ord),
+        "xi" => ("ξ", Ord),
+        "pi" => ("π", Ord),
+        "rho" => ("ρ", Ord),
+        "sigma" => ("σ", Ord),
+        "tau" => ("τ", Ord),
+        "upsilon" => ("υ", Ord),
+        "phi" => ("ϕ", Ord),
+        "varphi" => ("φ", Ord),
+        "chi" => ("χ", Ord),
+        "psi" => ("ψ", Ord),
+        "omega" => ("ω", Ord),
+        "Gamma" => ("Γ", Ord),
+        "Delta" => ("Δ", Ord),
+        "Theta" => ("Θ", Ord),
+        "Lambda" => ("Λ", Ord),
+        "Xi" => ("Ξ", Ord),
+        "Pi" => ("Π", Ord),
+        "Sigma" => ("Σ", Ord),
+        "Upsilon" => ("Υ", Ord),
+        "Phi" => ("Φ", Ord),
+        "Psi" => ("Ψ", Ord),
+        "Omega" => ("Ω", Ord),
+        "infty" => ("∞", Ord),
+        "partial" => ("∂", Ord),
+        "nabla" => ("∇", Ord),
+        "ell" => ("ℓ", Ord),
+        "forall" => ("∀", Ord),
+        "exists" => ("∃", Ord),
+        "emptyset" => ("∅", Ord),
+        "neg" => ("¬", Ord),
+        "prime" => ("′", Ord),
+        "ldots" | "dots" => ("…", Ord),
+        "cdots" => ("⋯", Ord),
+        "langle" => ("⟨", Ord),
+        "rangle" => ("⟩", Ord),
+        "lfloor" => ("⌊", Ord),
+        "rfloor" => ("⌋", Ord),
+        "lceil" => ("⌈", Ord),
+        "rceil" => ("⌉", Ord),
+        "lbrace" => ("{", Ord),
+        "rbrace" => ("}", Ord),
+        "pm" => ("±", Bin),
+        "mp" => ("∓", Bin),
+        "times" => ("×", Bin),
+        "cdot" => ("⋅", Bin),
+        "div" => ("÷", Bin),
+        "ast" => ("∗", Bin),
+        "circ" => ("∘", Bin),
+        "cup" => ("∪", Bin),
+        "cap" => ("∩", Bin),
+        "leq" | "le" => ("≤", Rel),
+        "geq" | "ge" => ("≥", Rel),
+        "neq" | "ne" => ("≠", Rel),
+        "approx" => ("≈", Rel),
+        "sim" => ("∼", Rel),
+        "simeq" => ("≃", Rel),
+        "equiv" => ("≡", Rel),
+        "propto" => ("∝", Rel),
+        "in" => ("∈", Rel),
+        "notin" => ("∉", Rel),
+        "subset" => ("⊂", Rel),
+        "subseteq" => ("⊆", Rel),
+        "to" | "rightarrow" => ("→", Rel),
+        "leftarrow" => ("←", Rel),
+        "Rightarrow" => ("⇒", Rel),
+        "iff" | "Leftrightarrow" => ("⇔", Rel),
+        "mapsto" => ("↦", Rel),
+        "mid" => ("∣", Rel),
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+/// A drawable piece of a laid out formula, positioned relative to the
+/// left end of the baseline (Y up)
+#[derive(Debug, Clone, PartialEq)]
+enum MathItem {
+    Glyphs {
+        text: String,
+        position: Vec2,
+        size: f32,
+    },
+    Stroke {
+        points: Vec<Vec2>,
+        thickness: f32,
+    },
+    Dot {
+        center: Vec2,
+        radius: f32,
+    },
+}
+
+impl MathItem {
+    fn translate(&mut self, offset: Vec2) {
+        match self {
+            MathItem::Glyphs { position, .. } => *position += offset,
+            MathItem::Stroke { points, .. } => points.iter_mut().for_each(|p| *p += offset),
+            MathItem::Dot { center, .. } => *center += offset,
+        }
+    }
+}
+
+/// A laid out box: extents around its baseline plus the items inside
+#[derive(Debug, Clone, Default)]
+struct MathBox {
+    width: f32,
+    ascent: f32,
+    descent: f32,
+    items: Vec<MathItem>,
+}
+
+impl MathBox {
+    fn height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+
+    fn space(width: f32) -> Self {
+        Self {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Place another box with its origin at `offset` from this box's origin
+    fn place(&mut self, other: MathBox, offset: Vec2) {
+        self.width = self.width.max(offset.x + other.width);
+        self.ascent = self.ascent.max(offset.y + other.ascent);
+        self.descent = self.descent.max(other.descent - offset.y);
+        self.items.extend(other.items.into_iter().map(|mut item| {
+            item.translate(offset);
+            item
+        }));
+    }
+
+    /// Append a box to the right along the baseline
+    fn append(&mut self, other: MathBox) {
+        let offset = vec2(self.width, 0.0);
+        self.place(other, offset);
+    }
+}
+
+struct Layout<'a> {
+    font: &'a Font,
+    /// Font size of the top-level formula, to tell scripts apart
+    base_size: f32,
+}
+
+impl Layout<'_> {
+    /// Lay out a horizontal list, inserting TeX-like spacing around operators
+    fn list(&self, nodes: &[Node], size: f32) -> MathBox {
+        let mut row = MathBox::default();
+        let mut previous: Option<Class> = None;
+        // Like TeX, scripts and limits are set without operator spacing
+        let spaced = size > self.base_size * SCRIPT_RATIO + 0.01;
+
+        for (i, node) in nodes.iter().enumerate() {
+            let class = match node {
+                // A binary operator with nothing to its left is a prefix (unary minus)
+                Node::Symbol(_, Class::Bin)
+                    if matches!(
+                        previous,
+                        None | Some(Class::Bin | Class::Rel | Class::Punct | Class::Op)
+                    ) =>
+                {
+                    Class::Ord
+                }
+                Node::Symbol(_, class) => *class,
+                Node::Scripts { base, .. } => match base.as_ref() {
+                    Node::Symbol(_, Class::Op) => Class::Op,
+                    _ => Class::Ord,
+                },
+                _ => Class::Ord,
+            };
+            let (before, after) = match class {
+                Class::Bin | Class::Rel if !spaced => (0.0, 0.0),
+                Class::Bin => (0.22, 0.22),
+                Class::Rel => (0.28, 0.28),
+                Class::Punct | Class::Op => (0.0, 0.17),
+                Class::Ord => (0.0, 0.0),
+            };
+            // Don't leave dangling space at either end of the row
+            let before = if previous.is_some() { before } else { 0.0 };
+            let after = if i + 1 < nodes.len() { after } else { 0.0 };
+
+            row.append(MathBox::space(before * size));
+            row.append(self.node(node, size));
+            row.append(MathBox::space(after * size));
+            previous = Some(class);
+        }
+        row
+    }
+
+    fn node(&self, node: &Node, size: f32) -> MathBox {
+        match node {
+            Node::Symbol(text, _) | Node::Text(text) => self.glyphs(text, size),
+            Node::Space(em) => MathBox::space(em * size),
+            Node::Group(nodes) => self.list(nodes, size),
+            Node::Scripts { base, sub, sup } => {
+                let base = self.node(base, size);
+                self.scripts(base, sub.as_deref(), sup.as_deref(), size)
+            }
+            Node::Frac(numerator, denominator) => self.fraction(numerator, denominator, size),
+            Node::Sqrt(body) => self.sqrt(body, size),
+            Node::Accent(accent, body) => self.accent(*accent, body, size),
+            Node::BigOp {
+                symbol,
+                limits,
+                sub,
+                sup,
+            } => self.big_op(*symbol, *limits, sub.as_deref(), sup.as_deref(), size),
+        }
+    }
+
+    fn glyphs(&self, text: &str, size: f32) -> MathBox {
+        let width = self.font.measure(text, size).width;
+        let (ascent, descent) = self.font.ink_extent(text, size);
+        MathBox {
+            width,
+            ascent,
+            descent,
+            items: vec![MathItem::Glyphs {
+                text: text.to_string(),
+                position: Vec2::ZERO,
+                size,
+            }],
+        }
+    }
+
+    /// Height of the math axis (the middle of a minus sign) above the baseline
+    fn axis_height(&self, size: f32) -> f32 {
+        let (ascent, descent) = self.font.ink_extent("−", size);
+        (ascent - descent) / 2.0
+    }
+
+    fn scripts(&self, base: MathBox, sub: Option<&Node>, sup: Option<&Node>, size: f32) -> MathBox {
+        let script_size = size * SCRIPT_RATIO;
+        let sub = sub.map(|node| self.node(node, script_size));
+        let sup = sup.map(|node| self.node(node, script_size));
+
+        let mut up = sup
+            .as_ref()
+            .map_or(0.0, |s| (base.ascent - 0.3 * s.ascent).max(0.38 * size));
+        let down = sub.as_ref().map_or(0.0, |s| {
+            (base.descent + 0.05 * size)
+                .max(0.2 * size)
+                .max(s.ascent - 0.45 * size)
+        });
+
+        // Keep a minimum gap between a superscript and a subscript on the same base
+        if let (Some(sub), Some(sup)) = (&sub, &sup) {
+            let gap = (up - sup.descent) - (sub.ascent - down);
+            let min_gap = 0.12 * size;
+            if gap < min_gap {
+                up += min_gap - gap;
+            }
+        }
+
+        let x = base.width + 0.04 * size;
+        let mut result = base;
+        if let Some(sup) = sup {
+            result.place(sup, vec2(x, up));
+        }
+        if let Some(sub) = sub {
+            result.place(sub, vec2(x, -down));
+        }
+        result
+    }
+
+    fn fraction(&self, numerator: &Node, denominator: &Node, size: f32) -> MathBox {
+        let part_size = size * FRACTION_RATIO;
+        let numerator = self.node(numerator, part_size);
+        let denominator = self.node(denominator, part_size);
+
+        let thickness = RULE_THICKNESS * size;
+        let gap = 0.15 * size;
+        let padding = 0.1 * size;
+        let axis = self.axis_height(size);
+        let width = numerator.width.max(denominator.width) + 2.0 * padding;
+
+        let numerator_shift = axis + thickness / 2.0 + gap + numerator.descent;
+        let denominator_shift = axis - thickness / 2.0 - gap - denominator.ascent;
+
+        let mut result = MathBox::space(width);
+        result.items.push(MathItem::Stroke {
+            points: vec![vec2(0.0, axis), vec2(width, axis)],
+            thickness,
+        });
+        let numerator_x = (width - numerator.width) / 2.0;
+        let denominator_x = (width - denominator.width) / 2.0;
+        result.place(numerator, vec2(numerator_x, numerator_shift));
+        result.place(denominator, vec2(denominator_x, denominator_shift));
+        result
+    }
+
+    fn sqrt(&self, body: &Node, size: f32) -> MathBox {
+        let body = self.node(body, size);
+        let thickness = RULE_THICKNESS * size;
+        let top = body.ascent.max(0.5 * size) + 0.15 * size;
+        let bottom = -body.descent.max(0.05 * size);
+        let h = top - bottom;
+        let radical_width = 0.5 * size;
+        let end = radical_width + body.width + 0.1 * size;
+
+        // Short upstroke, long downstroke to the bottom, then up to the overbar
+        let mut result = MathBox::space(end);
+        result.items.push(MathItem::Stroke {
+            points: vec![
+                vec2(0.0, bottom + 0.45 * h),
+                vec2(0.15 * radical_width, bottom + 0.52 * h),
+                vec2(0.5 * radical_width, bottom),
+                vec2(radical_width, top),
+                vec2(end, top),
+            ],
+            thickness,
+        });
+        result.ascent = top + thickness;
+        result.descent = -bottom;
+        result.place(body, vec2(radical_width + 0.05 * size, 0.0));
+        result
+    }
+
+    fn accent(&self, accent: Accent, body: &Node, size: f32) -> MathBox {
+        let mut result = self.node(body, size);
+        let thickness = RULE_THICKNESS * size;
+        let width = result.width;
+        let mid = width / 2.0;
+        let base = result.ascent + 0.1 * size;
+
+        // Short accents sit over the middle of the body; overline spans all of it
+        let half = match accent {
+            Accent::Overline | Accent::Vec => width / 2.0,
+            _ => (width * 0.4).clamp(0.15 * size, 0.3 * size),
+        };
+        let (left, right) = (mid - half, mid + half);
+
+        let (item, height) = match accent {
+            Accent::Hat => (
+                MathItem::Stroke {
+                    points: vec![
+                        vec2(left, base),
+                        vec2(mid, base + 0.18 * size),
+                        vec2(right, base),
+                    ],
+                    thickness,
+                },
+                0.18 * size,
+            ),
+            Accent::Bar | Accent::Overline => (
+                MathItem::Stroke {
+                    points: vec![vec2(left, base), vec2(right, base)],
+                    thickness,
+                },
+                0.0,
+            ),
+            Accent::Tilde => {
+                let points = (0..=12)
+                    .map(|i| {
+                        let f = i as f32 / 12.0;
+                        let wave = (f * std::f32::consts::TAU).sin();
+                        vec2(left + f * 2.0 * half, base + 0.05 * size * (1.0 + wave))
+                    })
+                    .collect();
+                (MathItem::Stroke { points, thickness }, 0.1 * size)
+            }
+            Accent::Dot => {
+                let radius = 0.05 * size;
+                let center = vec2(mid, base + radius);
+                (MathItem::Dot { center, radius }, 2.0 * radius)
+            }
+            Accent::Vec => (
+                MathItem::Stroke {
+                    points: vec![
+                        vec2(left, base),
+                        vec2(right, base),
+                        vec2(right - 0.12 * size, base + 0.08 * size),
+                        vec2(right, base),
+                        vec2(right - 0.12 * size, base - 0.08 * size),
+                    ],
+                    thickness,
+                },
+                0.08 * size,
+            ),
+        };
+
+        result.items.push(item);
+        result.ascent = base + height + thickness;
+        result
+    }
+
+    fn big_op(
+        &self,
+        symbol: char,
+        limits: bool,
+        sub: Option<&Node>,
+        sup: Option<&Node>,
+        size: f32,
+    ) -> MathBox {
+        // Center the enlarged symbol on the math axis
+        let mut operator = self.glyphs(&symbol.to_string(), size * BIG_OP_RATIO);
+        let shift = self.axis_height(size) - (operator.ascent - operator.descent) / 2.0;
+        operator
+            .items
+            .iter_mut()
+            .for_each(|item| item.translate(vec2(0.0, shift)));
+        operator.ascent += shift;
+        operator.descent -= shift;
+
+        if !limits {
+            return self.scripts(operator, sub, sup, size);
+        }
+
+        let script_size = size * SCRIPT_RATIO;
+        let sub = sub.map(|node| self.node(node, script_size));
+        let sup = sup.map(|node| self.node(node, script_size));
+        let gap = 0.12 * size;
+        let width = [
+            Some(operator.width),
+            sub.as_ref().map(|b| b.width),
+            sup.as_ref().map(|b| b.width),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(0.0, f32::max);
+
+        let mut result = MathBox::space(width);
+        let (operator_ascent, operator_descent) = (operator.ascent, operator.descent);
+        let operator_x = (width - operator.width) / 2.0;
+        result.place(operator, vec2(operator_x, 0.0));
+        if let Some(sup) = sup {
+            let offset = vec2(
+                (width - sup.width) / 2.0,
+                operator_ascent + gap + sup.descent,
+            );
+            result.place(sup, offset);
+        }
+        if let Some(sub) = sub {
+            let offset = vec2(
+                (width - sub.width) / 2.0,
+                -(operator_descent + gap + sub.ascent),
+            );
+            result.place(sub, offset);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph_positions(tex: &MathTex) -> Vec<(String, Vec2, f32)> {
+        tex.layout()
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                MathItem::Glyphs {
+                    text,
+                    position,
+                    size,
+                } => Some((text, position, size)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parses_scripts_and_commands() {
+        let nodes = Parser::new(r"\hat{y} = \beta_0 + \beta_1 x").parse();
+        assert_eq!(nodes.len(), 6);
+        assert!(matches!(nodes[0], Node::Accent(Accent::Hat, _)));
+        assert_eq!(nodes[1], Node::Symbol("=".to_string(), Class::Rel));
+        match &nodes[2] {
+            Node::Scripts { base, sub, sup } => {
+                assert_eq!(**base, Node::Symbol("β".to_string(), Class::Ord));
+                assert_eq!(
+                    sub.as_deref(),
+                    Some(&Node::Symbol("0".to_string(), Class::Ord))
+                );
+                assert!(sup.is_none());
+            }
+            other => panic!("unexpected node {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_superscript_is_raised_and_smaller() {
+        let glyphs = glyph_positions(&MathTex::new("x^2"));
+        assert_eq!(glyphs.len(), 2);
+        let (_, base_pos, base_size) = &glyphs[0];
+        let (_, sup_pos, sup_size) = &glyphs[1];
+        assert!(sup_pos.y > base_pos.y);
+        assert!(sup_pos.x > base_pos.x);
+        assert!(sup_size < base_size);
+    }
+
+    #[test]
+    fn test_fraction_stacks_around_bar() {
+        let tex = MathTex::new(r"\frac{a}{b}");
+        let layout = tex.layout();
+        let bar_y = layout
+            .items
+            .iter()
+            .find_map(|item| match item {
+                MathItem::Stroke { points, .. } => Some(points[0].y),
+                _ => None,
+            })
+            .expect("fraction bar");
+
+        let glyphs = glyph_positions(&tex);
+        assert!(glyphs[0].1.y > bar_y);
+        assert!(glyphs[1].1.y < bar_y);
+    }
+
+    #[test]
+    fn test_sum_limits_centered_above_and_below() {
+        let glyphs = glyph_positions(&MathTex::new(r"\sum_{i=1}^{n} x_i"));
+        let sum = glyphs.iter().find(|g| g.0 == "∑").unwrap();
+        let upper = glyphs.iter().find(|g| g.0 == "n").unwrap();
+        let lower = glyphs.iter().find(|g| g.0 == "i").unwrap();
+        assert!(upper.1.y > sum.1.y);
+        assert!(lower.1.y < sum.1.y);
+    }
+
+    #[test]
+    fn test_mixed_keeps_text_spaces() {
+        let tex = MathTex::mixed(r"Residual $e_i$");
+        let glyphs = glyph_positions(&tex);
+        assert_eq!(glyphs[0].0, "Residual ");
+        assert_eq!(glyphs[1].0, "e");
+    }
+
+    #[test]
+    fn test_unknown_command_is_kept_literally() {
+        let glyphs = glyph_positions(&MathTex::new(r"\foo"));
+        assert_eq!(glyphs[0].0, "\\foo");
+    }
+
+    #[test]
+    fn test_create_reveals_pieces_in_order() {
+        use crate::canvas::RecordingCanvas;
+
+        let tex = MathTex::new(r"\sqrt{x} + 1");
+        let mut full = RecordingCanvas::new();
+        tex.draw(&mut full, 1.0, Vec2::ZERO);
+        let mut partial = RecordingCanvas::new();
+        tex.draw(&mut partial, 0.3, Vec2::ZERO);
+
+        assert!(partial.len() < full.len());
+        assert_eq!(partial.commands(), &full.commands()[..partial.len()]);
+    }
+}
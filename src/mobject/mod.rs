@@ -1,14 +1,38 @@
 use macroquad::prelude::*;
 
+use crate::canvas::Canvas;
+
+pub mod area;
 pub mod axes;
+pub mod boxplot;
+pub mod coords;
 pub mod curve;
+pub mod histogram;
+pub mod kde;
+pub mod math_tex;
+pub mod scales;
 pub mod scatter;
 pub mod shapes;
+pub mod stem;
+pub mod text;
+pub mod ticks;
+pub mod violin;
 
+pub use area::AreaUnder;
 pub use axes::{Axes2D, Axes3D};
+pub use boxplot::{BoxPlot, BoxStats, Whiskers};
+pub use coords::{AxesFrame, PlotSpace};
 pub use curve::{ConfidenceBand, Curve};
+pub use histogram::{Binning, Histogram, Normalization};
+pub use kde::{Bandwidth, KdeCurve, Kernel};
+pub use math_tex::MathTex;
+pub use scales::AxisScale;
 pub use scatter::{MarkerShape, ScatterPlot};
 pub use shapes::{Arrow, Circle, Line, Rectangle};
+pub use stem::StemPlot;
+pub use text::{Anchor, Text, TextAlign};
+pub use ticks::{TickFormat, Ticks};
+pub use violin::ViolinPlot;
 
 /// Unique identifier for mobjects in a scene
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -55,14 +79,45 @@ impl BoundingRect {
             h: max.y - min.y,
         }
     }
+
+    /// Bounding box of this rectangle after scaling and rotating it about `pivot`
+    pub fn transformed_about(&self, pivot: Vec2, scale: f32, rotation: f32) -> Self {
+        if scale == 1.0 && rotation == 0.0 {
+            return *self;
+        }
+        let turn = Vec2::from_angle(rotation);
+        let corners = [
+            vec2(self.x, self.y),
+            vec2(self.x + self.w, self.y),
+            vec2(self.x + self.w, self.y + self.h),
+            vec2(self.x, self.y + self.h),
+        ]
+        .map(|c| pivot + turn.rotate(c - pivot) * scale);
+
+        let min = corners
+            .iter()
+            .fold(Vec2::splat(f32::MAX), |acc, p| acc.min(*p));
+        let max = corners
+            .iter()
+            .fold(Vec2::splat(f32::MIN), |acc, p| acc.max(*p));
+        Self::from_corners(min, max)
+    }
+
+    /// Whether two rectangles overlap (touching edges don't count)
+    pub fn intersects(&self, other: &BoundingRect) -> bool {
+        self.x < other.x + other.w
+            && other.x < self.x + self.w
+            && self.y < other.y + other.h
+            && other.y < self.y + self.h
+    }
 }
 
 /// Core trait for all drawable mathematical objects
 pub trait Mobject: Send + Sync {
-    /// Draw the mobject. Uses macroquad's global immediate-mode draw functions.
+    /// Draw the mobject onto a canvas (the live window or an offscreen framebuffer).
     /// The `t` parameter is for partial drawing (0.0-1.0) in Create animations.
     /// The `screen_center` is used to offset coordinates (center-origin to top-left origin).
-    fn draw(&self, t: f32, screen_center: Vec2);
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2);
     fn bounding_box(&self) -> BoundingRect;
 
     fn center(&self) -> Vec2;
@@ -71,14 +126,57 @@ pub trait Mobject: Send + Sync {
     fn opacity(&self) -> f32;
     fn set_opacity(&mut self, opacity: f32);
 
+    /// Scale factor applied about the center when drawing
     fn scale(&self) -> f32;
     fn set_scale(&mut self, scale: f32);
 
+    /// Rotation in radians (counter-clockwise) applied about the center when drawing
+    fn rotation(&self) -> f32;
     fn rotate(&mut self, angle: f32);
     fn set_rotate(&mut self, angle: f32);
 
     fn id(&self) -> MobjectId;
     fn clone_box(&self) -> Box<dyn Mobject>;
+
+    /// Data coordinate frame this mobject defines for others to plot in (axes)
+    fn axes_frame(&self) -> Option<AxesFrame> {
+        None
+    }
+
+    /// Change the data ranges of a coordinate system; ignored by other mobjects
+    fn set_ranges(&mut self, _x_range: (f32, f32), _y_range: (f32, f32)) {}
+
+    /// Axes whose data coordinates this mobject's points are expressed in
+    fn bound_axes(&self) -> Option<MobjectId> {
+        None
+    }
+
+    /// Receive the bound axes' frame at the time being drawn (called by the scene)
+    fn set_axes_frame(&mut self, _frame: AxesFrame) {}
+
+    /// Smoothing bandwidth of a density estimate
+    fn bandwidth(&self) -> Option<f32> {
+        None
+    }
+
+    /// Change the smoothing bandwidth of a density estimate; ignored by other mobjects
+    fn set_bandwidth(&mut self, _bandwidth: f32) {}
+
+    /// Points of a curve, for morphing between curves
+    fn curve_points(&self) -> Option<&[Vec2]> {
+        None
+    }
+
+    /// Replace the points of a curve; ignored by other mobjects
+    fn set_curve_points(&mut self, _points: Vec<Vec2>) {}
+
+    /// The x range a shaded region is clipped to
+    fn x_bounds(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// Clip a shaded region to `a <= x <= b`; ignored by other mobjects
+    fn set_x_bounds(&mut self, _a: f32, _b: f32) {}
 }
 
 /// Common properties shared by all mobjects
@@ -112,6 +210,17 @@ impl MobjectStyle {
     }
 }
 
+/// Fraction of the Create animation by which the last of a row of parts
+/// starts growing after the first
+const GROWTH_STAGGER: f32 = 0.3;
+
+/// How far part `index` of `count` has grown at Create progress `t`, from 0 to 1.
+/// Parts start one after another so they grow in a quick left-to-right wave.
+pub(crate) fn staggered_growth(index: usize, count: usize, t: f32) -> f32 {
+    let delay = GROWTH_STAGGER * index as f32 / count.max(2).saturating_sub(1) as f32;
+    ((t - delay) / (1.0 - GROWTH_STAGGER)).clamp(0.0, 1.0)
+}
+
 /// Helper to convert from center-origin coordinates to screen coordinates
 /// In macroquad, (0,0) is top-left and Y increases downward
 pub fn to_screen(pos: Vec2, screen_center: Vec2) -> Vec2 {
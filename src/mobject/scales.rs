@@ -0,0 +1,272 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::Ticks;
+
+/// Smallest logit probability; values closer to 0 or 1 are clipped to it
+const LOGIT_EPSILON: f32 = 1e-7;
+
+/// How data values along one axis map to evenly spaced screen positions.
+/// Values outside a scale's domain (non-positive values on a log axis,
+/// probabilities outside 0..1 on a logit axis) are clipped to its edge.
+#[derive(Clone, Default)]
+pub enum AxisScale {
+    #[default]
+    Linear,
+    /// Base-10 logarithm: one unit of the axis per decade
+    Log10,
+    /// Natural logarithm: one unit of the axis per factor of e (ticks still fall on decades)
+    Ln,
+    /// Linear around zero and logarithmic beyond `linthresh` in either direction,
+    /// via `sign(v)·log10(1 + |v|/linthresh)`
+    SymLog { linthresh: f32 },
+    /// Log-odds `log10(p / (1 - p))` for probabilities in 0..1
+    Logit,
+    /// User-supplied monotone transform and its inverse
+    Custom {
+        forward: Arc<dyn Fn(f32) -> f32 + Send + Sync>,
+        inverse: Arc<dyn Fn(f32) -> f32 + Send + Sync>,
+    },
+}
+
+impl AxisScale {
+    pub fn symlog(linthresh: f32) -> Self {
+        AxisScale::SymLog {
+            linthresh: linthresh.abs().max(f32::MIN_POSITIVE),
+        }
+    }
+
+    /// `forward` must be monotone over the axis range and `inverse` must undo it
+    pub fn custom(
+        forward: impl Fn(f32) -> f32 + Send + Sync + 'static,
+        inverse: impl Fn(f32) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        AxisScale::Custom {
+            forward: Arc::new(forward),
+            inverse: Arc::new(inverse),
+        }
+    }
+
+    /// Data value to its position along the evenly spaced axis
+    pub fn forward(&self, value: f32) -> f32 {
+        match self {
+            AxisScale::Linear => value,
+            AxisScale::Log10 => value.max(f32::MIN_POSITIVE).log10(),
+            AxisScale::Ln => value.max(f32::MIN_POSITIVE).ln(),
+            AxisScale::SymLog { linthresh } => {
+                value.signum() * (value.abs() / linthresh).ln_1p() / std::f32::consts::LN_10
+            }
+            AxisScale::Logit => {
+                let p = value.clamp(LOGIT_EPSILON, 1.0 - LOGIT_EPSILON);
+                (p / (1.0 - p)).log10()
+            }
+            AxisScale::Custom { forward, .. } => forward(value),
+        }
+    }
+
+    /// Axis position back to a data value
+    pub fn inverse(&self, position: f32) -> f32 {
+        match self {
+            AxisScale::Linear => position,
+            AxisScale::Log10 => 10f32.powf(position),
+            AxisScale::Ln => position.exp(),
+            AxisScale::SymLog { linthresh } => {
+                position.signum() * linthresh * (position.abs() * std::f32::consts::LN_10).exp_m1()
+            }
+            AxisScale::Logit => {
+                let odds = 10f32.powf(position);
+                odds / (1.0 + odds)
+            }
+            AxisScale::Custom { inverse, .. } => inverse(position),
+        }
+    }
+
+    /// Value `t` of the way from `from` to `to`, interpolated evenly along the axis
+    pub fn lerp(&self, from: f32, to: f32, t: f32) -> f32 {
+        if t <= 0.0 {
+            return from;
+        }
+        if t >= 1.0 {
+            return to;
+        }
+        let (a, b) = (self.forward(from), self.forward(to));
+        self.inverse(a + (b - a) * t)
+    }
+
+    /// About `target` ticks across `range`: nice linear ticks, decades for log
+    /// scales, and 0.5 plus powers of ten towards either end for logit
+    pub fn ticks(&self, range: (f32, f32), target: usize) -> Ticks {
+        let (lo, hi) = (range.0.min(range.1), range.0.max(range.1));
+        match self {
+            AxisScale::Linear | AxisScale::Custom { .. } => Ticks::nice(range, target),
+            AxisScale::Log10 | AxisScale::Ln => {
+                let (first, last) = decades(lo, hi);
+                // Less than two decades: plain ticks read better
+                if last - first < 1 {
+                    return Ticks::nice(range, target);
+                }
+                let (major, minor) = log_ticks(lo, hi, target);
+                Ticks {
+                    step: 10.0,
+                    major,
+                    minor,
+                }
+            }
+            AxisScale::SymLog { linthresh } => {
+                let side_target = target.div_ceil(2).max(1);
+                let (mut major, mut minor) = log_ticks(linthresh.max(lo), hi, side_target);
+                let (negative, negative_minor) = log_ticks(linthresh.max(-hi), -lo, side_target);
+                if lo <= 0.0 && hi >= 0.0 {
+                    major.push(0.0);
+                }
+                major.extend(negative.into_iter().map(|v| -v));
+                minor.extend(negative_minor.into_iter().map(|v| -v));
+                major.sort_by(f32::total_cmp);
+                minor.sort_by(f32::total_cmp);
+                Ticks {
+                    step: 10.0,
+                    major,
+                    minor,
+                }
+            }
+            AxisScale::Logit => {
+                let inside = |v: &f32| *v >= lo && *v <= hi;
+                let mut candidates: Vec<f32> = (1..=6)
+                    .flat_map(|k| {
+                        let tail = 10f32.powi(-k);
+                        [tail, 1.0 - tail]
+                    })
+                    .chain([0.5])
+                    .filter(inside)
+                    .collect();
+                candidates.sort_by(f32::total_cmp);
+                let stride = candidates.len().div_ceil(target.max(1)).max(1);
+                let major: Vec<f32> = candidates.into_iter().step_by(stride).collect();
+
+                let mut minor: Vec<f32> = (1..=6)
+                    .flat_map(|k| (2..=9).map(move |m| m as f32 * 10f32.powi(-k)))
+                    .filter(|v| *v < 0.5)
+                    .flat_map(|v| [v, 1.0 - v])
+                    .filter(inside)
+                    .filter(|v| major.iter().all(|m| (m - v).abs() > v.min(1.0 - v) * 1e-3))
+                    .collect();
+                minor.sort_by(f32::total_cmp);
+                Ticks {
+                    step: 10.0,
+                    major,
+                    minor,
+                }
+            }
+        }
+    }
+
+    /// Step to format the label of tick `value` with: the tick spacing on linear
+    /// axes, the value's own magnitude where ticks are spaced multiplicatively
+    pub fn label_step(&self, value: f32, step: f32) -> f32 {
+        match self {
+            AxisScale::Linear | AxisScale::Custom { .. } => step,
+            AxisScale::Logit => value.min(1.0 - value).abs().max(f32::MIN_POSITIVE),
+            _ if value == 0.0 => step,
+            _ => value.abs(),
+        }
+    }
+}
+
+/// Exponents of the first and last powers of ten inside the positive range `lo..hi`
+fn decades(lo: f32, hi: f32) -> (i32, i32) {
+    if hi <= 0.0 {
+        return (1, 0);
+    }
+    let lo = lo.max(f32::MIN_POSITIVE);
+    (
+        (lo.log10() - 1e-4).ceil() as i32,
+        (hi.log10() + 1e-4).floor() as i32,
+    )
+}
+
+/// Powers of ten in the positive range `lo..hi`, thinned to about `target`, with
+/// 2..9 × 10ⁿ minor ticks (or the skipped decades once thinned)
+fn log_ticks(lo: f32, hi: f32, target: usize) -> (Vec<f32>, Vec<f32>) {
+    let (first, last) = decades(lo, hi);
+    if last < first {
+        return (Vec::new(), Vec::new());
+    }
+    let count = (last - first + 1) as usize;
+    let stride = count.div_ceil(target.max(1)).max(1) as i32;
+    let (major, skipped): (Vec<i32>, Vec<i32>) =
+        (first..=last).partition(|k| k.rem_euclid(stride) == 0);
+    let major = major.into_iter().map(|k| 10f32.powi(k)).collect();
+    let minor = if stride == 1 {
+        (first - 1..=last)
+            .flat_map(|k| (2..=9).map(move |m| m as f32 * 10f32.powi(k)))
+            .filter(|v| *v >= lo && *v <= hi)
+            .collect()
+    } else {
+        skipped.into_iter().map(|k| 10f32.powi(k)).collect()
+    };
+    (major, minor)
+}
+
+impl fmt::Debug for AxisScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AxisScale::Linear => write!(f, "Linear"),
+            AxisScale::Log10 => write!(f, "Log10"),
+            AxisScale::Ln => write!(f, "Ln"),
+            AxisScale::SymLog { linthresh } => write!(f, "SymLog {{ linthresh: {} }}", linthresh),
+            AxisScale::Logit => write!(f, "Logit"),
+            AxisScale::Custom { .. } => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for AxisScale {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AxisScale::SymLog { linthresh: a }, AxisScale::SymLog { linthresh: b }) => a == b,
+            (AxisScale::Custom { forward: a, .. }, AxisScale::Custom { forward: b, .. }) => {
+                Arc::ptr_eq(a, b)
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scales_round_trip() {
+        let scales = [
+            AxisScale::Log10,
+            AxisScale::Ln,
+            AxisScale::symlog(1.0),
+            AxisScale::Logit,
+            AxisScale::custom(|v| v.sqrt(), |v| v * v),
+        ];
+        for scale in scales {
+            for value in [0.01, 0.3, 0.9] {
+                let back = scale.inverse(scale.forward(value));
+                assert!((back - value).abs() < 1e-4, "{:?} {}", scale, value);
+            }
+        }
+        assert_eq!(AxisScale::Log10.forward(1000.0), 3.0);
+        assert!(AxisScale::Log10.forward(-1.0).is_finite());
+        assert!((AxisScale::symlog(1.0).forward(-9.0) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_log_ticks_fall_on_decades() {
+        let ticks = AxisScale::Log10.ticks((0.5, 2000.0), 8);
+        assert_eq!(ticks.major, [1.0, 10.0, 100.0, 1000.0]);
+        assert!(ticks.minor.contains(&0.5) && ticks.minor.contains(&2000.0));
+
+        let ticks = AxisScale::Log10.ticks((1e-8, 1.0), 3);
+        assert_eq!(ticks.major.len(), 3);
+        assert_eq!(ticks.minor.len(), 6);
+
+        let ticks = AxisScale::Logit.ticks((0.001, 0.999), 10);
+        assert_eq!(ticks.major, [0.001, 0.01, 0.1, 0.5, 0.9, 0.99, 0.999]);
+    }
+}
@@ -1,4 +1,9 @@
-use super::{BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+use super::{
+    Axes2D, AxesFrame, BoundingRect, Curve, Mobject, MobjectId, MobjectStyle, PlotSpace, to_screen,
+};
+use crate::canvas::Canvas;
+use crate::data::Column;
+use crate::state::Dataset;
 use macroquad::prelude::*;
 
 /// Point marker shapes for scatter plots
@@ -11,6 +16,88 @@ pub enum MarkerShape {
     Cross,
 }
 
+impl MarkerShape {
+    /// Draw the marker centered at screen position `pos`. Transparent colors are skipped;
+    /// a cross is drawn in the fill color.
+    pub(crate) fn draw(
+        &self,
+        canvas: &mut dyn Canvas,
+        pos: Vec2,
+        radius: f32,
+        fill: Color,
+        stroke: Color,
+        stroke_weight: f32,
+    ) {
+        match self {
+            MarkerShape::Circle => {
+                if fill.a > 0.0 {
+                    canvas.draw_circle(pos.x, pos.y, radius, fill);
+                }
+
+                if stroke.a > 0.0 {
+                    canvas.draw_circle_lines(pos.x, pos.y, radius, stroke_weight, stroke);
+                }
+            }
+            MarkerShape::Square => {
+                let size = radius * 2.0;
+                let half = radius;
+
+                if fill.a > 0.0 {
+                    canvas.draw_rectangle(pos.x - half, pos.y - half, size, size, fill);
+                }
+
+                if stroke.a > 0.0 {
+                    canvas.draw_rectangle_lines(
+                        pos.x - half,
+                        pos.y - half,
+                        size,
+                        size,
+                        stroke_weight,
+                        stroke,
+                    );
+                }
+            }
+            MarkerShape::Diamond => {
+                let r = radius;
+                let points = [
+                    vec2(pos.x, pos.y - r),
+                    vec2(pos.x + r, pos.y),
+                    vec2(pos.x, pos.y + r),
+                    vec2(pos.x - r, pos.y),
+                ];
+
+                if fill.a > 0.0 {
+                    // Draw as two triangles
+                    canvas.draw_triangle(points[0], points[1], points[2], fill);
+                    canvas.draw_triangle(points[0], points[2], points[3], fill);
+                }
+
+                if stroke.a > 0.0 {
+                    for j in 0..4 {
+                        let next = (j + 1) % 4;
+                        canvas.draw_line(
+                            points[j].x,
+                            points[j].y,
+                            points[next].x,
+                            points[next].y,
+                            stroke_weight,
+                            stroke,
+                        );
+                    }
+                }
+            }
+            MarkerShape::Cross => {
+                let r = radius;
+                let weight = stroke_weight.max(2.0);
+                // Vertical line
+                canvas.draw_line(pos.x, pos.y - r, pos.x, pos.y + r, weight, fill);
+                // Horizontal line
+                canvas.draw_line(pos.x - r, pos.y, pos.x + r, pos.y, weight, fill);
+            }
+        }
+    }
+}
+
 /// A scatter plot mobject for visualizing 2D point data
 #[derive(Debug, Clone)]
 pub struct ScatterPlot {
@@ -22,6 +109,12 @@ pub struct ScatterPlot {
     style: MobjectStyle,
     /// Optional per-point colors (for coloring by value/residual/cluster)
     point_colors: Option<Vec<Color>>,
+    /// Fitted value at each point's x (None where there is no fit); when set,
+    /// residual segments are drawn
+    fitted: Option<Vec<Option<f32>>>,
+    residual_color: Color,
+    residual_weight: f32,
+    space: PlotSpace,
     scale: f32,
     rotation: f32,
 }
@@ -41,6 +134,10 @@ impl ScatterPlot {
                 opacity: 1.0,
             },
             point_colors: None,
+            fitted: None,
+            residual_color: Color::new(0.8, 0.8, 0.8, 0.7),
+            residual_weight: 1.5,
+            space: PlotSpace::Pixels,
             scale: 1.0,
             rotation: 0.0,
         }
@@ -50,17 +147,40 @@ impl ScatterPlot {
         Self::new().points(points)
     }
 
+    /// Points from two dataset columns, by name or position
+    pub fn from_dataset(data: &Dataset, x: impl Into<Column>, y: impl Into<Column>) -> Self {
+        Self::from_points(data.xy(x, y).unwrap_or_default())
+    }
+
+    /// Replace the points. Residuals were fitted to the old points, so they
+    /// are cleared; set them again afterwards.
     pub fn points(mut self, points: Vec<Vec2>) -> Self {
         self.points = points;
+        self.fitted = None;
         self
     }
 
-    /// Set the center offset for all points (useful with Axes2D)
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
     pub fn at(mut self, pos: Vec2) -> Self {
         self.center = pos;
         self
     }
 
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
     pub fn point_radius(mut self, radius: f32) -> Self {
         self.point_radius = radius;
         self
@@ -100,6 +220,105 @@ impl ScatterPlot {
         self
     }
 
+    /// Draw each point's residual as a vertical segment to `curve` (in the same
+    /// coordinates as the points). With `Create` the segments grow from the
+    /// curve one after another. Points beyond the curve's x range have no
+    /// fitted value, so they get no segment or residual.
+    pub fn residuals_to(mut self, curve: &Curve) -> Self {
+        let (lo, hi) = curve
+            .get_points()
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                (lo.min(p.x), hi.max(p.x))
+            });
+        self.fitted = Some(
+            self.points
+                .iter()
+                .map(|p| {
+                    (lo <= p.x && p.x <= hi)
+                        .then(|| curve.value_at(p.x))
+                        .flatten()
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Like `residuals_to`, for a fit given as a function, e.g. `|x| fit.predict(x)`.
+    /// The fit is evaluated at the current points, so call this after `points`.
+    pub fn residuals_from<F>(mut self, fit: F) -> Self
+    where
+        F: Fn(f32) -> f32,
+    {
+        self.fitted = Some(self.points.iter().map(|p| Some(fit(p.x))).collect());
+        self
+    }
+
+    pub fn residual_color(mut self, color: Color) -> Self {
+        self.residual_color = color;
+        self
+    }
+
+    pub fn residual_weight(mut self, weight: f32) -> Self {
+        self.residual_weight = weight;
+        self
+    }
+
+    /// Color points by their residual, e.g. by sign or magnitude. Call after
+    /// `residuals_to` or `residuals_from`; without a fit the colors are unchanged,
+    /// as are those of points without a residual.
+    pub fn color_by_residual<F>(mut self, color_fn: F) -> Self
+    where
+        F: Fn(f32) -> Color,
+    {
+        let Some(residuals) = self.residuals() else {
+            return self;
+        };
+        let colors = residuals
+            .iter()
+            .enumerate()
+            .map(|(i, residual)| match residual {
+                Some(r) => color_fn(*r),
+                None => self
+                    .point_colors
+                    .as_ref()
+                    .and_then(|colors| colors.get(i).copied())
+                    .unwrap_or(self.style.fill_color),
+            })
+            .collect();
+        self.point_colors = Some(colors);
+        self
+    }
+
+    /// Observed minus fitted value of each point (None where there is no fitted
+    /// value), if a fit was given
+    pub fn residuals(&self) -> Option<Vec<Option<f32>>> {
+        let fitted = self.fitted.as_ref()?;
+        Some(
+            self.points
+                .iter()
+                .zip(fitted)
+                .map(|(p, f)| f.map(|f| p.y - f))
+                .collect(),
+        )
+    }
+
+    /// Residual-vs-fitted diagnostic plot: one point per observation at
+    /// (fitted, residual), with residual segments to the zero line
+    pub fn residuals_vs_fitted<F>(points: &[Vec2], fit: F) -> Self
+    where
+        F: Fn(f32) -> f32,
+    {
+        let diagnostics = points
+            .iter()
+            .map(|p| {
+                let fitted = fit(p.x);
+                vec2(fitted, p.y - fitted)
+            })
+            .collect();
+        Self::from_points(diagnostics).residuals_from(|_| 0.0)
+    }
+
     pub fn len(&self) -> usize {
         self.points.len()
     }
@@ -120,14 +339,45 @@ impl Default for ScatterPlot {
 }
 
 impl Mobject for ScatterPlot {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+
         let style = self.style.with_opacity(self.style.opacity);
 
         // Calculate how many points to show based on animation progress
         let points_to_draw = ((self.points.len() as f32) * t).ceil() as usize;
 
+        // Residual segments grow from the fit to each point in turn, under the markers
+        if let Some(fitted) = &self.fitted {
+            let mut color = self.residual_color;
+            color.a *= style.opacity;
+            let n = self.points.len() as f32;
+            for (i, (point, &fit)) in self.points.iter().zip(fitted).enumerate() {
+                let growth = (t * n - i as f32).clamp(0.0, 1.0);
+                if growth <= 0.0 {
+                    break;
+                }
+                let Some(fit) = fit else {
+                    continue;
+                };
+                let tip = vec2(point.x, fit + (point.y - fit) * growth);
+                let from = to_screen(
+                    self.space.place(self.center, vec2(point.x, fit)),
+                    screen_center,
+                );
+                let to = to_screen(self.space.place(self.center, tip), screen_center);
+                canvas.draw_line(from.x, from.y, to.x, to.y, self.residual_weight, color);
+            }
+        }
+
         for (i, point) in self.points.iter().take(points_to_draw).enumerate() {
-            let screen_pos = to_screen(self.center + *point, screen_center);
+            let screen_pos = to_screen(self.space.place(self.center, *point), screen_center);
 
             // Get color for this point
             let fill_color = self
@@ -144,104 +394,14 @@ impl Mobject for ScatterPlot {
                 fill_color.a * style.opacity,
             );
 
-            match self.marker {
-                MarkerShape::Circle => {
-                    if fill_with_opacity.a > 0.0 {
-                        draw_circle(
-                            screen_pos.x,
-                            screen_pos.y,
-                            self.point_radius,
-                            fill_with_opacity,
-                        );
-                    }
-
-                    if style.stroke_color.a > 0.0 {
-                        draw_circle_lines(
-                            screen_pos.x,
-                            screen_pos.y,
-                            self.point_radius,
-                            style.stroke_weight,
-                            style.stroke_color,
-                        );
-                    }
-                }
-                MarkerShape::Square => {
-                    let size = self.point_radius * 2.0;
-                    let half = self.point_radius;
-
-                    if fill_with_opacity.a > 0.0 {
-                        draw_rectangle(
-                            screen_pos.x - half,
-                            screen_pos.y - half,
-                            size,
-                            size,
-                            fill_with_opacity,
-                        );
-                    }
-
-                    if style.stroke_color.a > 0.0 {
-                        draw_rectangle_lines(
-                            screen_pos.x - half,
-                            screen_pos.y - half,
-                            size,
-                            size,
-                            style.stroke_weight,
-                            style.stroke_color,
-                        );
-                    }
-                }
-                MarkerShape::Diamond => {
-                    let r = self.point_radius;
-                    let points = [
-                        vec2(screen_pos.x, screen_pos.y - r),
-                        vec2(screen_pos.x + r, screen_pos.y),
-                        vec2(screen_pos.x, screen_pos.y + r),
-                        vec2(screen_pos.x - r, screen_pos.y),
-                    ];
-
-                    if fill_with_opacity.a > 0.0 {
-                        // Draw as two triangles
-                        draw_triangle(points[0], points[1], points[2], fill_with_opacity);
-                        draw_triangle(points[0], points[2], points[3], fill_with_opacity);
-                    }
-
-                    if style.stroke_color.a > 0.0 {
-                        for j in 0..4 {
-                            let next = (j + 1) % 4;
-                            draw_line(
-                                points[j].x,
-                                points[j].y,
-                                points[next].x,
-                                points[next].y,
-                                style.stroke_weight,
-                                style.stroke_color,
-                            );
-                        }
-                    }
-                }
-                MarkerShape::Cross => {
-                    let r = self.point_radius;
-                    let weight = style.stroke_weight.max(2.0);
-                    // Vertical line
-                    draw_line(
-                        screen_pos.x,
-                        screen_pos.y - r,
-                        screen_pos.x,
-                        screen_pos.y + r,
-                        weight,
-                        fill_with_opacity,
-                    );
-                    // Horizontal line
-                    draw_line(
-                        screen_pos.x - r,
-                        screen_pos.y,
-                        screen_pos.x + r,
-                        screen_pos.y,
-                        weight,
-                        fill_with_opacity,
-                    );
-                }
-            }
+            self.marker.draw(
+                &mut canvas,
+                screen_pos,
+                self.point_radius,
+                fill_with_opacity,
+                style.stroke_color,
+                style.stroke_weight,
+            );
         }
     }
 
@@ -250,16 +410,20 @@ impl Mobject for ScatterPlot {
             return BoundingRect::from_xy_wh(self.center, Vec2::ZERO);
         }
 
-        let min = self
-            .points
-            .iter()
-            .fold(Vec2::splat(f32::MAX), |acc, p| acc.min(*p));
-        let max = self
-            .points
-            .iter()
-            .fold(Vec2::splat(f32::MIN), |acc, p| acc.max(*p));
-
-        BoundingRect::from_corners(self.center + min, self.center + max)
+        // Residual segments reach from each point to the fit
+        let fitted = self.fitted.iter().flat_map(|fitted| {
+            self.points
+                .iter()
+                .zip(fitted)
+                .filter_map(|(p, &fit)| Some(vec2(p.x, fit?)))
+        });
+        let (min, max) = self.points.iter().copied().chain(fitted).fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), p| (min.min(p), max.max(p)),
+        );
+
+        self.space
+            .bounds(min, max, self.center, self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -286,6 +450,10 @@ impl Mobject for ScatterPlot {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -298,7 +466,84 @@ impl Mobject for ScatterPlot {
         self.id
     }
 
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
     fn clone_box(&self) -> Box<dyn Mobject> {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::{DrawCommand, RecordingCanvas};
+
+    #[test]
+    fn test_residual_segments_grow_in_sequence() {
+        let fit = Curve::from_function(|x| 2.0 * x, 0.0, 4.0, 5);
+        let scatter =
+            ScatterPlot::from_points(vec![vec2(1.0, 3.0), vec2(2.0, 3.0), vec2(3.0, 7.0)])
+                .residuals_to(&fit)
+                .color_by_residual(|r| if r > 0.0 { RED } else { BLUE });
+        assert_eq!(
+            scatter.residuals(),
+            Some(vec![Some(1.0), Some(-1.0), Some(1.0)])
+        );
+
+        let segments = |t: f32| {
+            let mut canvas = RecordingCanvas::new();
+            scatter.draw(&mut canvas, t, Vec2::ZERO);
+            canvas
+                .commands()
+                .iter()
+                .filter_map(|c| match c {
+                    DrawCommand::Line { from, to, .. } => Some(to.y - from.y),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        // Halfway through Create: the first segment is done, the second half grown
+        // (screen y points down, so a positive residual has a negative length)
+        assert_eq!(segments(0.5), vec![-1.0, 0.5]);
+        assert_eq!(segments(1.0), vec![-1.0, 1.0, -1.0]);
+
+        let diagnostic = ScatterPlot::residuals_vs_fitted(scatter.get_points(), |x| 2.0 * x);
+        assert_eq!(diagnostic.get_points()[2], vec2(6.0, 1.0));
+        assert_eq!(
+            diagnostic.residuals(),
+            Some(vec![Some(1.0), Some(-1.0), Some(1.0)])
+        );
+
+        // New points drop residuals fitted to the old ones
+        let moved = scatter.clone().points(vec![vec2(0.0, 0.0)]);
+        assert_eq!(moved.residuals(), None);
+    }
+
+    #[test]
+    fn test_points_beyond_the_curve_have_no_residual() {
+        let fit = Curve::from_function(|x| 2.0 * x, 0.0, 4.0, 5);
+        let scatter = ScatterPlot::from_points(vec![vec2(1.0, 3.0), vec2(6.0, 0.0)])
+            .color(GREEN)
+            .residuals_to(&fit)
+            .color_by_residual(|_| RED);
+        assert_eq!(scatter.residuals(), Some(vec![Some(1.0), None]));
+
+        let mut canvas = RecordingCanvas::new();
+        scatter.draw(&mut canvas, 1.0, Vec2::ZERO);
+        let lines = canvas
+            .commands()
+            .iter()
+            .filter(|c| matches!(c, DrawCommand::Line { .. }))
+            .count();
+        assert_eq!(lines, 1);
+        assert_eq!(scatter.point_colors, Some(vec![RED, GREEN]));
+        // The segment-free point does not stretch the bounds down to the fit
+        assert_eq!(scatter.bounding_box().h, 3.0);
+    }
+}
@@ -1,4 +1,5 @@
 use super::{BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+use crate::canvas::{Canvas, TransformedCanvas};
 use macroquad::prelude::*;
 
 /// A circle mobject
@@ -57,7 +58,10 @@ impl Default for Circle {
 }
 
 impl Mobject for Circle {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let pivot = to_screen(self.center, screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, self.scale, self.rotation);
+
         let style = self.style.with_opacity(self.style.opacity);
         let screen_pos = to_screen(self.center, screen_center);
 
@@ -71,12 +75,12 @@ impl Mobject for Circle {
 
         // Draw fill if present
         if style.fill_color.a > 0.0 {
-            draw_circle(screen_pos.x, screen_pos.y, self.radius, style.fill_color);
+            canvas.draw_circle(screen_pos.x, screen_pos.y, self.radius, style.fill_color);
         }
 
         // Draw stroke
         if t >= 1.0 {
-            draw_circle_lines(
+            canvas.draw_circle_lines(
                 screen_pos.x,
                 screen_pos.y,
                 self.radius,
@@ -94,7 +98,7 @@ impl Mobject for Circle {
                 .collect();
 
             for i in 0..points.len().saturating_sub(1) {
-                draw_line(
+                canvas.draw_line(
                     points[i].x,
                     points[i].y,
                     points[i + 1].x,
@@ -107,7 +111,8 @@ impl Mobject for Circle {
     }
 
     fn bounding_box(&self) -> BoundingRect {
-        BoundingRect::from_xy_wh(self.center, Vec2::splat(self.radius * 2.0))
+        let rect = BoundingRect::from_xy_wh(self.center, Vec2::splat(self.radius * 2.0));
+        rect.transformed_about(self.center, self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -134,6 +139,10 @@ impl Mobject for Circle {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -192,14 +201,17 @@ impl Line {
 }
 
 impl Mobject for Line {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let pivot = to_screen(self.center(), screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, self.scale, self.rotation);
+
         let style = self.style.with_opacity(self.style.opacity);
         let current_end = self.start.lerp(self.end, t);
 
         let screen_start = to_screen(self.start, screen_center);
         let screen_end = to_screen(current_end, screen_center);
 
-        draw_line(
+        canvas.draw_line(
             screen_start.x,
             screen_start.y,
             screen_end.x,
@@ -212,7 +224,8 @@ impl Mobject for Line {
     fn bounding_box(&self) -> BoundingRect {
         let min = self.start.min(self.end);
         let max = self.start.max(self.end);
-        BoundingRect::from_corners(min, max)
+        let rect = BoundingRect::from_corners(min, max);
+        rect.transformed_about(self.center(), self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -241,6 +254,10 @@ impl Mobject for Line {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -317,7 +334,10 @@ impl Default for Rectangle {
 }
 
 impl Mobject for Rectangle {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let pivot = to_screen(self.center, screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, self.scale, self.rotation);
+
         let style = self.style.with_opacity(self.style.opacity);
         let hw = self.width / 2.0;
         let hh = self.height / 2.0;
@@ -339,7 +359,7 @@ impl Mobject for Rectangle {
         // Draw fill
         if style.fill_color.a > 0.0 {
             let screen_pos = to_screen(self.center, screen_center);
-            draw_rectangle(
+            canvas.draw_rectangle(
                 screen_pos.x - hw,
                 screen_pos.y - hh,
                 self.width,
@@ -356,7 +376,7 @@ impl Mobject for Rectangle {
             // Draw complete rectangle outline
             for i in 0..4 {
                 let next = (i + 1) % 4;
-                draw_line(
+                canvas.draw_line(
                     screen_corners[i].x,
                     screen_corners[i].y,
                     screen_corners[next].x,
@@ -386,7 +406,7 @@ impl Mobject for Rectangle {
                 let screen_start = to_screen(start, screen_center);
                 let screen_end = to_screen(current_end, screen_center);
 
-                draw_line(
+                canvas.draw_line(
                     screen_start.x,
                     screen_start.y,
                     screen_end.x,
@@ -401,7 +421,8 @@ impl Mobject for Rectangle {
     }
 
     fn bounding_box(&self) -> BoundingRect {
-        BoundingRect::from_xy_wh(self.center, vec2(self.width, self.height))
+        let rect = BoundingRect::from_xy_wh(self.center, vec2(self.width, self.height));
+        rect.transformed_about(self.center, self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -428,6 +449,10 @@ impl Mobject for Rectangle {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
@@ -487,7 +512,10 @@ impl Arrow {
 }
 
 impl Mobject for Arrow {
-    fn draw(&self, t: f32, screen_center: Vec2) {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let pivot = to_screen(self.center(), screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, self.scale, self.rotation);
+
         let style = self.style.with_opacity(self.style.opacity);
         let current_end = self.start.lerp(self.end, t);
 
@@ -495,7 +523,7 @@ impl Mobject for Arrow {
         let screen_end = to_screen(current_end, screen_center);
 
         // Draw line
-        draw_line(
+        canvas.draw_line(
             screen_start.x,
             screen_start.y,
             screen_end.x,
@@ -517,14 +545,15 @@ impl Mobject for Arrow {
             let screen_left = to_screen(left, screen_center);
             let screen_right = to_screen(right, screen_center);
 
-            draw_triangle(screen_tip, screen_left, screen_right, style.stroke_color);
+            canvas.draw_triangle(screen_tip, screen_left, screen_right, style.stroke_color);
         }
     }
 
     fn bounding_box(&self) -> BoundingRect {
         let min = self.start.min(self.end);
         let max = self.start.max(self.end);
-        BoundingRect::from_corners(min, max)
+        let rect = BoundingRect::from_corners(min, max);
+        rect.transformed_about(self.center(), self.scale, self.rotation)
     }
 
     fn center(&self) -> Vec2 {
@@ -553,6 +582,10 @@ impl Mobject for Arrow {
         self.scale = scale;
     }
 
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
     fn rotate(&mut self, angle: f32) {
         self.rotation += angle;
     }
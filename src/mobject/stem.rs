@@ -0,0 +1,298 @@
+use super::{
+    Axes2D, AxesFrame, BoundingRect, Mobject, MobjectId, MobjectStyle, PlotSpace, staggered_growth,
+    to_screen,
+};
+use crate::canvas::Canvas;
+use macroquad::prelude::*;
+
+/// Heights at discrete positions, such as a probability mass function, drawn
+/// as stems topped with dots or as bars centered on each position
+#[derive(Debug, Clone)]
+pub struct StemPlot {
+    id: MobjectId,
+    center: Vec2,
+    points: Vec<Vec2>,
+    /// Bar width in x units, or None for stems
+    bar_width: Option<f32>,
+    marker_radius: f32,
+    /// Positions within this x range are drawn in the highlight color
+    highlight: Option<(f32, f32, Color)>,
+    style: MobjectStyle,
+    space: PlotSpace,
+    scale: f32,
+    rotation: f32,
+}
+
+impl StemPlot {
+    pub fn new() -> Self {
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            points: Vec::new(),
+            bar_width: None,
+            marker_radius: 4.0,
+            highlight: None,
+            style: MobjectStyle {
+                stroke_color: Color::new(0.3, 0.6, 1.0, 1.0),
+                fill_color: Color::new(0.3, 0.6, 1.0, 1.0),
+                stroke_weight: 2.0,
+                opacity: 1.0,
+            },
+            space: PlotSpace::Pixels,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// One stem per point, from the baseline at `(x, 0)` up to `(x, y)`
+    pub fn from_points(points: Vec<Vec2>) -> Self {
+        Self::new().points(points)
+    }
+
+    pub fn points(mut self, points: Vec<Vec2>) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Draw bars `width` x units wide instead of stems
+    pub fn bars(mut self, width: f32) -> Self {
+        self.bar_width = Some(width);
+        self
+    }
+
+    /// Radius in pixels of the dot on each stem; 0 for none
+    pub fn marker_radius(mut self, radius: f32) -> Self {
+        self.marker_radius = radius;
+        self
+    }
+
+    /// Draw the positions in `min..=max` in `color`, e.g. a tail whose
+    /// probability is being shown
+    pub fn highlight(mut self, min: f32, max: f32, color: Color) -> Self {
+        self.highlight = Some((min, max, color));
+        self
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.stroke_color = color;
+        self.style.fill_color = color;
+        self
+    }
+
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.style.stroke_weight = weight;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn get_points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// The highlight color (faded like the rest) inside its range, else `color`
+    fn color_at(&self, x: f32, color: Color) -> Color {
+        match self.highlight {
+            Some((min, max, mut highlight)) if x >= min && x <= max => {
+                highlight.a *= self.style.opacity;
+                highlight
+            }
+            _ => color,
+        }
+    }
+}
+
+impl Default for StemPlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mobject for StemPlot {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+        let style = self.style.with_opacity(self.style.opacity);
+
+        let n = self.points.len();
+        for (i, point) in self.points.iter().enumerate() {
+            let growth = staggered_growth(i, n, t);
+            if growth <= 0.0 {
+                continue;
+            }
+            let place = |p: Vec2| to_screen(self.space.place(self.center, p), screen_center);
+            let top = vec2(point.x, point.y * growth);
+
+            match self.bar_width {
+                Some(width) => {
+                    let a = place(vec2(point.x - width / 2.0, 0.0));
+                    let b = place(vec2(point.x + width / 2.0, top.y));
+                    let (min, size) = (a.min(b), (b - a).abs());
+                    let fill = self.color_at(point.x, style.fill_color);
+                    canvas.draw_rectangle(min.x, min.y, size.x, size.y, fill);
+                }
+                None => {
+                    let (base, tip) = (place(vec2(point.x, 0.0)), place(top));
+                    let stroke = self.color_at(point.x, style.stroke_color);
+                    if style.stroke_weight > 0.0 {
+                        canvas.draw_line(base.x, base.y, tip.x, tip.y, style.stroke_weight, stroke);
+                    }
+                    if self.marker_radius > 0.0 && growth >= 1.0 {
+                        canvas.draw_circle(tip.x, tip.y, self.marker_radius, stroke);
+                    }
+                }
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        if self.points.is_empty() {
+            return BoundingRect::from_xy_wh(self.center, Vec2::ZERO);
+        }
+        let half = self.bar_width.unwrap_or(0.0) / 2.0;
+        let (min, max) = self.points.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), p| {
+                (
+                    min.min(vec2(p.x - half, p.y.min(0.0))),
+                    max.max(vec2(p.x + half, p.y.max(0.0))),
+                )
+            },
+        );
+        self.space
+            .bounds(min, max, self.center, self.scale, self.rotation)
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::{DrawCommand, RecordingCanvas};
+
+    #[test]
+    fn test_bars_highlight_and_grow_in_sequence() {
+        let mut plot =
+            StemPlot::from_points(vec![vec2(0.0, 10.0), vec2(1.0, 20.0), vec2(2.0, 30.0)])
+                .bars(0.5)
+                .color(BLUE)
+                .highlight(1.5, 3.0, RED);
+        plot.set_opacity(0.5);
+        let bars = |t: f32| {
+            let mut canvas = RecordingCanvas::new();
+            plot.draw(&mut canvas, t, Vec2::ZERO);
+            canvas
+                .commands()
+                .iter()
+                .filter_map(|c| match c {
+                    DrawCommand::Rectangle {
+                        position,
+                        size,
+                        color,
+                    } => Some((*position, *size, *color)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Bars are centered on x and rise from the baseline (screen y points down)
+        let done = bars(1.0);
+        assert_eq!(done[1].0, vec2(0.75, -20.0));
+        assert_eq!(done[1].1, vec2(0.5, 20.0));
+        let faded = |c: Color| Color::new(c.r, c.g, c.b, 0.5);
+        let colors: Vec<Color> = done.iter().map(|bar| bar.2).collect();
+        assert_eq!(colors, [faded(BLUE), faded(BLUE), faded(RED)]);
+
+        // Create grows the bars one after another
+        assert!(bars(0.0).is_empty());
+        let halfway = bars(0.5);
+        for (i, (bar, point)) in halfway.iter().zip(plot.get_points()).enumerate() {
+            let expected = point.y * staggered_growth(i, 3, 0.5);
+            assert!((bar.1.y - expected).abs() < 1e-4);
+        }
+        assert!(halfway[0].1.y / 10.0 > halfway[2].1.y / 30.0);
+    }
+}
@@ -0,0 +1,289 @@
+use super::{BoundingRect, Mobject, MobjectId, MobjectStyle, to_screen};
+use crate::canvas::{Canvas, Font, TextStyle, TransformedCanvas};
+use macroquad::prelude::*;
+
+/// Which point of a text block sits at the mobject's center position
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Offset from the block's top-left corner as fractions of (width, height)
+    pub(crate) fn fraction(self) -> Vec2 {
+        match self {
+            Anchor::TopLeft => vec2(0.0, 0.0),
+            Anchor::Top => vec2(0.5, 0.0),
+            Anchor::TopRight => vec2(1.0, 0.0),
+            Anchor::Left => vec2(0.0, 0.5),
+            Anchor::Center => vec2(0.5, 0.5),
+            Anchor::Right => vec2(1.0, 0.5),
+            Anchor::BottomLeft => vec2(0.0, 1.0),
+            Anchor::Bottom => vec2(0.5, 1.0),
+            Anchor::BottomRight => vec2(1.0, 1.0),
+        }
+    }
+}
+
+/// Horizontal alignment of lines within a multi-line text block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// A text label mobject. Supports multiple lines (split on '\n'),
+/// custom fonts and a typewriter-style reveal during Create.
+#[derive(Debug, Clone)]
+pub struct Text {
+    id: MobjectId,
+    center: Vec2,
+    text: String,
+    font: Font,
+    font_size: f32,
+    line_spacing: f32,
+    anchor: Anchor,
+    align: TextAlign,
+    style: MobjectStyle,
+    scale: f32,
+    rotation: f32,
+}
+
+impl Text {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            text: text.into(),
+            font: Font::default(),
+            font_size: 24.0,
+            line_spacing: 1.2,
+            anchor: Anchor::Center,
+            align: TextAlign::Left,
+            style: MobjectStyle {
+                fill_color: WHITE,
+                ..Default::default()
+            },
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Distance between baselines as a multiple of the font's line height
+    pub fn line_spacing(mut self, spacing: f32) -> Self {
+        self.line_spacing = spacing;
+        self
+    }
+
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.fill_color = color;
+        self
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Width and height of the whole text block
+    pub fn size(&self) -> Vec2 {
+        let (width, _) = self.line_widths();
+        let metrics = self.font.measure("", self.font_size);
+        let lines = self.text.lines().count().max(1);
+        let height = metrics.height() + (lines - 1) as f32 * self.line_height();
+        vec2(width, height)
+    }
+
+    fn line_height(&self) -> f32 {
+        self.font.measure("", self.font_size).height() * self.line_spacing
+    }
+
+    /// Widest line and the width of each line
+    fn line_widths(&self) -> (f32, Vec<f32>) {
+        let widths: Vec<f32> = self
+            .text
+            .lines()
+            .map(|line| self.font.measure(line, self.font_size).width)
+            .collect();
+        let max = widths.iter().copied().fold(0.0, f32::max);
+        (max, widths)
+    }
+
+    /// Top-left corner of the block in center-origin coordinates (Y up)
+    fn top_left(&self) -> Vec2 {
+        let size = self.size();
+        let offset = self.anchor.fraction() * size;
+        vec2(self.center.x - offset.x, self.center.y + offset.y)
+    }
+}
+
+impl Mobject for Text {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let pivot = to_screen(self.center, screen_center);
+        let mut canvas = TransformedCanvas::about(canvas, pivot, self.scale, self.rotation);
+
+        let style = self.style.with_opacity(self.style.opacity);
+        if style.fill_color.a <= 0.0 {
+            return;
+        }
+
+        // Typewriter reveal: show the first fraction `t` of all characters
+        let total_chars: usize = self.text.lines().map(|l| l.chars().count()).sum();
+        let mut chars_left = ((total_chars as f32) * t).ceil() as usize;
+
+        let text_style = TextStyle {
+            font: self.font.clone(),
+            font_size: self.font_size,
+            color: style.fill_color,
+        };
+        let ascent = self.font.measure("", self.font_size).ascent;
+        let (block_width, widths) = self.line_widths();
+        let top_left = self.top_left();
+
+        for (i, line) in self.text.lines().enumerate() {
+            if chars_left == 0 {
+                break;
+            }
+            let visible: String = line.chars().take(chars_left).collect();
+            chars_left -= visible.chars().count();
+
+            let x_offset = match self.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => (block_width - widths[i]) / 2.0,
+                TextAlign::Right => block_width - widths[i],
+            };
+            let baseline = top_left + vec2(x_offset, -ascent - i as f32 * self.line_height());
+            let screen_pos = to_screen(baseline, screen_center);
+
+            canvas.draw_text(&visible, screen_pos.x, screen_pos.y, &text_style);
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        let size = self.size();
+        let top_left = self.top_left();
+        let rect =
+            BoundingRect::from_corners(top_left - vec2(0.0, size.y), top_left + vec2(size.x, 0.0));
+        rect.transformed_about(self.center, self.scale, self.rotation)
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::{DrawCommand, RecordingCanvas};
+
+    fn drawn_text(text: &Text, t: f32) -> Vec<(String, Vec2)> {
+        let mut canvas = RecordingCanvas::new();
+        text.draw(&mut canvas, t, Vec2::ZERO);
+        canvas
+            .commands()
+            .iter()
+            .filter_map(|c| match c {
+                DrawCommand::Text { text, position, .. } => Some((text.clone(), *position)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_typewriter_reveal_spans_lines() {
+        let text = Text::new("abcd\nefgh");
+
+        let half = drawn_text(&text, 0.5);
+        assert_eq!(half.len(), 1);
+        assert_eq!(half[0].0, "abcd");
+
+        let most = drawn_text(&text, 0.75);
+        assert_eq!(most[1].0, "ef");
+        assert!(most[1].1.y > most[0].1.y);
+    }
+
+    #[test]
+    fn test_anchor_positions_block() {
+        let text = Text::new("label").at(vec2(100.0, 0.0)).anchor(Anchor::Left);
+        let bounds = text.bounding_box();
+
+        assert!((bounds.x - 100.0).abs() < 0.001);
+        assert!((bounds.y + bounds.h / 2.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// How tick values are turned into label text
+#[derive(Clone, Default)]
+pub enum TickFormat {
+    /// As many decimals as the tick spacing needs, switching to scientific
+    /// notation for very large or very small magnitudes
+    #[default]
+    Auto,
+    /// A fixed number of decimals: `Fixed(2)` gives "1.50"
+    Fixed(usize),
+    /// Mantissa with the given decimals times a power of ten: `Scientific(1)` gives "1.5×10³"
+    Scientific(usize),
+    /// Fractions shown as percentages: `Percent(0)` turns 0.25 into "25%"
+    Percent(usize),
+    /// User-supplied formatter
+    Custom(Arc<dyn Fn(f32) -> String + Send + Sync>),
+}
+
+impl TickFormat {
+    pub fn custom(format: impl Fn(f32) -> String + Send + Sync + 'static) -> Self {
+        TickFormat::Custom(Arc::new(format))
+    }
+
+    /// Label for `value`, where `step` is the distance between neighbouring ticks
+    pub fn format(&self, value: f32, step: f32) -> String {
+        match self {
+            TickFormat::Auto => {
+                let magnitude = value.abs();
+                if magnitude >= 1e5 || (magnitude > 0.0 && magnitude < 1e-3) {
+                    scientific(
+                        value,
+                        significant_decimals(step / 10f32.powf(magnitude.log10().floor())),
+                    )
+                } else {
+                    fixed(value, significant_decimals(step))
+                }
+            }
+            TickFormat::Fixed(decimals) => fixed(value, *decimals),
+            TickFormat::Scientific(decimals) => scientific(value, *decimals),
+            TickFormat::Percent(decimals) => format!("{}%", fixed(value * 100.0, *decimals)),
+            TickFormat::Custom(format) => format(value),
+        }
+    }
+}
+
+impl fmt::Debug for TickFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TickFormat::Auto => write!(f, "Auto"),
+            TickFormat::Fixed(d) => write!(f, "Fixed({})", d),
+            TickFormat::Scientific(d) => write!(f, "Scientific({})", d),
+            TickFormat::Percent(d) => write!(f, "Percent({})", d),
+            TickFormat::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Major and minor tick positions along one axis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ticks {
+    /// Distance between major ticks
+    pub step: f32,
+    pub major: Vec<f32>,
+    /// Subdivisions between (and just outside) the major ticks
+    pub minor: Vec<f32>,
+}
+
+impl Ticks {
+    /// About `target` major ticks at "nice" multiples (1, 2 or 5 × 10ⁿ) across `range`,
+    /// with 4 or 5 minor subdivisions each
+    pub fn nice(range: (f32, f32), target: usize) -> Self {
+        let step = nice_step(range.1 - range.0, target);
+        let mantissa = step / 10f32.powf(step.log10().floor());
+        // 1 and 5 split evenly into fifths, 2 into quarters (matplotlib's rule)
+        let divisions = if (mantissa - 2.0).abs() < 0.01 { 4 } else { 5 };
+
+        let major = multiples(range, step);
+        let minor = multiples(range, step / divisions as f32)
+            .into_iter()
+            .filter(|v| major.iter().all(|m| (m - v).abs() > step * 1e-3))
+            .collect();
+        Self { step, major, minor }
+    }
+
+    /// Major ticks at every multiple of `step` inside `range`, no minor ticks
+    pub fn fixed(range: (f32, f32), step: f32) -> Self {
+        Self {
+            step,
+            major: multiples(range, step),
+            minor: Vec::new(),
+        }
+    }
+}
+
+/// The 1/2/5 × 10ⁿ step closest to giving `target` intervals over `span`
+pub fn nice_step(span: f32, target: usize) -> f32 {
+    let span = span.abs();
+    if span == 0.0 || !span.is_finite() {
+        return 1.0;
+    }
+    let raw = span / target.max(1) as f32;
+    let magnitude = 10f32.powf(raw.log10().floor());
+    let mantissa = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .min_by(|a, b| {
+            let (da, db) = (
+                (raw / magnitude / a).ln().abs(),
+                (raw / magnitude / b).ln().abs(),
+            );
+            da.total_cmp(&db)
+        })
+        .unwrap_or(1.0);
+    mantissa * magnitude
+}
+
+/// Multiples of `step` inside `range`, computed by index so they don't drift
+fn multiples(range: (f32, f32), step: f32) -> Vec<f32> {
+    let (lo, hi) = (range.0.min(range.1), range.0.max(range.1));
+    if step <= 0.0 || !step.is_finite() || (hi - lo) / step > 10_000.0 {
+        return Vec::new();
+    }
+    let tolerance = step * 1e-4;
+    let first = ((lo - tolerance) / step).ceil() as i64;
+    let last = ((hi + tolerance) / step).floor() as i64;
+    // Round to the step's precision to avoid values like 0.30000001 and "-0"
+    let precision = 10f32.powi(significant_decimals(step) as i32);
+    (first..=last)
+        .map(|i| (i as f32 * step * precision).round() / precision + 0.0)
+        .collect()
+}
+
+/// Fewest decimals (up to 6) that represent multiples of `step` exactly
+fn significant_decimals(step: f32) -> usize {
+    let step = step.abs();
+    if step == 0.0 || !step.is_finite() {
+        return 0;
+    }
+    (0..6)
+        .find(|&d| {
+            let scaled = step * 10f32.powi(d as i32);
+            scaled.round() >= 1.0 && (scaled - scaled.round()).abs() < 1e-3 * scaled
+        })
+        .unwrap_or(6)
+}
+
+/// Fixed decimals with a typographic minus sign and no negative zero
+fn fixed(value: f32, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value);
+    if text
+        .trim_start_matches('-')
+        .chars()
+        .all(|c| c == '0' || c == '.')
+    {
+        return text.trim_start_matches('-').to_string();
+    }
+    text.replacen('-', "−", 1)
+}
+
+fn scientific(value: f32, decimals: usize) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    let text = format!("{:.*e}", decimals, value);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    let exponent: String = exponent.chars().map(superscript).collect();
+    format!("{}×10{}", mantissa.replacen('-', "−", 1), exponent)
+}
+
+fn superscript(c: char) -> char {
+    match c {
+        '-' => '⁻',
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_uses_step_precision() {
+        assert_eq!(TickFormat::Auto.format(2.0, 1.0), "2");
+        assert_eq!(TickFormat::Auto.format(0.75, 0.25), "0.75");
+        assert_eq!(TickFormat::Auto.format(-1.5, 0.5), "−1.5");
+        assert_eq!(TickFormat::Auto.format(-0.0, 0.5), "0.0");
+        assert_eq!(TickFormat::Auto.format(0.0015, 0.0005), "0.0015");
+        assert_eq!(TickFormat::Auto.format(250000.0, 50000.0), "2.5×10⁵");
+    }
+
+    #[test]
+    fn test_nice_ticks() {
+        let ticks = Ticks::nice((-4.3, 4.3), 8);
+        assert_eq!(ticks.step, 1.0);
+        assert_eq!(ticks.major.first(), Some(&-4.0));
+        assert_eq!(ticks.major.len(), 9);
+        assert!(ticks.minor.contains(&-4.2));
+
+        let ticks = Ticks::nice((0.0, 10_000.0), 5);
+        assert_eq!(ticks.step, 2000.0);
+        assert_eq!(ticks.major, [0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10_000.0]);
+        assert_eq!(ticks.minor.len(), 15);
+    }
+
+    #[test]
+    fn test_explicit_formats() {
+        assert_eq!(TickFormat::Fixed(2).format(1.5, 1.0), "1.50");
+        assert_eq!(TickFormat::Scientific(1).format(0.00123, 1.0), "1.2×10⁻³");
+        assert_eq!(TickFormat::Percent(0).format(0.25, 0.05), "25%");
+        let custom = TickFormat::custom(|v| format!("${}", v));
+        assert_eq!(custom.format(3.0, 1.0), "$3");
+    }
+}
@@ -0,0 +1,368 @@
+use super::boxplot::group_color;
+use super::kde::density;
+use super::{
+    Axes2D, AxesFrame, Bandwidth, BoundingRect, BoxStats, Kernel, Mobject, MobjectId, MobjectStyle,
+    PlotSpace, Whiskers, to_screen,
+};
+use crate::canvas::Canvas;
+use crate::data::Column;
+use crate::state::Dataset;
+use macroquad::prelude::*;
+
+/// Farthest the density is drawn beyond the data, in bandwidths
+const CUT: f32 = 2.0;
+
+/// Violin plots of several groups: each a kernel density estimate mirrored
+/// about x = 0, 1, 2, ..., lining up with `Axes2D::x_categories`. Values are along y.
+#[derive(Debug, Clone)]
+pub struct ViolinPlot {
+    id: MobjectId,
+    center: Vec2,
+    names: Vec<String>,
+    groups: Vec<Vec<f32>>,
+    /// Right half of each violin as (value, half-width) pairs in x units
+    profiles: Vec<Vec<Vec2>>,
+    /// Inner box plot of each group
+    stats: Vec<Option<BoxStats>>,
+    kernel: Kernel,
+    bandwidth: Bandwidth,
+    samples: usize,
+    /// Widest point of each violin, in x units (one unit per group)
+    width: f32,
+    /// Draw a slim box plot (quartiles, whiskers, median dot) inside each violin
+    show_box: bool,
+    group_colors: Option<Vec<Color>>,
+    style: MobjectStyle,
+    space: PlotSpace,
+    scale: f32,
+    rotation: f32,
+}
+
+impl ViolinPlot {
+    pub fn new() -> Self {
+        Self {
+            id: MobjectId::new(),
+            center: Vec2::ZERO,
+            names: Vec::new(),
+            groups: Vec::new(),
+            profiles: Vec::new(),
+            stats: Vec::new(),
+            kernel: Kernel::default(),
+            bandwidth: Bandwidth::default(),
+            samples: 100,
+            width: 0.8,
+            show_box: true,
+            group_colors: None,
+            style: MobjectStyle {
+                stroke_color: Color::new(0.9, 0.9, 0.9, 1.0),
+                fill_color: Color::new(0.3, 0.6, 1.0, 0.5),
+                stroke_weight: 1.5,
+                opacity: 1.0,
+            },
+            space: PlotSpace::Pixels,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// One group per value of the `group` column, holding its rows' `value` column
+    pub fn from_dataset(
+        data: &Dataset,
+        value: impl Into<Column>,
+        group: impl Into<Column>,
+    ) -> Self {
+        let value = value.into();
+        Self::from_groups(data.group_by(group).into_iter().map(|(name, rows)| {
+            let values = rows.values(value.clone()).unwrap_or_default();
+            (name, values)
+        }))
+    }
+
+    pub fn from_groups<S: Into<String>>(groups: impl IntoIterator<Item = (S, Vec<f32>)>) -> Self {
+        groups
+            .into_iter()
+            .fold(Self::new(), |plot, (name, values)| {
+                plot.group(name, &values)
+            })
+    }
+
+    /// Add a group in the next slot; non-finite values are ignored
+    pub fn group(mut self, name: impl Into<String>, values: &[f32]) -> Self {
+        self.names.push(name.into());
+        let values: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        self.profiles.push(self.profile(&values));
+        self.stats
+            .push(BoxStats::from_values(&values, Whiskers::default()));
+        self.groups.push(values);
+        self
+    }
+
+    pub fn kernel(mut self, kernel: Kernel) -> Self {
+        self.kernel = kernel;
+        self.refresh();
+        self
+    }
+
+    /// Bandwidth rule, applied to each group separately
+    pub fn bandwidth(mut self, bandwidth: Bandwidth) -> Self {
+        self.bandwidth = bandwidth;
+        self.refresh();
+        self
+    }
+
+    /// Points along each side of a violin
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(2);
+        self.refresh();
+        self
+    }
+
+    /// Width of each violin at its widest, in x units (groups are one unit apart)
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self.refresh();
+        self
+    }
+
+    pub fn show_box(mut self, show: bool) -> Self {
+        self.show_box = show;
+        self
+    }
+
+    /// Set the center offset, an extra pixel offset when on axes; see [`PlotSpace`]
+    pub fn at(mut self, pos: Vec2) -> Self {
+        self.center = pos;
+        self
+    }
+
+    /// Plot in the data coordinates of `axes`; see [`PlotSpace`]
+    pub fn on_axes(mut self, axes: &Axes2D) -> Self {
+        self.space = PlotSpace::Axes {
+            id: axes.id(),
+            frame: Some(axes.frame()),
+        };
+        self
+    }
+
+    /// Like `on_axes`, for axes known only by id; see [`PlotSpace`]
+    pub fn on_axes_id(mut self, id: MobjectId) -> Self {
+        self.space = PlotSpace::Axes { id, frame: None };
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.fill_color = color;
+        self
+    }
+
+    /// One fill color per group, cycling if there are fewer colors than groups
+    pub fn group_colors(mut self, colors: Vec<Color>) -> Self {
+        self.group_colors = Some(colors);
+        self
+    }
+
+    pub fn stroke_color(mut self, color: Color) -> Self {
+        self.style.stroke_color = color;
+        self
+    }
+
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.style.stroke_weight = weight;
+        self
+    }
+
+    /// Group names in slot order, for `Axes2D::x_categories`
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Re-estimate every profile after the kernel, bandwidth or sampling changed
+    fn refresh(&mut self) {
+        self.profiles = self
+            .groups
+            .iter()
+            .map(|values| self.profile(values))
+            .collect();
+    }
+
+    /// Right half of the violin of `values` as (value, half-width) pairs in x units
+    fn profile(&self, values: &[f32]) -> Vec<Vec2> {
+        if values.is_empty() {
+            return Vec::new();
+        }
+        let h = self.bandwidth.resolve(values);
+        let reach = self.kernel.reach().min(CUT) * h;
+        let (lo, hi) = values
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        let (lo, hi) = (lo - reach, hi + reach);
+
+        let mut profile: Vec<Vec2> = (0..self.samples)
+            .map(|i| {
+                let y = lo + (hi - lo) * i as f32 / (self.samples - 1) as f32;
+                vec2(y, density(values, self.kernel, h, y))
+            })
+            .collect();
+        // Every violin is equally wide at its widest point
+        let peak = profile.iter().fold(0f32, |acc, p| acc.max(p.y));
+        if peak > 0.0 {
+            for p in &mut profile {
+                p.y *= self.width / 2.0 / peak;
+            }
+        }
+        profile
+    }
+}
+
+impl Default for ViolinPlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mobject for ViolinPlot {
+    fn draw(&self, canvas: &mut dyn Canvas, t: f32, screen_center: Vec2) {
+        let mut canvas = self.space.canvas(
+            canvas,
+            screen_center,
+            self.center,
+            self.scale,
+            self.rotation,
+        );
+        let style = self.style.with_opacity(self.style.opacity);
+        let point =
+            |x: f32, y: f32| to_screen(self.space.place(self.center, vec2(x, y)), screen_center);
+
+        for (index, profile) in self.profiles.iter().enumerate() {
+            if profile.len() < 2 {
+                continue;
+            }
+            let x = index as f32;
+
+            // Violins swell out sideways from their center line
+            let outline: Vec<Vec2> = profile
+                .iter()
+                .map(|p| point(x + p.y * t, p.x))
+                .chain(profile.iter().rev().map(|p| point(x - p.y * t, p.x)))
+                .collect();
+            let mut fill = group_color(&self.group_colors, index, self.style.fill_color);
+            fill.a *= style.opacity;
+            if fill.a > 0.0 {
+                canvas.draw_polygon(&outline, fill);
+            }
+            if style.stroke_color.a > 0.0 {
+                let mut closed = outline.clone();
+                closed.push(outline[0]);
+                canvas.draw_polyline(&closed, style.stroke_weight, style.stroke_color);
+            }
+
+            if let (true, Some(stats)) = (self.show_box, &self.stats[index]) {
+                let stroke = style.stroke_color;
+                let (low, high) = (point(x, stats.low), point(x, stats.high));
+                canvas.draw_line(low.x, low.y, high.x, high.y, style.stroke_weight, stroke);
+                let (q1, q3) = (point(x, stats.q1), point(x, stats.q3));
+                let box_weight = style.stroke_weight * 4.0 * t;
+                canvas.draw_line(q1.x, q1.y, q3.x, q3.y, box_weight, stroke);
+                let median = point(x, stats.median);
+                let mut dot = style.fill_color;
+                dot.a = style.opacity;
+                canvas.draw_circle(median.x, median.y, box_weight * 0.4, dot);
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingRect {
+        let (low, high) = self
+            .profiles
+            .iter()
+            .flatten()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                (lo.min(p.x), hi.max(p.x))
+            });
+        if low > high {
+            return BoundingRect::from_xy_wh(self.center, Vec2::ZERO);
+        }
+        let half = self.width / 2.0;
+        self.space.bounds(
+            vec2(-half, low),
+            vec2(self.groups.len() as f32 - 1.0 + half, high),
+            self.center,
+            self.scale,
+            self.rotation,
+        )
+    }
+
+    fn center(&self) -> Vec2 {
+        self.center
+    }
+
+    fn set_center(&mut self, pos: Vec2) {
+        self.center = pos;
+    }
+
+    fn opacity(&self) -> f32 {
+        self.style.opacity
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.style.opacity = opacity;
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    fn set_rotate(&mut self, angle: f32) {
+        self.rotation = angle;
+    }
+
+    fn id(&self) -> MobjectId {
+        self.id
+    }
+
+    fn bound_axes(&self) -> Option<MobjectId> {
+        self.space.axes_id()
+    }
+
+    fn set_axes_frame(&mut self, frame: AxesFrame) {
+        self.space.set_frame(frame);
+    }
+
+    fn clone_box(&self) -> Box<dyn Mobject> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violins_share_peak_width() {
+        let plot = ViolinPlot::new()
+            .group("narrow", &[0.0, 0.1, 0.2, 0.1])
+            .group("wide", &[-3.0, 0.0, 2.0, 5.0])
+            .width(0.6);
+
+        for index in 0..2 {
+            let peak = plot.profiles[index]
+                .iter()
+                .fold(0f32, |acc, p| acc.max(p.y));
+            assert!((peak - 0.3).abs() < 1e-5);
+        }
+        let bounds = plot.bounding_box();
+        assert!((bounds.x + 0.3).abs() < 1e-5 && (bounds.w - 1.6).abs() < 1e-5);
+    }
+}
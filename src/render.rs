@@ -2,6 +2,7 @@ use macroquad::prelude::*;
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::canvas::{Canvas, RasterCanvas};
 use crate::scene::Scene;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -83,33 +84,60 @@ impl RenderConfig {
 pub struct Renderer;
 
 impl Renderer {
-    /// Render a scene to frames (Simplified)
-    /// I have a lot of xtra stuff to do  here
-    /// integrates into macroquad's async main pattern
-    pub fn render_frames(
-        scene: &mut Scene,
-        config: &RenderConfig,
-        _draw_fn: impl Fn(&mut Scene, f32, Vec2),
-    ) -> std::io::Result<Vec<PathBuf>> {
+    /// Render a scene to PNG frames using the software rasterizer.
+    /// Needs no window or GPU, so it can run in headless batch jobs.
+    /// Frames are independent, so they are rendered in parallel across all cores.
+    pub fn render_frames(scene: &Scene, config: &RenderConfig) -> std::io::Result<Vec<PathBuf>> {
         std::fs::create_dir_all(&config.output_dir)?;
 
         let duration = scene.duration();
-        let total_frames = config.total_frames(duration);
+        let total_frames = config.total_frames(duration) as usize;
         let frame_duration = 1.0 / config.fps as f32;
 
-        let mut frame_paths = Vec::new();
+        let frame_paths: Vec<PathBuf> = (0..total_frames)
+            .map(|frame| config.output_dir.join(format!("frame_{:05}.png", frame)))
+            .collect();
+
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .clamp(1, total_frames.max(1));
+
+        std::thread::scope(|s| {
+            let handles: Vec<_> = (0..workers)
+                .map(|worker| {
+                    let frame_paths = &frame_paths;
+                    s.spawn(move || -> std::io::Result<()> {
+                        let mut canvas = RasterCanvas::new(config.width, config.height);
+                        let screen_center = canvas.screen_center();
+
+                        for frame in (worker..total_frames).step_by(workers) {
+                            let time = frame as f32 * frame_duration;
+                            scene.render_frame(&mut canvas, time, screen_center);
+                            canvas.save_png(&frame_paths[frame])?;
+                        }
+                        Ok(())
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .try_for_each(|h| h.join().expect("render worker panicked"))
+        })?;
 
-        for frame in 0..total_frames {
-            let _time = frame as f32 * frame_duration;
-            let frame_path = config.output_dir.join(format!("frame_{:05}.png", frame));
+        Ok(frame_paths)
+    }
 
-            // The actual drawing would be done by the draw function (which accesses macroquad)
-            // draw_fn(scene, time, screen_center);
+    /// Render a scene according to `config.format`.
+    /// Returns the video path for `Mp4`, or the output directory for `Frames`.
+    pub fn render(scene: &Scene, config: &RenderConfig) -> std::io::Result<PathBuf> {
+        Self::render_frames(scene, config)?;
 
-            frame_paths.push(frame_path);
+        match config.format {
+            OutputFormat::Frames => Ok(config.output_dir.clone()),
+            OutputFormat::Mp4 => Self::frames_to_video(config),
         }
-
-        Ok(frame_paths)
     }
 
     /// MP4 using ffmpeg
@@ -151,15 +179,15 @@ impl Renderer {
 }
 
 pub trait Renderable {
-    fn render_frame(&mut self, time: f32, screen_center: Vec2);
+    fn render_frame(&self, canvas: &mut dyn Canvas, time: f32, screen_center: Vec2);
 
     fn duration(&self) -> f32;
 }
 
 impl Renderable for Scene {
-    fn render_frame(&mut self, time: f32, screen_center: Vec2) {
-        clear_background(self.background_color());
-        self.draw_at(time, screen_center);
+    fn render_frame(&self, canvas: &mut dyn Canvas, time: f32, screen_center: Vec2) {
+        canvas.clear(self.background_color());
+        self.draw_at(canvas, time, screen_center);
     }
 
     fn duration(&self) -> f32 {
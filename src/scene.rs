@@ -1,7 +1,9 @@
 use macroquad::prelude::*;
 use std::collections::HashMap;
+use std::path::Path;
 
 use crate::animation::Animation;
+use crate::canvas::{Canvas, SvgCanvas};
 use crate::mobject::{Mobject, MobjectId};
 use crate::timeline::Timeline;
 
@@ -21,9 +23,13 @@ impl Default for Camera {
     }
 }
 
-/// A scene contains mobjects and a timeline of animations
+/// A scene contains mobjects and a timeline of animations.
+/// Mobjects are stored in their initial state; the state at any time is
+/// computed from that snapshot and the timeline, never by mutating it.
 pub struct Scene {
     mobjects: HashMap<MobjectId, Box<dyn Mobject>>,
+    /// Insertion order, which is also the drawing order
+    order: Vec<MobjectId>,
     timeline: Timeline,
     camera: Camera,
     background: Color,
@@ -33,6 +39,7 @@ impl Scene {
     pub fn new() -> Self {
         Self {
             mobjects: HashMap::new(),
+            order: Vec::new(),
             timeline: Timeline::new(),
             camera: Camera::default(),
             background: Color::new(0.0, 0.0, 0.0, 1.0), // Black background
@@ -46,7 +53,9 @@ impl Scene {
 
     pub fn add(&mut self, mobject: impl Mobject + 'static) -> MobjectId {
         let id = mobject.id();
-        self.mobjects.insert(id, Box::new(mobject));
+        if self.mobjects.insert(id, Box::new(mobject)).is_none() {
+            self.order.push(id);
+        }
         id
     }
 
@@ -56,9 +65,11 @@ impl Scene {
     }
 
     pub fn remove(&mut self, id: MobjectId) -> Option<Box<dyn Mobject>> {
+        self.order.retain(|&o| o != id);
         self.mobjects.remove(&id)
     }
 
+    /// Initial (pre-animation) state of a mobject
     pub fn get(&self, id: MobjectId) -> Option<&dyn Mobject> {
         match self.mobjects.get(&id) {
             Some(b) => Some(b.as_ref()),
@@ -66,6 +77,7 @@ impl Scene {
         }
     }
 
+    /// Mutable access to a mobject's initial state
     pub fn get_mut(&mut self, id: MobjectId) -> Option<&mut dyn Mobject> {
         match self.mobjects.get_mut(&id) {
             Some(b) => Some(b.as_mut()),
@@ -118,29 +130,67 @@ impl Scene {
         self
     }
 
+    /// State of a mobject at `time`, computed from its initial state and the timeline.
+    /// Returns None if the mobject is not in the scene or not yet introduced.
+    pub fn mobject_at(&self, id: MobjectId, time: f32) -> Option<Box<dyn Mobject>> {
+        let initial = self.mobjects.get(&id)?;
+        if !self.timeline.is_visible_at(id, time) {
+            return None;
+        }
+
+        let mut state = initial.clone_box();
+        self.timeline.apply_at(state.as_mut(), time);
+
+        // Plots on axes follow the axes' state at the same time, visible or not
+        if let Some(axes_id) = state.bound_axes() {
+            let frame = self.mobjects.get(&axes_id).and_then(|axes| {
+                let mut axes = axes.clone_box();
+                self.timeline.apply_at(axes.as_mut(), time);
+                axes.axes_frame()
+            });
+            if let Some(frame) = frame {
+                state.set_axes_frame(frame);
+            }
+        }
+        Some(state)
+    }
+
     /// Draw all mobjects at a specific time
     /// screen_center is the center of the screen in screen coordinates (for coordinate transform)
-    pub fn draw_at(&mut self, time: f32, screen_center: Vec2) {
-        // Apply all active animations
-        let active = self.timeline.active_at(time);
-
-        for (entry, t) in &active {
-            let target_id = entry.animation.target_id();
-            if let Some(mobject) = self.mobjects.get_mut(&target_id) {
-                entry.animation.apply(mobject.as_mut(), *t);
+    pub fn draw_at(&self, canvas: &mut dyn Canvas, time: f32, screen_center: Vec2) {
+        for &id in &self.order {
+            if let Some(mobject) = self.mobject_at(id, time) {
+                // Get draw progress for Create-type animations
+                let draw_progress = self.timeline.draw_progress_for(id, time);
+                mobject.draw(canvas, draw_progress, screen_center);
             }
         }
+    }
 
-        // Draw all mobjects
-        for (id, mobject) in &self.mobjects {
-            // Get draw progress for Create-type animations
-            let draw_progress = self.timeline.draw_progress_for(*id, time);
-            mobject.draw(draw_progress, screen_center);
-        }
+    /// Render the scene at `time` to an SVG document of the given size
+    pub fn to_svg(&self, time: f32, width: u32, height: u32) -> String {
+        let mut canvas = SvgCanvas::new(width, height);
+        let screen_center = canvas.screen_center();
+        canvas.clear(self.background);
+        self.draw_at(&mut canvas, time, screen_center);
+        canvas.to_svg_string()
     }
 
+    /// Export a still of the scene at `time` as a standalone SVG file of the given
+    /// size, e.g. the `width` and `height` of the `RenderConfig` used for video
+    pub fn export_svg(
+        &self,
+        time: f32,
+        width: u32,
+        height: u32,
+        path: impl AsRef<Path>,
+    ) -> std::io::Result<()> {
+        std::fs::write(path, self.to_svg(time, width, height))
+    }
+
+    /// Mobject ids in drawing order
     pub fn mobject_ids(&self) -> Vec<MobjectId> {
-        self.mobjects.keys().copied().collect()
+        self.order.clone()
     }
 }
 
@@ -149,3 +199,71 @@ impl Default for Scene {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{FadeIn, MoveTo, Scale, SetRange};
+    use crate::canvas::{DrawCommand, RecordingCanvas};
+    use crate::mobject::{Axes2D, Circle, Curve};
+
+    fn draw(scene: &Scene, time: f32) -> Vec<DrawCommand> {
+        let mut canvas = RecordingCanvas::new();
+        scene.draw_at(&mut canvas, time, vec2(400.0, 300.0));
+        canvas.commands().to_vec()
+    }
+
+    #[test]
+    fn test_seeking_gives_identical_frames() {
+        let mut scene = Scene::new();
+        let a = scene.add(Circle::new().radius(10.0).at(vec2(-100.0, 0.0)));
+        let b = scene.add(Circle::new().radius(20.0));
+        scene
+            .play(FadeIn::new(a))
+            .play(MoveTo::new(a, vec2(100.0, 50.0)))
+            .play(FadeIn::new(b));
+
+        let direct = draw(&scene, 1.5);
+        draw(&scene, 3.0);
+        draw(&scene, 0.2);
+        assert_eq!(draw(&scene, 1.5), direct);
+    }
+
+    #[test]
+    fn test_introduced_mobjects_hidden_until_their_animation() {
+        let mut scene = Scene::new();
+        let a = scene.add(Circle::new());
+        let b = scene.add(Circle::new());
+        scene.play(FadeIn::new(a)).play(FadeIn::new(b));
+
+        assert!(scene.mobject_at(b, 0.5).is_none());
+        assert!(scene.mobject_at(b, 1.5).is_some());
+        assert_eq!(scene.mobject_ids(), vec![a, b]);
+    }
+
+    #[test]
+    fn test_plots_follow_bound_axes() {
+        let mut scene = Scene::new();
+        let axes = Axes2D::new().scale(10.0);
+        let axes_id = scene.add(axes.clone());
+        let curve =
+            scene.add(Curve::from_points(vec![vec2(1.0, 1.0), vec2(2.0, 3.0)]).on_axes(&axes));
+        scene
+            .play(MoveTo::new(axes_id, vec2(100.0, 0.0)))
+            .play(Scale::new(axes_id, 2.0))
+            .play(SetRange::new(axes_id).x_range(0.0, 10.0));
+
+        let bounds = |time| scene.mobject_at(curve, time).unwrap().bounding_box();
+        assert!((bounds(0.0).x - 10.0).abs() < 0.001);
+        assert!((bounds(1.0).x - 110.0).abs() < 0.001);
+        assert!((bounds(2.0).x - 120.0).abs() < 0.001);
+        assert!((bounds(2.0).h - 40.0).abs() < 0.001);
+
+        let ranges = scene
+            .mobject_at(axes_id, 3.0)
+            .unwrap()
+            .axes_frame()
+            .unwrap();
+        assert_eq!(ranges.x_range, (0.0, 10.0));
+    }
+}
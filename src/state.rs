@@ -1,77 +1,399 @@
+use crate::data::{Column, ColumnData, DataReader, Value, csv, json};
+use crate::stats::Rng;
+use crate::stats::describe::{self, Summary};
+use crate::stats::rng::covariance_factor;
 use macroquad::prelude::*;
-use macroquad::rand::gen_range;
+use std::io;
+use std::path::Path;
 
-#[derive(Debug, Clone)]
+/// A table of named, typed columns of equal length.
+///
+/// Plotting helpers read the columns named `x`, `y` and `z`; any other
+/// column can be pulled out by name or position with `values`, split with
+/// `group_by` or used to `filter` rows.
+///
+/// ```ignore
+/// let data = Dataset::read_csv("penguins.csv")?;
+/// let adults = data.filter(|row| row.number("age").is_some_and(|age| age >= 1.0));
+/// let boxes = BoxPlot::from_dataset(&adults, "mass", "species");
+/// let scatter = ScatterPlot::from_dataset(&adults, "flipper", "mass")
+///     .color_by(&adults.values("species").unwrap(), |code| palette[code as usize]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub struct Dataset {
-    pub points: Vec<Vec3>,
+    names: Vec<String>,
+    columns: Vec<ColumnData>,
 }
 
 impl Dataset {
     pub fn new() -> Self {
-        Self { points: Vec::new() }
+        Self {
+            names: Vec::new(),
+            columns: Vec::new(),
+        }
     }
 
+    /// Float columns `x`, `y` and `z` from the coordinates of `points`
     pub fn from_points(points: Vec<Vec3>) -> Self {
-        Self { points }
+        let axis = |i: usize| ColumnData::from(points.iter().map(|p| p[i]).collect::<Vec<f32>>());
+        Self::new()
+            .with_column("x", axis(0))
+            .with_column("y", axis(1))
+            .with_column("z", axis(2))
+    }
+
+    /// Add a column, or replace the one with the same name.
+    ///
+    /// Panics if its length differs from the columns already present.
+    pub fn with_column(mut self, name: impl Into<String>, data: impl Into<ColumnData>) -> Self {
+        let (name, data) = (name.into(), data.into());
+        if let Some(first) = self.columns.first() {
+            assert_eq!(
+                first.len(),
+                data.len(),
+                "column \"{}\" has {} rows but the dataset has {}",
+                name,
+                data.len(),
+                first.len()
+            );
+        }
+        match self.names.iter().position(|n| *n == name) {
+            Some(index) => self.columns[index] = data,
+            None => {
+                self.names.push(name);
+                self.columns.push(data);
+            }
+        }
+        self
+    }
+
+    /// All columns of a CSV file with a header row, each typed as the
+    /// narrowest of boolean, integer, float (missing values as NaN) or
+    /// categorical that holds every value
+    pub fn read_csv(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse_csv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Like `read_csv`, for tab-separated files
+    pub fn read_tsv(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse_tsv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Like `read_csv`, for JSON holding an array of records or an object of column arrays
+    pub fn read_json(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse_csv(text: &str) -> io::Result<Self> {
+        Ok(csv::parse(text, ',', true)?.into_dataset())
+    }
+
+    pub fn parse_tsv(text: &str) -> io::Result<Self> {
+        Ok(csv::parse(text, '\t', true)?.into_dataset())
+    }
+
+    pub fn parse_json(text: &str) -> io::Result<Self> {
+        Ok(json::parse(text)?.into_dataset())
+    }
+
+    /// Read x, y and optionally z columns, by header name or index, from a CSV
+    /// file. Rows with missing values are skipped; `DataReader` offers other
+    /// policies and headerless files.
+    pub fn from_csv(
+        path: impl AsRef<Path>,
+        x: impl Into<Column>,
+        y: impl Into<Column>,
+        z: Option<Column>,
+    ) -> io::Result<Self> {
+        Self::reader(x, y, z).csv(path)
+    }
+
+    /// Like `from_csv`, for tab-separated files
+    pub fn from_tsv(
+        path: impl AsRef<Path>,
+        x: impl Into<Column>,
+        y: impl Into<Column>,
+        z: Option<Column>,
+    ) -> io::Result<Self> {
+        Self::reader(x, y, z).tsv(path)
+    }
+
+    /// Like `from_csv`, for JSON holding an array of records or an object of column arrays
+    pub fn from_json(
+        path: impl AsRef<Path>,
+        x: impl Into<Column>,
+        y: impl Into<Column>,
+        z: Option<Column>,
+    ) -> io::Result<Self> {
+        Self::reader(x, y, z).json(path)
+    }
+
+    fn reader(x: impl Into<Column>, y: impl Into<Column>, z: Option<Column>) -> DataReader {
+        let reader = DataReader::new(x, y);
+        match z {
+            Some(z) => reader.z(z),
+            None => reader,
+        }
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, ColumnData::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// A column by name or position
+    pub fn column(&self, column: impl Into<Column>) -> Option<&ColumnData> {
+        self.index_of(&column.into())
+            .map(|index| &self.columns[index])
+    }
+
+    /// A column as numbers, for histograms, `ScatterPlot::color_by` and the
+    /// like. Categories give their level index and booleans 0 or 1.
+    pub fn values(&self, column: impl Into<Column>) -> Option<Vec<f32>> {
+        self.column(column).map(ColumnData::to_f32)
+    }
+
+    /// Count, mean, sd, quartiles and extremes of a column, or `None` for a
+    /// missing or categorical column; see `stats::describe`
+    pub fn describe(&self, column: impl Into<Column>) -> Option<Summary> {
+        describe::column(self, column)
+    }
+
+    /// Pairs of two columns as numbers
+    pub fn xy(&self, x: impl Into<Column>, y: impl Into<Column>) -> Option<Vec<Vec2>> {
+        let (x, y) = (self.values(x)?, self.values(y)?);
+        Some(x.into_iter().zip(y).map(|(x, y)| vec2(x, y)).collect())
+    }
+
+    /// The `x`, `y` and `z` columns as points; a missing column reads as 0
+    pub fn points(&self) -> Vec<Vec3> {
+        let axis = |name: &str| self.values(name).unwrap_or_else(|| vec![0.0; self.len()]);
+        let (x, y, z) = (axis("x"), axis("y"), axis("z"));
+        (0..self.len()).map(|i| vec3(x[i], y[i], z[i])).collect()
+    }
+
+    /// Get 2D projection (drop z coordinate)
+    pub fn as_2d(&self) -> Vec<Vec2> {
+        self.points().iter().map(|p| vec2(p.x, p.y)).collect()
+    }
+
+    pub fn row(&self, index: usize) -> Option<Row<'_>> {
+        (index < self.len()).then_some(Row { data: self, index })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Row<'_>> {
+        (0..self.len()).map(|index| Row { data: self, index })
+    }
+
+    /// The rows for which `keep` is true
+    pub fn filter<F>(&self, keep: F) -> Self
+    where
+        F: Fn(&Row) -> bool,
+    {
+        let indices: Vec<usize> = self
+            .rows()
+            .filter(|row| keep(row))
+            .map(|row| row.index)
+            .collect();
+        self.take(&indices)
+    }
+
+    /// Split the rows by the values of a column: categories in level order,
+    /// other types in order of first appearance. Each group keeps every column.
+    pub fn group_by(&self, column: impl Into<Column>) -> Vec<(String, Dataset)> {
+        let Some(key) = self.column(column) else {
+            return Vec::new();
+        };
+        let mut groups: Vec<(String, Vec<usize>)> = match key.levels() {
+            Some(levels) => levels.iter().map(|l| (l.clone(), Vec::new())).collect(),
+            None => Vec::new(),
+        };
+        for index in 0..self.len() {
+            let name = key.get(index).map(|v| v.to_string()).unwrap_or_default();
+            match groups.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, rows)) => rows.push(index),
+                None => groups.push((name, vec![index])),
+            }
+        }
+        groups
+            .into_iter()
+            .filter(|(_, rows)| !rows.is_empty())
+            .map(|(name, rows)| (name, self.take(&rows)))
+            .collect()
+    }
+
+    fn take(&self, indices: &[usize]) -> Self {
+        Self {
+            names: self.names.clone(),
+            columns: self.columns.iter().map(|c| c.take(indices)).collect(),
+        }
+    }
+
+    fn index_of(&self, column: &Column) -> Option<usize> {
+        match column {
+            Column::Index(index) => (*index < self.columns.len()).then_some(*index),
+            Column::Name(name) => self.names.iter().position(|n| n == name),
+        }
     }
 
     /// Generate sample GAM style data . Creates a wavy surface with noise
-    pub fn generate_gam_data() -> Self {
+    pub fn generate_gam_data(rng: &mut Rng) -> Self {
         let mut points = Vec::new();
         let n_points = 50;
 
         for _ in 0..n_points {
-            let x: f32 = gen_range(-2.5, 2.5);
-            let y: f32 = gen_range(-2.5, 2.5);
+            let x = rng.range(-2.5, 2.5);
+            let y = rng.range(-2.5, 2.5);
 
             let z_base = x.sin() + (y / 1.2).cos() + 0.5;
-            let z_noisy = z_base + gen_range(-0.3, 0.3);
+            let z_noisy = z_base + rng.range(-0.3, 0.3);
 
             points.push(vec3(x, y, z_noisy));
         }
-        Self { points }
+        Self::from_points(points)
     }
 
     /// Generate linear regression sample data
-    pub fn generate_linear_data(n_points: usize, slope: f32, intercept: f32, noise: f32) -> Self {
-        let points = (0..n_points)
+    pub fn generate_linear_data(
+        n_points: usize,
+        slope: f32,
+        intercept: f32,
+        noise: f32,
+        rng: &mut Rng,
+    ) -> Self {
+        let (x, y): (Vec<f32>, Vec<f32>) = (0..n_points)
             .map(|_| {
-                let x = gen_range(-3.0, 3.0);
-                let y = slope * x + intercept + gen_range(-noise, noise);
-                vec3(x, y, 0.0)
+                let x = rng.range(-3.0, 3.0);
+                (x, slope * x + intercept + rng.range(-noise, noise))
             })
-            .collect();
-        Self { points }
+            .unzip();
+        Self::new().with_column("x", x).with_column("y", y)
     }
 
-    /// Generate clustered sample data
-    pub fn generate_clusters(n_clusters: usize, points_per_cluster: usize, spread: f32) -> Self {
-        let mut points = Vec::new();
+    /// Generate clustered sample data, with the categorical column `cluster`
+    /// holding each point's cluster ("0", "1", ...)
+    pub fn generate_clusters(
+        n_clusters: usize,
+        points_per_cluster: usize,
+        spread: f32,
+        rng: &mut Rng,
+    ) -> Self {
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        let mut cluster = Vec::new();
 
         for i in 0..n_clusters {
             let angle = (i as f32 / n_clusters as f32) * std::f32::consts::TAU;
             let center = vec2(angle.cos() * 2.0, angle.sin() * 2.0);
 
             for _ in 0..points_per_cluster {
-                let offset = vec2(gen_range(-spread, spread), gen_range(-spread, spread));
+                let offset = vec2(rng.range(-spread, spread), rng.range(-spread, spread));
                 let p = center + offset;
-                points.push(vec3(p.x, p.y, i as f32)); // z stores cluster ID
+                x.push(p.x);
+                y.push(p.y);
+                cluster.push(i.to_string());
             }
         }
-        Self { points }
+        Self::new()
+            .with_column("x", x)
+            .with_column("y", y)
+            .with_column("cluster", cluster)
     }
 
-    /// Get 2D projection (drop z coordinate)
-    pub fn as_2d(&self) -> Vec<Vec2> {
-        self.points.iter().map(|p| vec2(p.x, p.y)).collect()
+    /// `n` normal draws in column `x`
+    pub fn generate_normal(n: usize, mean: f64, sd: f64, rng: &mut Rng) -> Self {
+        Self::sample(n, rng, |rng| mean + sd * rng.normal())
     }
 
-    pub fn len(&self) -> usize {
-        self.points.len()
+    /// `n` draws from Student's t with `df` degrees of freedom in column `x`
+    pub fn generate_t(n: usize, df: f64, rng: &mut Rng) -> Self {
+        Self::sample(n, rng, |rng| rng.student_t(df))
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.points.is_empty()
+    /// `n` exponential draws with the given rate in column `x`
+    pub fn generate_exponential(n: usize, rate: f64, rng: &mut Rng) -> Self {
+        Self::sample(n, rng, |rng| rng.exponential(rate))
+    }
+
+    /// `n` Poisson counts with mean `lambda` in the integer column `x`
+    pub fn generate_poisson(n: usize, lambda: f64, rng: &mut Rng) -> Self {
+        let counts: Vec<i64> = (0..n).map(|_| rng.poisson(lambda) as i64).collect();
+        Self::new().with_column("x", counts)
+    }
+
+    /// `n` binomial counts of successes in `trials` trials in the integer column `x`
+    pub fn generate_binomial(n: usize, trials: u64, p: f64, rng: &mut Rng) -> Self {
+        let counts: Vec<i64> = (0..n).map(|_| rng.binomial(trials, p) as i64).collect();
+        Self::new().with_column("x", counts)
+    }
+
+    /// `n` draws from a multivariate normal, one column per dimension named
+    /// `x`, `y`, `z`, then `x4`, `x5`... `None` if `covariance` is not a
+    /// symmetric positive definite matrix matching `mean`.
+    pub fn generate_multivariate_normal(
+        n: usize,
+        mean: &[f64],
+        covariance: &[Vec<f64>],
+        rng: &mut Rng,
+    ) -> Option<Self> {
+        let factor = covariance_factor(mean.len(), covariance)?;
+        let draws: Vec<Vec<f64>> = (0..n)
+            .map(|_| rng.correlated_normal(mean, &factor))
+            .collect();
+        let data = (0..mean.len()).fold(Self::new(), |data, dim| {
+            let name = match dim {
+                0..3 => ["x", "y", "z"][dim].to_string(),
+                _ => format!("x{}", dim + 1),
+            };
+            data.with_column(name, draws.iter().map(|d| d[dim]).collect::<Vec<f64>>())
+        });
+        Some(data)
+    }
+
+    /// Linear data whose noise fans out: `y = intercept + slope·x + ε` with
+    /// `x` uniform on [0, 6] and ε normal with sd `base_sd + sd_growth·x`
+    pub fn generate_heteroscedastic(
+        n: usize,
+        slope: f32,
+        intercept: f32,
+        base_sd: f32,
+        sd_growth: f32,
+        rng: &mut Rng,
+    ) -> Self {
+        let (x, y): (Vec<f32>, Vec<f32>) = (0..n)
+            .map(|_| {
+                let x = rng.range(0.0, 6.0);
+                let sd = base_sd + sd_growth * x;
+                (x, intercept + slope * x + sd * rng.normal() as f32)
+            })
+            .unzip();
+        Self::new().with_column("x", x).with_column("y", y)
+    }
+
+    /// Binary outcomes for logistic regression: `x` uniform on [-3, 3] and
+    /// the boolean `y` true with probability `σ(intercept + slope·x)`
+    pub fn generate_logistic_data(n: usize, intercept: f32, slope: f32, rng: &mut Rng) -> Self {
+        let (x, y): (Vec<f32>, Vec<bool>) = (0..n)
+            .map(|_| {
+                let x = rng.range(-3.0, 3.0);
+                let p = 1.0 / (1.0 + (-(intercept + slope * x)).exp());
+                (x, rng.uniform() < p as f64)
+            })
+            .unzip();
+        Self::new().with_column("x", x).with_column("y", y)
+    }
+
+    fn sample(n: usize, rng: &mut Rng, draw: impl Fn(&mut Rng) -> f64) -> Self {
+        let values: Vec<f64> = (0..n).map(|_| draw(rng)).collect();
+        Self::new().with_column("x", values)
     }
 }
 
@@ -80,3 +402,138 @@ impl Default for Dataset {
         Self::new()
     }
 }
+
+/// One row of a `Dataset`, as passed to `Dataset::filter`
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    data: &'a Dataset,
+    index: usize,
+}
+
+impl<'a> Row<'a> {
+    /// Position of the row in its dataset
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get(&self, column: impl Into<Column>) -> Option<Value<'a>> {
+        self.data.column(column)?.get(self.index)
+    }
+
+    /// The value as a number, or `None` for a category or a missing float
+    pub fn number(&self, column: impl Into<Column>) -> Option<f64> {
+        self.get(column)?.as_f64().filter(|v| !v.is_nan())
+    }
+
+    /// The level of a categorical value
+    pub fn category(&self, column: impl Into<Column>) -> Option<&'a str> {
+        match self.get(column)? {
+            Value::Category(level) => Some(level),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_columns_filter_and_group() {
+        let data = Dataset::new()
+            .with_column("mass", vec![3.1f64, 4.2, f64::NAN, 5.0])
+            .with_column("age", vec![1i64, 3, 2, 4])
+            .with_column("tagged", vec![true, false, true, true])
+            .with_column("species", vec!["gentoo", "adelie", "gentoo", "chinstrap"]);
+        assert_eq!(data.len(), 4);
+        assert_eq!(data.values("species"), Some(vec![0.0, 1.0, 0.0, 2.0]));
+        assert_eq!(data.values(2), Some(vec![1.0, 0.0, 1.0, 1.0]));
+        assert_eq!(data.row(1).unwrap().category("species"), Some("adelie"));
+        assert_eq!(data.row(2).unwrap().number("mass"), None);
+
+        let heavy = data.filter(|row| row.number("mass").is_some_and(|m| m > 4.0));
+        assert_eq!(heavy.values("age"), Some(vec![3.0, 4.0]));
+        // Filtering keeps every level, so codes stay comparable across subsets
+        assert_eq!(heavy.values("species"), Some(vec![1.0, 2.0]));
+
+        let groups = data.group_by("species");
+        let names: Vec<&str> = groups.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["gentoo", "adelie", "chinstrap"]);
+        assert_eq!(groups[0].1.values("age"), Some(vec![1.0, 2.0]));
+        let by_tag = data.group_by("tagged");
+        assert_eq!(by_tag[0].0, "true");
+        assert_eq!(by_tag[0].1.len(), 3);
+
+        let clusters = Dataset::generate_clusters(3, 5, 0.1, &mut Rng::new(1));
+        assert_eq!(
+            clusters.column("cluster").unwrap().levels().unwrap().len(),
+            3
+        );
+        assert_eq!(clusters.points()[14].z, 0.0);
+    }
+
+    #[test]
+    fn test_seeded_generators_repeat_and_match_moments() {
+        let data = |seed| Dataset::generate_linear_data(20, 2.0, 1.0, 0.5, &mut Rng::new(seed));
+        assert_eq!(data(3), data(3));
+        assert_ne!(data(3), data(4));
+
+        let mut rng = Rng::new(11);
+        let n = 20_000;
+        let moments = |data: Dataset| {
+            let values = data.values("x").unwrap();
+            let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n as f64;
+            let var = values
+                .iter()
+                .map(|&v| (v as f64 - mean).powi(2))
+                .sum::<f64>()
+                / n as f64;
+            (mean, var)
+        };
+        let close = |(mean, var): (f64, f64), (m, v): (f64, f64)| {
+            assert!(
+                (mean - m).abs() < 0.05 * v.sqrt().max(1.0),
+                "mean {} vs {}",
+                mean,
+                m
+            );
+            assert!((var / v - 1.0).abs() < 0.06, "variance {} vs {}", var, v);
+        };
+        close(
+            moments(Dataset::generate_normal(n, 3.0, 2.0, &mut rng)),
+            (3.0, 4.0),
+        );
+        close(moments(Dataset::generate_t(n, 6.0, &mut rng)), (0.0, 1.5));
+        close(
+            moments(Dataset::generate_exponential(n, 0.5, &mut rng)),
+            (2.0, 4.0),
+        );
+        close(
+            moments(Dataset::generate_poisson(n, 4.0, &mut rng)),
+            (4.0, 4.0),
+        );
+        close(
+            moments(Dataset::generate_poisson(n, 90.0, &mut rng)),
+            (90.0, 90.0),
+        );
+        close(
+            moments(Dataset::generate_binomial(n, 200, 0.3, &mut rng)),
+            (60.0, 42.0),
+        );
+
+        let covariance = vec![vec![1.0, 0.8], vec![0.8, 2.0]];
+        let mvn =
+            Dataset::generate_multivariate_normal(n, &[1.0, -1.0], &covariance, &mut rng).unwrap();
+        let cross = mvn
+            .as_2d()
+            .iter()
+            .map(|p| (p.x as f64 - 1.0) * (p.y as f64 + 1.0))
+            .sum::<f64>()
+            / n as f64;
+        assert!((cross - 0.8).abs() < 0.05, "covariance {}", cross);
+        let singular = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+        assert!(
+            Dataset::generate_multivariate_normal(5, &[0.0, 0.0], &singular, &mut rng).is_none()
+        );
+    }
+}
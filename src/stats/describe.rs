@@ -0,0 +1,516 @@
+//! Descriptive statistics of samples and `Dataset` columns.
+//!
+//! Non-finite values (including missing float cells, stored as NaN) are left
+//! out; paired statistics drop a pair if either value is. A statistic with
+//! too few values to be defined is NaN. `Summary` and `Matrix` print with
+//! two decimals by default, or as many as a format precision asks for, so
+//! they can go straight into a `Text`:
+//!
+//! ```ignore
+//! let summary = data.describe("mass").unwrap();
+//! scene.add(Text::new(format!("{:.1}", summary)).at(vec2(300.0, 0.0)));
+//! let r = describe::correlation(&x, &y, Correlation::Spearman);
+//! scene.add(Text::new(format!("ρ = {:.2}", r)));
+//! ```
+
+use crate::data::{Column, ColumnData};
+use crate::state::Dataset;
+use std::fmt;
+
+/// How a quantile is read between order statistics, as in Hyndman and Fan
+/// (1996) and the matching `method`s of NumPy's `quantile`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuantileMethod {
+    /// Type 1: the smallest value whose empirical CDF reaches q
+    InvertedCdf,
+    /// Type 2: like `InvertedCdf`, averaging at discontinuities
+    AveragedInvertedCdf,
+    /// Type 4: linear interpolation of the empirical CDF
+    InterpolatedInvertedCdf,
+    /// Type 5: piecewise linear with knots midway through the steps
+    Hazen,
+    /// Type 6: the k-th value at q = k / (n + 1) (Minitab, SPSS)
+    Weibull,
+    /// Type 7: the k-th value at q = (k - 1) / (n - 1) (R, NumPy and pandas default)
+    #[default]
+    Linear,
+    /// Type 8: approximately median-unbiased whatever the distribution
+    MedianUnbiased,
+    /// Type 9: approximately unbiased for normal samples
+    NormalUnbiased,
+}
+
+/// Which correlation coefficient to compute
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Correlation {
+    /// Linear association
+    #[default]
+    Pearson,
+    /// Pearson correlation of ranks (ties get their average rank)
+    Spearman,
+    /// Kendall's τ-b, from concordant and discordant pairs with a tie correction
+    Kendall,
+}
+
+pub fn mean(values: &[f32]) -> f32 {
+    mean_f64(&finite(values)) as f32
+}
+
+/// Mean weighted by `weights`, paired by position
+pub fn weighted_mean(values: &[f32], weights: &[f32]) -> f32 {
+    let (total, weight) = pairs(values, weights)
+        .iter()
+        .fold((0.0, 0.0), |(total, weight), (v, w)| {
+            (total + v * w, weight + w)
+        });
+    (total / weight) as f32
+}
+
+pub fn median(values: &[f32]) -> f32 {
+    quantile(values, 0.5, QuantileMethod::Linear)
+}
+
+/// Sample variance, with an n - 1 denominator
+pub fn variance(values: &[f32]) -> f32 {
+    variance_f64(&finite(values)) as f32
+}
+
+/// Sample standard deviation
+pub fn sd(values: &[f32]) -> f32 {
+    variance(values).sqrt()
+}
+
+pub fn min(values: &[f32]) -> f32 {
+    finite(values).into_iter().fold(f64::NAN, f64::min) as f32
+}
+
+pub fn max(values: &[f32]) -> f32 {
+    finite(values).into_iter().fold(f64::NAN, f64::max) as f32
+}
+
+/// The `q` quantile (0 to 1) by `method`
+pub fn quantile(values: &[f32], q: f32, method: QuantileMethod) -> f32 {
+    quantiles(values, &[q], method)[0]
+}
+
+/// Several quantiles, sorting the values once
+pub fn quantiles(values: &[f32], qs: &[f32], method: QuantileMethod) -> Vec<f32> {
+    let sorted = sorted(values);
+    qs.iter()
+        .map(|&q| sorted_quantile(&sorted, q as f64, method) as f32)
+        .collect()
+}
+
+/// Interquartile range, Q3 - Q1
+pub fn iqr(values: &[f32], method: QuantileMethod) -> f32 {
+    let quartiles = quantiles(values, &[0.25, 0.75], method);
+    quartiles[1] - quartiles[0]
+}
+
+/// Adjusted Fisher–Pearson skewness G1, as reported by pandas, SAS and Excel.
+/// Needs at least 3 values.
+pub fn skewness(values: &[f32]) -> f32 {
+    let values = finite(values);
+    let n = values.len() as f64;
+    let (m2, m3) = (central_moment(&values, 2), central_moment(&values, 3));
+    let g1 = m3 / m2.powf(1.5);
+    let adjusted = (n * (n - 1.0)).sqrt() / (n - 2.0) * g1;
+    if n < 3.0 { f32::NAN } else { adjusted as f32 }
+}
+
+/// Bias-corrected excess kurtosis G2 (0 for a normal distribution), as
+/// reported by pandas, SAS and Excel. Needs at least 4 values.
+pub fn kurtosis(values: &[f32]) -> f32 {
+    let values = finite(values);
+    let n = values.len() as f64;
+    let (m2, m4) = (central_moment(&values, 2), central_moment(&values, 4));
+    let g2 = m4 / (m2 * m2) - 3.0;
+    let adjusted = (n - 1.0) / ((n - 2.0) * (n - 3.0)) * ((n + 1.0) * g2 + 6.0);
+    if n < 4.0 { f32::NAN } else { adjusted as f32 }
+}
+
+/// Sample covariance of paired values, with an n - 1 denominator
+pub fn covariance(x: &[f32], y: &[f32]) -> f32 {
+    let (x, y): (Vec<f64>, Vec<f64>) = pairs(x, y).into_iter().unzip();
+    covariance_f64(&x, &y) as f32
+}
+
+pub fn correlation(x: &[f32], y: &[f32], method: Correlation) -> f32 {
+    let (x, y): (Vec<f64>, Vec<f64>) = pairs(x, y).into_iter().unzip();
+    let r = match method {
+        Correlation::Pearson => pearson(&x, &y),
+        Correlation::Spearman => pearson(&ranks(&x), &ranks(&y)),
+        Correlation::Kendall => kendall(&x, &y),
+    };
+    r as f32
+}
+
+/// Common summary statistics of one sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// Number of finite values
+    pub n: usize,
+    pub mean: f32,
+    pub sd: f32,
+    pub min: f32,
+    /// First quartile (`QuantileMethod::Linear`)
+    pub q1: f32,
+    pub median: f32,
+    /// Third quartile (`QuantileMethod::Linear`)
+    pub q3: f32,
+    pub max: f32,
+    pub skewness: f32,
+    pub kurtosis: f32,
+}
+
+impl Summary {
+    pub fn of(values: &[f32]) -> Self {
+        let n = finite(values).len();
+        let quartiles = quantiles(values, &[0.25, 0.5, 0.75], QuantileMethod::Linear);
+        Self {
+            n,
+            mean: mean(values),
+            sd: sd(values),
+            min: min(values),
+            q1: quartiles[0],
+            median: quartiles[1],
+            q3: quartiles[2],
+            max: max(values),
+            skewness: skewness(values),
+            kurtosis: kurtosis(values),
+        }
+    }
+
+    /// Interquartile range
+    pub fn iqr(&self) -> f32 {
+        self.q3 - self.q1
+    }
+}
+
+/// One statistic per line: `n = 50`, `mean = 3.14`, and so on
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = f.precision().unwrap_or(2);
+        writeln!(f, "n = {}", self.n)?;
+        let lines = [
+            ("mean", self.mean),
+            ("sd", self.sd),
+            ("min", self.min),
+            ("Q1", self.q1),
+            ("median", self.median),
+            ("Q3", self.q3),
+            ("max", self.max),
+        ];
+        for (i, (name, value)) in lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{} = {:.*}", name, decimals, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Summary of a dataset column, or `None` if there is no such column or it
+/// is categorical (its level codes are not quantities). Booleans count as 0
+/// and 1, so their mean is the proportion of `true`.
+pub fn column(data: &Dataset, column: impl Into<Column>) -> Option<Summary> {
+    numeric(data, column).map(|values| Summary::of(&values))
+}
+
+/// A square matrix of pairwise statistics between named columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    pub names: Vec<String>,
+    /// Row-major values; `values[i][j]` pairs `names[i]` with `names[j]`
+    pub values: Vec<Vec<f32>>,
+}
+
+impl Matrix {
+    /// The entry for two columns by name
+    pub fn get(&self, row: &str, column: &str) -> Option<f32> {
+        let index = |name: &str| self.names.iter().position(|n| n == name);
+        Some(self.values[index(row)?][index(column)?])
+    }
+}
+
+/// A header row of names, then one row per column
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = f.precision().unwrap_or(2);
+        let cells: Vec<Vec<String>> = self
+            .values
+            .iter()
+            .map(|row| row.iter().map(|v| format!("{:.*}", decimals, v)).collect())
+            .collect();
+        let label_width = self
+            .names
+            .iter()
+            .map(|n| n.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = self
+            .names
+            .iter()
+            .map(|n| n.chars().count())
+            .chain(cells.iter().flatten().map(String::len))
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:label_width$}", "")?;
+        for name in &self.names {
+            write!(f, "  {:>width$}", name)?;
+        }
+        for (name, row) in self.names.iter().zip(&cells) {
+            write!(f, "\n{:label_width$}", name)?;
+            for cell in row {
+                write!(f, "  {:>width$}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Covariances between each pair of `columns`, or `None` if one is missing
+/// or categorical
+pub fn covariance_matrix<C: Into<Column>>(
+    data: &Dataset,
+    columns: impl IntoIterator<Item = C>,
+) -> Option<Matrix> {
+    pairwise(data, columns, covariance)
+}
+
+/// Correlations between each pair of `columns`, or `None` if one is missing
+/// or categorical
+pub fn correlation_matrix<C: Into<Column>>(
+    data: &Dataset,
+    columns: impl IntoIterator<Item = C>,
+    method: Correlation,
+) -> Option<Matrix> {
+    pairwise(data, columns, |x, y| correlation(x, y, method))
+}
+
+fn pairwise<C, F>(data: &Dataset, columns: impl IntoIterator<Item = C>, stat: F) -> Option<Matrix>
+where
+    C: Into<Column>,
+    F: Fn(&[f32], &[f32]) -> f32,
+{
+    let mut names = Vec::new();
+    let mut samples = Vec::new();
+    for column in columns {
+        let column = column.into();
+        samples.push(numeric(data, column.clone())?);
+        names.push(match column {
+            Column::Name(name) => name,
+            Column::Index(index) => data.column_names()[index].clone(),
+        });
+    }
+    let values = samples
+        .iter()
+        .map(|x| samples.iter().map(|y| stat(x, y)).collect())
+        .collect();
+    Some(Matrix { names, values })
+}
+
+/// A column's values as numbers, unless it is missing or categorical
+fn numeric(data: &Dataset, column: impl Into<Column>) -> Option<Vec<f32>> {
+    match data.column(column)? {
+        ColumnData::Categorical { .. } => None,
+        values => Some(values.to_f32()),
+    }
+}
+
+fn finite(values: &[f32]) -> Vec<f64> {
+    values
+        .iter()
+        .filter(|v| v.is_finite())
+        .map(|&v| v as f64)
+        .collect()
+}
+
+fn sorted(values: &[f32]) -> Vec<f64> {
+    let mut sorted = finite(values);
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+fn pairs(x: &[f32], y: &[f32]) -> Vec<(f64, f64)> {
+    x.iter()
+        .zip(y)
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .map(|(&x, &y)| (x as f64, y as f64))
+        .collect()
+}
+
+fn mean_f64(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn variance_f64(values: &[f64]) -> f64 {
+    covariance_f64(values, values)
+}
+
+fn covariance_f64(x: &[f64], y: &[f64]) -> f64 {
+    let (mx, my) = (mean_f64(x), mean_f64(y));
+    let sum: f64 = x.iter().zip(y).map(|(x, y)| (x - mx) * (y - my)).sum();
+    sum / (x.len() as f64 - 1.0)
+}
+
+fn central_moment(values: &[f64], power: i32) -> f64 {
+    let m = mean_f64(values);
+    values.iter().map(|v| (v - m).powi(power)).sum::<f64>() / values.len() as f64
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    covariance_f64(x, y) / (variance_f64(x) * variance_f64(y)).sqrt()
+}
+
+/// Ranks from 1, with tied values sharing their average rank
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let tied = order[start..]
+            .iter()
+            .take_while(|&&i| values[i] == values[order[start]])
+            .count();
+        let rank = start as f64 + (tied as f64 + 1.0) / 2.0;
+        for &i in &order[start..start + tied] {
+            ranks[i] = rank;
+        }
+        start += tied;
+    }
+    ranks
+}
+
+fn kendall(x: &[f64], y: &[f64]) -> f64 {
+    let (mut concordant, mut discordant, mut tied_x, mut tied_y) = (0.0, 0.0, 0.0, 0.0f64);
+    for i in 0..x.len() {
+        for j in i + 1..x.len() {
+            let (dx, dy) = (x[i] - x[j], y[i] - y[j]);
+            match (dx == 0.0, dy == 0.0) {
+                (true, true) => {}
+                (true, false) => tied_x += 1.0,
+                (false, true) => tied_y += 1.0,
+                _ if dx * dy > 0.0 => concordant += 1.0,
+                _ => discordant += 1.0,
+            }
+        }
+    }
+    let untied = concordant + discordant;
+    (concordant - discordant) / ((untied + tied_x) * (untied + tied_y)).sqrt()
+}
+
+fn sorted_quantile(sorted: &[f64], q: f64, method: QuantileMethod) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let n = sorted.len() as f64;
+    let q = q.clamp(0.0, 1.0);
+    // The j-th order statistic counting from 1, held at the ends
+    let at = |j: f64| sorted[(j.max(1.0).min(n) as usize) - 1];
+    // The step methods jump where n·q is whole; q = 0.1 from an f32 must still land there
+    let step = {
+        let h = n * q;
+        if (h - h.round()).abs() < n * f32::EPSILON as f64 {
+            h.round()
+        } else {
+            h
+        }
+    };
+    let (alpha, beta) = match method {
+        QuantileMethod::InvertedCdf => return at(step.ceil()),
+        QuantileMethod::AveragedInvertedCdf => {
+            let h = step;
+            return if h.fract() == 0.0 {
+                (at(h) + at(h + 1.0)) / 2.0
+            } else {
+                at(h.ceil())
+            };
+        }
+        QuantileMethod::InterpolatedInvertedCdf => (0.0, 1.0),
+        QuantileMethod::Hazen => (0.5, 0.5),
+        QuantileMethod::Weibull => (0.0, 0.0),
+        QuantileMethod::Linear => (1.0, 1.0),
+        QuantileMethod::MedianUnbiased => (1.0 / 3.0, 1.0 / 3.0),
+        QuantileMethod::NormalUnbiased => (3.0 / 8.0, 3.0 / 8.0),
+    };
+    let h = (n + 1.0 - alpha - beta) * q + alpha;
+    let j = h.floor();
+    at(j) + (at(j + 1.0) - at(j)) * (h - j)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile_methods_match_hyndman_fan() {
+        let values: Vec<f32> = (1..=10).map(|v| v as f32).collect();
+        let expected = [
+            (QuantileMethod::InvertedCdf, 1.0),
+            (QuantileMethod::AveragedInvertedCdf, 1.5),
+            (QuantileMethod::InterpolatedInvertedCdf, 1.0),
+            (QuantileMethod::Hazen, 1.5),
+            (QuantileMethod::Weibull, 1.1),
+            (QuantileMethod::Linear, 1.9),
+            (QuantileMethod::MedianUnbiased, 1.366_667),
+            (QuantileMethod::NormalUnbiased, 1.4),
+        ];
+        for (method, q10) in expected {
+            let found = quantile(&values, 0.1, method);
+            assert!((found - q10).abs() < 1e-5, "{:?}: {}", method, found);
+            assert_eq!(quantile(&values, 0.0, method), 1.0);
+            assert_eq!(quantile(&values, 1.0, method), 10.0);
+        }
+        assert_eq!(iqr(&values, QuantileMethod::Linear), 4.5);
+        assert_eq!(median(&[3.0, f32::NAN, 1.0, 2.0]), 2.0);
+    }
+
+    #[test]
+    fn test_moments_correlations_and_matrices() {
+        let values = [1.0, 2.0, 3.0, 10.0];
+        assert_eq!(mean(&values), 4.0);
+        assert!((variance(&values) - 50.0 / 3.0).abs() < 1e-5);
+        assert!((skewness(&values) - 1.763_633).abs() < 1e-4);
+        assert!((kurtosis(&values) - 3.228).abs() < 1e-4);
+        assert_eq!(weighted_mean(&values, &[1.0, 1.0, 2.0, 0.0]), 2.25);
+
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [2.0, 1.0, 4.0, 3.0, 5.0];
+        assert!((correlation(&x, &y, Correlation::Pearson) - 0.8).abs() < 1e-6);
+        assert!((correlation(&x, &y, Correlation::Spearman) - 0.8).abs() < 1e-6);
+        assert!((correlation(&x, &y, Correlation::Kendall) - 0.6).abs() < 1e-6);
+        // τ-b with ties in both variables
+        let tau = correlation(
+            &[1.0, 1.0, 2.0, 3.0],
+            &[1.0, 2.0, 2.0, 3.0],
+            Correlation::Kendall,
+        );
+        assert!((tau - 0.8).abs() < 1e-6, "{}", tau);
+
+        let data = Dataset::new()
+            .with_column("x", x.to_vec())
+            .with_column("y", y.to_vec());
+        let matrix = covariance_matrix(&data, ["x", "y"]).unwrap();
+        assert_eq!(matrix.get("x", "y"), Some(2.0));
+        assert_eq!(
+            matrix.to_string(),
+            "      x     y\nx  2.50  2.00\ny  2.00  2.50"
+        );
+        assert!(covariance_matrix(&data, ["x", "z"]).is_none());
+        // Level codes of a categorical column are not numbers to summarize
+        let data = data.with_column(
+            "species",
+            ColumnData::categorical(&["a", "b", "a", "c", "b"]),
+        );
+        assert!(column(&data, "species").is_none());
+        assert!(correlation_matrix(&data, ["x", "species"], Correlation::Pearson).is_none());
+
+        let summary = column(&data, "y").unwrap();
+        assert_eq!((summary.n, summary.q1, summary.q3), (5, 2.0, 4.0));
+        assert!(format!("{:.1}", summary).starts_with("n = 5\nmean = 3.0\nsd = 1.6\n"));
+    }
+}
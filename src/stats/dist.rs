@@ -0,0 +1,545 @@
+//! Probability distributions with densities, CDFs, quantiles and sampling,
+//! and helpers that draw them on an `Axes2D`.
+//!
+//! ```ignore
+//! let axes = Axes2D::new().x_range(-4.0, 4.0).y_range(0.0, 0.45);
+//! let t = StudentT::new(5.0);
+//! let density = scene.add(t.density_curve(&axes, 200));
+//! // Two-sided 5% rejection region
+//! let cut = t.quantile(0.975);
+//! let left = scene.add(t.area(&axes, f64::NEG_INFINITY, -cut, 200).fill(RED));
+//! let right = scene.add(t.area(&axes, cut, f64::INFINITY, 200).fill(RED));
+//! // Slide the right tail in to the observed statistic
+//! scene.play(SetBounds::new(right, 1.2, 4.0).duration(2.0));
+//!
+//! let binomial = Binomial::new(20, 0.3);
+//! let pmf = scene.add(binomial.stems(&axes).highlight(10.0, 20.0, RED));
+//! ```
+
+use super::rng::Rng;
+use super::special::{
+    beta_inc, gamma_inc, gamma_inc_upper, invert_monotone, ln_gamma, normal_cdf, student_t_cdf,
+};
+use crate::mobject::{AreaUnder, Axes2D, Curve, StemPlot};
+use macroquad::prelude::*;
+
+/// A distribution over the real line, or part of it
+pub trait Continuous {
+    /// Probability density at `x`
+    fn pdf(&self, x: f64) -> f64;
+
+    /// Probability of a value at most `x`
+    fn cdf(&self, x: f64) -> f64;
+
+    /// One random draw
+    fn sample(&self, rng: &mut Rng) -> f64;
+
+    /// The smallest and largest possible values
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// The value below which a fraction `p` of the distribution lies
+    fn quantile(&self, p: f64) -> f64 {
+        let (low, high) = self.support();
+        invert_monotone(|x| self.cdf(x), p).clamp(low, high)
+    }
+
+    /// Probability of a value above `x`
+    fn sf(&self, x: f64) -> f64 {
+        1.0 - self.cdf(x)
+    }
+
+    /// `n` random draws
+    fn samples(&self, n: usize, rng: &mut Rng) -> Vec<f32> {
+        (0..n).map(|_| self.sample(rng) as f32).collect()
+    }
+
+    /// The density over the x range of `axes`, bound to them
+    fn density_curve(&self, axes: &Axes2D, samples: usize) -> Curve {
+        let (low, high) = axes.frame().x_range;
+        Curve::from_function(|x| self.pdf(x as f64) as f32, low, high, samples.max(2)).on_axes(axes)
+    }
+
+    /// The CDF over the x range of `axes`, bound to them
+    fn cdf_curve(&self, axes: &Axes2D, samples: usize) -> Curve {
+        let (low, high) = axes.frame().x_range;
+        Curve::from_function(|x| self.cdf(x as f64) as f32, low, high, samples.max(2)).on_axes(axes)
+    }
+
+    /// The region under the density between `a` and `b`, whose area is the
+    /// probability `cdf(b) - cdf(a)`. Infinite bounds stop at the edges of
+    /// `axes`, so `area(&axes, x, f64::INFINITY, ..)` shades an upper tail.
+    /// The density is sampled across the whole x range of `axes`, so
+    /// `SetBounds` can sweep the region anywhere on them.
+    fn area(&self, axes: &Axes2D, a: f64, b: f64, samples: usize) -> AreaUnder {
+        let (low, high) = axes.frame().x_range;
+        let density =
+            Curve::from_function(|x| self.pdf(x as f64) as f32, low, high, samples.max(2));
+        AreaUnder::new(&density)
+            .bounds(a as f32, b as f32)
+            .on_axes(axes)
+    }
+}
+
+/// A distribution over whole numbers from 0
+pub trait Discrete {
+    /// Probability of exactly `k`
+    fn pmf(&self, k: i64) -> f64;
+
+    /// Probability of a value at most `k`
+    fn cdf(&self, k: i64) -> f64;
+
+    /// One random draw
+    fn sample(&self, rng: &mut Rng) -> i64;
+
+    /// The largest possible value
+    fn max_value(&self) -> i64 {
+        i64::MAX
+    }
+
+    /// The smallest `k` with `cdf(k) >= p`
+    fn quantile(&self, p: f64) -> i64 {
+        if p >= 1.0 {
+            return self.max_value();
+        }
+        let mut high = 1;
+        while high < self.max_value() && self.cdf(high) < p {
+            high = high.saturating_mul(2).min(self.max_value());
+        }
+        let mut low = -1;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.cdf(mid) >= p {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        high
+    }
+
+    /// Probability of a value above `k`
+    fn sf(&self, k: i64) -> f64 {
+        1.0 - self.cdf(k)
+    }
+
+    /// `n` random draws
+    fn samples(&self, n: usize, rng: &mut Rng) -> Vec<f32> {
+        (0..n).map(|_| self.sample(rng) as f32).collect()
+    }
+
+    /// One stem per whole number in the x range of `axes`, bound to them.
+    /// `.bars(1.0)` turns the stems into touching bars.
+    fn stems(&self, axes: &Axes2D) -> StemPlot {
+        let (low, high) = axes.frame().x_range;
+        let (low, high) = (low.ceil().max(0.0) as i64, high.floor() as i64);
+        let points = (low..=high.min(self.max_value()))
+            .map(|k| vec2(k as f32, self.pmf(k) as f32))
+            .collect();
+        StemPlot::from_points(points).on_axes(axes)
+    }
+}
+
+/// Normal distribution with the given mean and standard deviation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, sd: f64) -> Self {
+        Self { mean, sd }
+    }
+
+    /// Mean 0 and standard deviation 1
+    pub fn standard() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+impl Continuous for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.sd;
+        (-0.5 * z * z).exp() / (self.sd * (2.0 * std::f64::consts::PI).sqrt())
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        normal_cdf((x - self.mean) / self.sd)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        normal_cdf((self.mean - x) / self.sd)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        self.mean + self.sd * rng.normal()
+    }
+}
+
+/// Student's t distribution with `df` degrees of freedom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentT {
+    pub df: f64,
+}
+
+impl StudentT {
+    pub fn new(df: f64) -> Self {
+        Self { df }
+    }
+}
+
+impl Continuous for StudentT {
+    fn pdf(&self, x: f64) -> f64 {
+        let v = self.df;
+        let log = ln_gamma((v + 1.0) / 2.0)
+            - ln_gamma(v / 2.0)
+            - 0.5 * (v * std::f64::consts::PI).ln()
+            - (v + 1.0) / 2.0 * (1.0 + x * x / v).ln();
+        log.exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        student_t_cdf(x, self.df)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        student_t_cdf(-x, self.df)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        rng.student_t(self.df)
+    }
+}
+
+/// Chi-squared distribution with `df` degrees of freedom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    pub df: f64,
+}
+
+impl ChiSquared {
+    pub fn new(df: f64) -> Self {
+        Self { df }
+    }
+}
+
+impl Continuous for ChiSquared {
+    fn pdf(&self, x: f64) -> f64 {
+        Gamma::new(self.df / 2.0, 2.0).pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        gamma_inc(self.df / 2.0, x.max(0.0) / 2.0)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        gamma_inc_upper(self.df / 2.0, x.max(0.0) / 2.0)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        rng.chi_squared(self.df)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+/// Fisher's F distribution, the ratio of two scaled chi-squared variables
+/// with `df1` and `df2` degrees of freedom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherF {
+    pub df1: f64,
+    pub df2: f64,
+}
+
+impl FisherF {
+    pub fn new(df1: f64, df2: f64) -> Self {
+        Self { df1, df2 }
+    }
+}
+
+impl Continuous for FisherF {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let (d1, d2) = (self.df1, self.df2);
+        let log = 0.5 * (d1 * (d1 * x).ln() + d2 * d2.ln() - (d1 + d2) * (d1 * x + d2).ln())
+            - x.ln()
+            - (ln_gamma(d1 / 2.0) + ln_gamma(d2 / 2.0) - ln_gamma((d1 + d2) / 2.0));
+        log.exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        let (d1, d2) = (self.df1, self.df2);
+        beta_inc(d1 / 2.0, d2 / 2.0, d1 * x / (d1 * x + d2))
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        let (d1, d2) = (self.df1, self.df2);
+        if x <= 0.0 {
+            return 1.0;
+        }
+        beta_inc(d2 / 2.0, d1 / 2.0, d2 / (d1 * x + d2))
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        rng.chi_squared_mean(self.df1) / rng.chi_squared_mean(self.df2)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+/// Beta distribution on [0, 1] with shape parameters `a` and `b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beta {
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Beta {
+    pub fn new(a: f64, b: f64) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Continuous for Beta {
+    fn pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return 0.0;
+        }
+        let (a, b) = (self.a, self.b);
+        let log = xlogy(a - 1.0, x) + xlogy(b - 1.0, 1.0 - x) + ln_gamma(a + b)
+            - ln_gamma(a)
+            - ln_gamma(b);
+        log.exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        beta_inc(self.a, self.b, x)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        rng.beta(self.a, self.b)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+/// Gamma distribution with the given shape and scale (mean `shape · scale`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamma {
+    pub shape: f64,
+    pub scale: f64,
+}
+
+impl Gamma {
+    pub fn new(shape: f64, scale: f64) -> Self {
+        Self { shape, scale }
+    }
+}
+
+impl Continuous for Gamma {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        let (k, theta) = (self.shape, self.scale);
+        let log = xlogy(k - 1.0, x) - x / theta - ln_gamma(k) - k * theta.ln();
+        log.exp()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        gamma_inc(self.shape, x.max(0.0) / self.scale)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        gamma_inc_upper(self.shape, x.max(0.0) / self.scale)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        self.scale * rng.gamma(self.shape)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+/// Number of successes in `trials` independent trials with probability `p`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binomial {
+    pub trials: u64,
+    pub p: f64,
+}
+
+impl Binomial {
+    pub fn new(trials: u64, p: f64) -> Self {
+        Self { trials, p }
+    }
+}
+
+impl Discrete for Binomial {
+    fn pmf(&self, k: i64) -> f64 {
+        let n = self.trials as i64;
+        if k < 0 || k > n {
+            return 0.0;
+        }
+        match self.p {
+            0.0 => (k == 0) as u8 as f64,
+            1.0 => (k == n) as u8 as f64,
+            p => {
+                let (n, k) = (n as f64, k as f64);
+                let log = ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+                    + k * p.ln()
+                    + (n - k) * (1.0 - p).ln();
+                log.exp()
+            }
+        }
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        let n = self.trials as i64;
+        if k < 0 {
+            return 0.0;
+        }
+        if k >= n {
+            return 1.0;
+        }
+        beta_inc((n - k) as f64, k as f64 + 1.0, 1.0 - self.p)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> i64 {
+        rng.binomial(self.trials, self.p) as i64
+    }
+
+    fn max_value(&self) -> i64 {
+        self.trials as i64
+    }
+}
+
+/// Number of events at rate `lambda` per interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Poisson {
+    pub lambda: f64,
+}
+
+impl Poisson {
+    pub fn new(lambda: f64) -> Self {
+        Self { lambda }
+    }
+}
+
+impl Discrete for Poisson {
+    fn pmf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return 0.0;
+        }
+        match self.lambda {
+            0.0 => (k == 0) as u8 as f64,
+            lambda => {
+                let k = k as f64;
+                (k * lambda.ln() - lambda - ln_gamma(k + 1.0)).exp()
+            }
+        }
+    }
+
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return 0.0;
+        }
+        gamma_inc_upper(k as f64 + 1.0, self.lambda)
+    }
+
+    fn sample(&self, rng: &mut Rng) -> i64 {
+        rng.poisson(self.lambda) as i64
+    }
+}
+
+/// `a·ln(x)`, taking 0·ln(0) as 0 so a unit shape parameter keeps its density
+/// finite at the edge of the support
+fn xlogy(a: f64, x: f64) -> f64 {
+    if a == 0.0 { 0.0 } else { a * x.ln() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continuous_match_reference_values() {
+        let close = |a: f64, b: f64, tol: f64| assert!((a - b).abs() < tol, "{} vs {}", a, b);
+        let normal = Normal::new(1.0, 2.0);
+        close(normal.cdf(1.0 + 2.0 * 1.96), 0.975_002, 1e-6);
+        close(normal.quantile(0.975), 1.0 + 2.0 * 1.959_964, 1e-6);
+        close(Normal::standard().sf(8.0), 6.220_96e-16, 1e-20);
+        close(Normal::standard().pdf(0.0), 0.398_942_3, 1e-7);
+
+        close(StudentT::new(5.0).pdf(0.0), 0.379_606_7, 1e-7);
+        close(ChiSquared::new(3.0).cdf(7.814_728), 0.95, 1e-6);
+        close(ChiSquared::new(3.0).quantile(0.95), 7.814_728, 1e-5);
+        close(ChiSquared::new(4.0).pdf(2.0), 0.183_939_7, 1e-7);
+        close(FisherF::new(3.0, 10.0).quantile(0.95), 3.708_265, 1e-5);
+        close(FisherF::new(3.0, 10.0).pdf(1.0), 0.404_122_8, 1e-6);
+        close(Beta::new(2.0, 5.0).cdf(0.3), 0.579_825, 1e-6);
+        close(Beta::new(2.0, 5.0).pdf(0.3), 2.160_9, 1e-4);
+        close(Gamma::new(2.0, 3.0).cdf(6.0), 0.593_994_1, 1e-7);
+
+        // Unit shapes are finite at the edge of the support
+        close(Gamma::new(1.0, 2.0).pdf(0.0), 0.5, 1e-12);
+        close(ChiSquared::new(2.0).pdf(0.0), 0.5, 1e-12);
+        close(Beta::new(1.0, 1.0).pdf(0.0), 1.0, 1e-12);
+        close(Beta::new(1.0, 1.0).pdf(1.0), 1.0, 1e-12);
+        close(Beta::new(1.0, 3.0).pdf(0.0), 3.0, 1e-12);
+        close(Beta::new(2.0, 3.0).pdf(0.0), 0.0, 1e-12);
+
+        // Densities integrate to their CDFs
+        let f = FisherF::new(4.0, 12.0);
+        let step = 1e-3;
+        let integral: f64 = (0..2000)
+            .map(|i| f.pdf((i as f64 + 0.5) * step) * step)
+            .sum();
+        close(integral, f.cdf(2.0), 1e-5);
+
+        let mut rng = Rng::new(5);
+        let draws = Gamma::new(2.0, 3.0).samples(20_000, &mut rng);
+        let mean = draws.iter().sum::<f32>() / draws.len() as f32;
+        assert!((mean - 6.0).abs() < 0.1, "{}", mean);
+    }
+
+    #[test]
+    fn test_discrete_pmf_cdf_quantile_and_stems() {
+        let binomial = Binomial::new(10, 0.3);
+        assert!((binomial.pmf(3) - 0.266_827_9).abs() < 1e-7);
+        let total: f64 = (0..=4).map(|k| binomial.pmf(k)).sum();
+        assert!((binomial.cdf(4) - total).abs() < 1e-12);
+        assert_eq!(binomial.quantile(0.5), 3);
+        assert_eq!(binomial.quantile(1.0), 10);
+
+        assert_eq!(Poisson::new(0.0).pmf(0), 1.0);
+        assert_eq!(Poisson::new(0.0).cdf(0), 1.0);
+        let poisson = Poisson::new(4.0);
+        assert!((poisson.cdf(2) - 0.238_103_3).abs() < 1e-7);
+        assert_eq!(poisson.quantile(0.95), 8);
+        assert_eq!(poisson.quantile(0.0), 0);
+
+        let axes = Axes2D::new().x_range(-1.5, 12.0).y_range(0.0, 0.3);
+        let stems = binomial.stems(&axes);
+        assert_eq!(stems.len(), 11);
+        assert_eq!(stems.get_points()[3], vec2(3.0, binomial.pmf(3) as f32));
+
+        let coarse = Normal::standard().density_curve(&axes, 1);
+        assert_eq!(coarse.get_points().len(), 2);
+        assert!(coarse.get_points().iter().all(|p| p.is_finite()));
+        let tail = Normal::standard().area(&axes, 1.0, f64::INFINITY, 541);
+        assert_eq!(tail.get_bounds(), Some((1.0, 12.0)));
+        assert!((tail.integral() - 0.158_655_3).abs() < 1e-3);
+    }
+}
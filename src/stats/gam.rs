@@ -0,0 +1,514 @@
+//! Penalized regression splines: `y ~ s(x)` with automatic smoothness selection
+
+use super::FitError;
+use super::linalg::{cholesky, cholesky_inverse, cholesky_solve, normal_equations, quadratic_form};
+use super::special::student_t_quantile;
+use crate::mobject::{ConfidenceBand, Curve};
+use crate::state::Dataset;
+use macroquad::prelude::*;
+
+/// Dimension of the penalty's null space: straight lines are never penalized
+const NULL_SPACE: usize = 2;
+
+/// How the smoothing parameter λ is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Smoothing {
+    /// Generalized cross-validation, minimizing `n·RSS / (n - edf)²`
+    #[default]
+    Gcv,
+    /// Restricted maximum likelihood; less prone to undersmoothing than GCV
+    Reml,
+    /// A fixed λ
+    Fixed(f32),
+}
+
+/// A generalized additive model of one smooth term, `y ~ s(x)`: a cubic
+/// B-spline basis over the data range whose wiggliness `∫f''(x)²dx` is
+/// penalized by λ.
+///
+/// ```ignore
+/// let fit = Gam::new().smoothing(Smoothing::Reml).fit_dataset(&data)?;
+/// scene.add(fit.confidence_band(-2.5, 2.5, 100, 0.95).on_axes(&axes));
+/// scene.add(fit.curve(-2.5, 2.5, 100).on_axes(&axes));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gam {
+    basis_size: usize,
+    smoothing: Smoothing,
+}
+
+impl Gam {
+    pub fn new() -> Self {
+        Self {
+            basis_size: 10,
+            smoothing: Smoothing::default(),
+        }
+    }
+
+    /// Number of basis functions (at least 4). This caps how wiggly the fit can
+    /// be; the penalty decides how much of that freedom is used.
+    pub fn basis_size(mut self, size: usize) -> Self {
+        self.basis_size = size.max(4);
+        self
+    }
+
+    pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Smooth of a dataset's y against its x
+    pub fn fit_dataset(&self, data: &Dataset) -> Result<GamFit, FitError> {
+        let (x, y): (Vec<f32>, Vec<f32>) = data.as_2d().iter().map(|p| (p.x, p.y)).unzip();
+        self.fit(&x, &y)
+    }
+
+    pub fn fit(&self, x: &[f32], y: &[f32]) -> Result<GamFit, FitError> {
+        if x.len() != y.len() {
+            return Err(FitError::LengthMismatch {
+                expected: y.len(),
+                found: x.len(),
+            });
+        }
+        // Pairs with a missing (non-finite) value are dropped before counting
+        let (x, y): (Vec<f64>, Vec<f64>) = x
+            .iter()
+            .zip(y)
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|(&x, &y)| (x as f64, y as f64))
+            .unzip();
+        let n = y.len();
+        if n < self.basis_size.max(NULL_SPACE + 2) {
+            return Err(FitError::TooFewPoints {
+                needed: self.basis_size.max(NULL_SPACE + 2),
+                found: n,
+            });
+        }
+        let (min, max) = x
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        if max <= min {
+            return Err(FitError::Singular);
+        }
+
+        let basis = SplineBasis::new(min, max, self.basis_size);
+        let rows: Vec<Vec<f64>> = x.iter().map(|&v| basis.row(v)).collect();
+        let problem = Problem::new(&basis, rows, y);
+
+        let criterion = |s: &Solution| match self.smoothing {
+            Smoothing::Reml => s.reml,
+            _ => s.gcv,
+        };
+        let solution = match self.smoothing {
+            Smoothing::Fixed(lambda) => problem.solve(lambda.max(0.0) as f64),
+            _ => problem.select(criterion),
+        }
+        .ok_or(FitError::Singular)?;
+
+        let mean_y = problem.y.iter().sum::<f64>() / n as f64;
+        let tss: f64 = problem.y.iter().map(|v| (v - mean_y).powi(2)).sum();
+        let variance = solution.rss / (n as f64 - solution.edf).max(1.0);
+        let inverse = cholesky_inverse(&solution.factor);
+
+        Ok(GamFit {
+            lambda: solution.lambda as f32,
+            edf: solution.edf as f32,
+            sigma: variance.sqrt() as f32,
+            r_squared: if tss > 0.0 {
+                (1.0 - solution.rss / tss) as f32
+            } else {
+                1.0
+            },
+            score: criterion(&solution) as f32,
+            n,
+            basis,
+            coefficients: solution.beta,
+            covariance: inverse
+                .iter()
+                .map(|row| row.iter().map(|v| v * variance).collect())
+                .collect(),
+        })
+    }
+}
+
+impl Default for Gam {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fitted smooth `y = f(x)`
+#[derive(Debug, Clone)]
+pub struct GamFit {
+    /// Smoothing parameter used
+    pub lambda: f32,
+    /// Effective degrees of freedom, from 2 (a straight line) up to the basis size
+    pub edf: f32,
+    /// Residual standard error
+    pub sigma: f32,
+    pub r_squared: f32,
+    /// GCV or REML score at `lambda` (GCV for a fixed λ)
+    pub score: f32,
+    /// Observations used in the fit
+    pub n: usize,
+    basis: SplineBasis,
+    coefficients: Vec<f64>,
+    /// Bayesian posterior covariance of the coefficients, σ²(XᵀX + λS)⁻¹
+    covariance: Vec<Vec<f64>>,
+}
+
+impl GamFit {
+    /// Fitted value at `x`; outside the data range the fit continues as a straight line
+    pub fn predict(&self, x: f32) -> f32 {
+        let row = self.basis.row(x as f64);
+        row.iter()
+            .zip(&self.coefficients)
+            .map(|(b, c)| b * c)
+            .sum::<f64>() as f32
+    }
+
+    /// Pointwise standard error of the smooth at `x`
+    pub fn std_error(&self, x: f32) -> f32 {
+        let row = self.basis.row(x as f64);
+        quadratic_form(&self.covariance, &row).max(0.0).sqrt() as f32
+    }
+
+    /// Smallest and largest x the smooth was fitted to
+    pub fn x_range(&self) -> (f32, f32) {
+        (self.basis.min as f32, self.basis.max as f32)
+    }
+
+    /// The smooth over `x_min..x_max`
+    pub fn curve(&self, x_min: f32, x_max: f32, samples: usize) -> Curve {
+        Curve::from_function(|x| self.predict(x), x_min, x_max, samples.max(2))
+    }
+
+    /// Band of `multiple` standard errors either side of the smooth
+    /// (2 gives the familiar "±2 SE" band)
+    pub fn std_error_band(
+        &self,
+        x_min: f32,
+        x_max: f32,
+        samples: usize,
+        multiple: f32,
+    ) -> ConfidenceBand {
+        let samples = samples.max(2);
+        let (centers, errors): (Vec<Vec2>, Vec<f32>) = (0..samples)
+            .map(|i| {
+                let x = x_min + (x_max - x_min) * i as f32 / (samples - 1) as f32;
+                (vec2(x, self.predict(x)), multiple * self.std_error(x))
+            })
+            .unzip();
+        ConfidenceBand::from_curve_with_error(&centers, &errors)
+    }
+
+    /// Pointwise confidence band at `level` (e.g. 0.95), using Student-t
+    /// quantiles on the residual degrees of freedom
+    pub fn confidence_band(
+        &self,
+        x_min: f32,
+        x_max: f32,
+        samples: usize,
+        level: f32,
+    ) -> ConfidenceBand {
+        let alpha = 1.0 - level.clamp(0.0, 0.999_999) as f64;
+        let df = (self.n as f64 - self.edf as f64).max(1.0);
+        let t = student_t_quantile(1.0 - alpha / 2.0, df) as f32;
+        self.std_error_band(x_min, x_max, samples, t)
+    }
+}
+
+/// Cubic B-splines on evenly spaced knots, with the data range spanning the
+/// middle `size - 3` knot intervals
+#[derive(Debug, Clone, PartialEq)]
+struct SplineBasis {
+    min: f64,
+    max: f64,
+    spacing: f64,
+    size: usize,
+}
+
+impl SplineBasis {
+    fn new(min: f64, max: f64, size: usize) -> Self {
+        Self {
+            min,
+            max,
+            spacing: (max - min) / (size - 3) as f64,
+            size,
+        }
+    }
+
+    /// Left end of the support of basis function `j`
+    fn knot(&self, j: usize) -> f64 {
+        self.min + (j as f64 - 3.0) * self.spacing
+    }
+
+    /// Basis function values at `x`, extended linearly beyond the data range
+    fn row(&self, x: f64) -> Vec<f64> {
+        let edge = x.clamp(self.min, self.max);
+        let overshoot = x - edge;
+        (0..self.size)
+            .map(|j| {
+                let u = (edge - self.knot(j)) / self.spacing;
+                bspline(u) + overshoot * bspline_slope(u) / self.spacing
+            })
+            .collect()
+    }
+
+    /// Penalty matrix `S` with `βᵀSβ = ∫f''(x)²dx` over the data range
+    fn penalty(&self) -> Vec<Vec<f64>> {
+        let h = self.spacing;
+        let mut penalty = vec![vec![0.0; self.size]; self.size];
+        // f'' is linear between knots, so Simpson's rule is exact for f''²
+        for interval in 0..self.size - 3 {
+            let start = self.min + interval as f64 * h;
+            for (x, weight) in [(start, 1.0), (start + h / 2.0, 4.0), (start + h, 1.0)] {
+                let curvature: Vec<f64> = (0..self.size)
+                    .map(|j| bspline_curvature((x - self.knot(j)) / h) / (h * h))
+                    .collect();
+                for (row, ci) in penalty.iter_mut().zip(&curvature) {
+                    for (cell, cj) in row.iter_mut().zip(&curvature) {
+                        *cell += weight * h / 6.0 * ci * cj;
+                    }
+                }
+            }
+        }
+        penalty
+    }
+}
+
+/// Uniform cubic B-spline on `0..4`
+fn bspline(u: f64) -> f64 {
+    match u.floor() as i32 {
+        0 => u.powi(3) / 6.0,
+        1 => (-3.0 * u.powi(3) + 12.0 * u * u - 12.0 * u + 4.0) / 6.0,
+        2 => (3.0 * u.powi(3) - 24.0 * u * u + 60.0 * u - 44.0) / 6.0,
+        3 => (4.0 - u).powi(3) / 6.0,
+        _ => 0.0,
+    }
+}
+
+fn bspline_slope(u: f64) -> f64 {
+    match u.floor() as i32 {
+        0 => u * u / 2.0,
+        1 => -1.5 * u * u + 4.0 * u - 2.0,
+        2 => 1.5 * u * u - 8.0 * u + 10.0,
+        3 => -(4.0 - u).powi(2) / 2.0,
+        _ => 0.0,
+    }
+}
+
+fn bspline_curvature(u: f64) -> f64 {
+    match u.floor() as i32 {
+        0 => u,
+        1 => 4.0 - 3.0 * u,
+        2 => 3.0 * u - 8.0,
+        3 => 4.0 - u,
+        _ => 0.0,
+    }
+}
+
+/// The penalized least squares problem for a given basis and data
+struct Problem {
+    rows: Vec<Vec<f64>>,
+    y: Vec<f64>,
+    xtx: Vec<Vec<f64>>,
+    xty: Vec<f64>,
+    penalty: Vec<Vec<f64>>,
+}
+
+/// The fit at one value of λ and its selection scores
+struct Solution {
+    lambda: f64,
+    beta: Vec<f64>,
+    /// Cholesky factor of `XᵀX + λS`
+    factor: Vec<Vec<f64>>,
+    rss: f64,
+    edf: f64,
+    gcv: f64,
+    reml: f64,
+}
+
+impl Problem {
+    fn new(basis: &SplineBasis, rows: Vec<Vec<f64>>, y: Vec<f64>) -> Self {
+        let (xtx, xty) = normal_equations(&rows, &y);
+        Self {
+            rows,
+            y,
+            xtx,
+            xty,
+            penalty: basis.penalty(),
+        }
+    }
+
+    /// Minimize `criterion` over λ: a coarse grid in log λ, then golden-section refinement
+    fn select(&self, criterion: impl Fn(&Solution) -> f64) -> Option<Solution> {
+        // Search relative to the balance of data and penalty so the grid suits any units
+        let trace = |m: &[Vec<f64>]| (0..m.len()).map(|i| m[i][i]).sum::<f64>();
+        let ratio = trace(&self.xtx) / trace(&self.penalty).max(f64::MIN_POSITIVE);
+        let at = |log_lambda: f64| self.solve(ratio * 10f64.powf(log_lambda));
+        let score = |log_lambda: f64| at(log_lambda).map_or(f64::INFINITY, |s| criterion(&s));
+
+        const STEP: f64 = 0.25;
+        let best = (-32..=32)
+            .map(|i| i as f64 * STEP)
+            .map(|s| (s, score(s)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?
+            .0;
+
+        let golden = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut lo, mut hi) = (best - STEP, best + STEP);
+        for _ in 0..30 {
+            let a = hi - golden * (hi - lo);
+            let b = lo + golden * (hi - lo);
+            if score(a) < score(b) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        let refined = (lo + hi) / 2.0;
+        if score(refined) <= score(best) {
+            at(refined)
+        } else {
+            at(best)
+        }
+    }
+
+    fn solve(&self, lambda: f64) -> Option<Solution> {
+        let n = self.y.len() as f64;
+        let p = self.xty.len();
+        let system: Vec<Vec<f64>> = self
+            .xtx
+            .iter()
+            .zip(&self.penalty)
+            .map(|(a, s)| a.iter().zip(s).map(|(a, s)| a + lambda * s).collect())
+            .collect();
+        let factor = cholesky(&system)?;
+        let beta = cholesky_solve(&factor, &self.xty);
+
+        let rss: f64 = self
+            .rows
+            .iter()
+            .zip(&self.y)
+            .map(|(row, y)| (y - row.iter().zip(&beta).map(|(x, b)| x * b).sum::<f64>()).powi(2))
+            .sum();
+        // edf = tr((XᵀX + λS)⁻¹ XᵀX)
+        let inverse = cholesky_inverse(&factor);
+        let edf: f64 = inverse
+            .iter()
+            .zip(&self.xtx)
+            .map(|(a, b)| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>())
+            .sum();
+        let gcv = n * rss / (n - edf).max(f64::EPSILON).powi(2);
+
+        // Restricted likelihood with σ² profiled out, up to a constant
+        let free = n - NULL_SPACE as f64;
+        let deviance = rss + lambda * quadratic_form(&self.penalty, &beta);
+        let log_det: f64 = factor
+            .iter()
+            .enumerate()
+            .map(|(i, row)| 2.0 * row[i].ln())
+            .sum();
+        let reml = free * (deviance / free).ln() + log_det
+            - (p - NULL_SPACE) as f64 * lambda.max(f64::MIN_POSITIVE).ln();
+
+        Some(Solution {
+            lambda,
+            beta,
+            factor,
+            rss,
+            edf,
+            gcv,
+            reml,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic, roughly uniform noise in -0.5..0.5
+    fn noise(i: usize) -> f32 {
+        ((i * 7919) % 1000) as f32 / 1000.0 - 0.5
+    }
+
+    #[test]
+    fn test_penalty_ignores_straight_lines() {
+        let basis = SplineBasis::new(-1.0, 3.0, 8);
+        // A straight line's B-spline coefficients are the centers of their supports
+        let line: Vec<f64> = (0..8)
+            .map(|j| 2.0 * (basis.knot(j) + 2.0 * basis.spacing))
+            .collect();
+        assert!(quadratic_form(&basis.penalty(), &line).abs() < 1e-9);
+        let row = basis.row(1.3);
+        let value: f64 = row.iter().zip(&line).map(|(b, c)| b * c).sum();
+        assert!((value - 2.6).abs() < 1e-9 && (row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // Heavily smoothed data collapses to the least-squares line, even when extrapolating
+        let x: Vec<f32> = (0..30).map(|i| i as f32 / 10.0).collect();
+        let y: Vec<f32> = x
+            .iter()
+            .enumerate()
+            .map(|(i, x)| 1.0 + x + 0.1 * noise(i))
+            .collect();
+        let fit = Gam::new()
+            .smoothing(Smoothing::Fixed(1e9))
+            .fit(&x, &y)
+            .unwrap();
+        let ols = super::super::fit_linear(&x, &y).unwrap();
+        assert!((fit.edf - 2.0).abs() < 1e-3);
+        for x in [-1.0, 0.5, 4.0] {
+            assert!((fit.predict(x) - ols.predict(x)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_gcv_and_reml_recover_smooth_signal() {
+        let x: Vec<f32> = (0..80).map(|i| -2.5 + 5.0 * i as f32 / 79.0).collect();
+        let y: Vec<f32> = x
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x.sin() + 0.6 * noise(i))
+            .collect();
+
+        for smoothing in [Smoothing::Gcv, Smoothing::Reml] {
+            let fit = Gam::new().smoothing(smoothing).fit(&x, &y).unwrap();
+            assert!(
+                fit.edf > 3.0 && fit.edf < 9.0,
+                "{:?}: edf {}",
+                smoothing,
+                fit.edf
+            );
+            let covered = x
+                .iter()
+                .filter(|&&x| {
+                    let se = fit.std_error(x);
+                    (fit.predict(x) - x.sin()).abs() < 2.5 * se && se < 0.15
+                })
+                .count();
+            assert!(covered >= 72, "{:?}: {} covered", smoothing, covered);
+        }
+    }
+
+    #[test]
+    fn test_missing_values_are_dropped() {
+        let mut x: Vec<f32> = (0..12).map(|i| i as f32).collect();
+        let mut y: Vec<f32> = x.iter().map(|x| 2.0 * x - 1.0).collect();
+        x[3] = f32::NAN;
+        y[7] = f32::INFINITY;
+        let fit = Gam::new().basis_size(6).fit(&x, &y).unwrap();
+        assert_eq!(fit.n, 10);
+        assert!((fit.predict(5.0) - 9.0).abs() < 1e-3);
+
+        x[0] = f32::NAN;
+        assert!(matches!(
+            Gam::new().basis_size(10).fit(&x, &y),
+            Err(FitError::TooFewPoints {
+                needed: 10,
+                found: 9
+            })
+        ));
+    }
+}
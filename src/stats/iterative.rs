@@ -0,0 +1,256 @@
+//! Iterative model fitting, recorded step by step so convergence can be animated
+
+use super::linalg::{cholesky, cholesky_solve, normal_equations};
+use crate::mobject::{Axes2D, Curve};
+use macroquad::prelude::*;
+
+/// The parameters and loss after every step of an iterative fit, starting
+/// with the initial guess.
+///
+/// ```ignore
+/// let path = FitPath::gradient_descent(&x, &y, 0.05, 40);
+/// let fit = scene.add(path.curve(0, line, -3.0, 3.0, 50).on_axes(&axes));
+/// let inset = path.loss_axes(200.0, 120.0).at(vec2(260.0, 160.0));
+/// let loss = scene.add(path.loss_curve().on_axes(&inset));
+/// scene.add(inset);
+/// scene.play_together(vec![
+///     Box::new(MorphCurve::through(fit, path.frames(line, -3.0, 3.0, 50)).duration(4.0).easing(Easing::Linear)),
+///     Box::new(Create::new(loss).duration(4.0).easing(Easing::Linear)),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitPath {
+    /// Parameter vector at each step
+    pub parameters: Vec<Vec<f32>>,
+    /// Loss at each step
+    pub losses: Vec<f32>,
+}
+
+impl FitPath {
+    /// Apply `update` `iterations` times from `initial`, recording each step
+    pub fn record<L, U>(initial: Vec<f32>, iterations: usize, loss: L, mut update: U) -> Self
+    where
+        L: Fn(&[f32]) -> f32,
+        U: FnMut(&[f32]) -> Vec<f32>,
+    {
+        let mut parameters = vec![initial];
+        for _ in 0..iterations {
+            let next = update(&parameters[parameters.len() - 1]);
+            parameters.push(next);
+        }
+        let losses = parameters.iter().map(|p| loss(p)).collect();
+        Self { parameters, losses }
+    }
+
+    /// Gradient descent on the mean squared error of the line `y = a + b·x`,
+    /// starting from a flat line through zero. Parameters are `[a, b]`.
+    pub fn gradient_descent(x: &[f32], y: &[f32], learning_rate: f32, iterations: usize) -> Self {
+        let n = x.len().min(y.len()).max(1) as f32;
+        let residuals = |p: &[f32]| {
+            x.iter()
+                .zip(y)
+                .map(|(x, y)| (*x, y - line(p, *x)))
+                .collect::<Vec<_>>()
+        };
+        let loss = |p: &[f32]| residuals(p).iter().map(|(_, r)| r * r).sum::<f32>() / n;
+        let update = |p: &[f32]| {
+            let (da, db) = residuals(p).iter().fold((0.0, 0.0), |(da, db), (x, r)| {
+                (da - 2.0 * r / n, db - 2.0 * r * x / n)
+            });
+            vec![p[0] - learning_rate * da, p[1] - learning_rate * db]
+        };
+        Self::record(vec![0.0, 0.0], iterations, loss, update)
+    }
+
+    /// Iteratively reweighted least squares (Newton's method) for logistic
+    /// regression `P(y = 1) = σ(a + b·x)` with `y` of 0 or 1, starting from
+    /// `[0, 0]`. The loss is the mean negative log-likelihood.
+    pub fn logistic_irls(x: &[f32], y: &[f32], iterations: usize) -> Self {
+        let n = x.len().min(y.len()).max(1) as f32;
+        let loss = |p: &[f32]| {
+            let total: f32 = x
+                .iter()
+                .zip(y)
+                .map(|(x, y)| {
+                    let prob = logistic(p, *x).clamp(1e-7, 1.0 - 1e-7);
+                    -(y * prob.ln() + (1.0 - y) * (1.0 - prob).ln())
+                })
+                .sum();
+            total / n
+        };
+        let update = |p: &[f32]| {
+            // Weighted least squares on the working response z = η + (y - μ)/w
+            let (rows, z): (Vec<Vec<f64>>, Vec<f64>) = x
+                .iter()
+                .zip(y)
+                .filter_map(|(&x, &y)| {
+                    let (x, y) = (x as f64, y as f64);
+                    let eta = p[0] as f64 + p[1] as f64 * x;
+                    let mu = 1.0 / (1.0 + (-eta).exp());
+                    let w = mu * (1.0 - mu);
+                    (w > 1e-10).then(|| {
+                        let root = w.sqrt();
+                        (vec![root, root * x], root * (eta + (y - mu) / w))
+                    })
+                })
+                .unzip();
+            let (xtx, xtz) = normal_equations(&rows, &z);
+            match cholesky(&xtx) {
+                Some(factor) => cholesky_solve(&factor, &xtz)
+                    .iter()
+                    .map(|&b| b as f32)
+                    .collect(),
+                // Separated data: the estimate has diverged, so stay put
+                None => p.to_vec(),
+            }
+        };
+        Self::record(vec![0.0, 0.0], iterations, loss, update)
+    }
+
+    /// Number of recorded steps, including the initial guess
+    pub fn len(&self) -> usize {
+        self.parameters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Parameters after the last step
+    pub fn last(&self) -> Option<&[f32]> {
+        self.parameters.last().map(Vec::as_slice)
+    }
+
+    /// The model at step `step` over `x_min..x_max`
+    pub fn curve<F>(&self, step: usize, model: F, x_min: f32, x_max: f32, samples: usize) -> Curve
+    where
+        F: Fn(&[f32], f32) -> f32,
+    {
+        let points = self.points(step, &model, x_min, x_max, samples);
+        Curve::from_points(points)
+    }
+
+    /// The model's points after each step, for `MorphCurve::through`. The
+    /// initial guess is left out, since the morphed curve starts there.
+    pub fn frames<F>(&self, model: F, x_min: f32, x_max: f32, samples: usize) -> Vec<Vec<Vec2>>
+    where
+        F: Fn(&[f32], f32) -> f32,
+    {
+        (1..self.len())
+            .map(|step| self.points(step, &model, x_min, x_max, samples))
+            .collect()
+    }
+
+    /// Loss against iteration number
+    pub fn loss_curve(&self) -> Curve {
+        Curve::from_points(self.loss_points())
+    }
+
+    /// Small axes fitted to the loss curve, for an inset beside the main plot
+    pub fn loss_axes(&self, width: f32, height: f32) -> Axes2D {
+        Axes2D::fit_to_points(&self.loss_points()).size(width, height)
+    }
+
+    fn loss_points(&self) -> Vec<Vec2> {
+        self.losses
+            .iter()
+            .enumerate()
+            .map(|(i, &loss)| vec2(i as f32, loss))
+            .collect()
+    }
+
+    fn points<F>(&self, step: usize, model: &F, x_min: f32, x_max: f32, samples: usize) -> Vec<Vec2>
+    where
+        F: Fn(&[f32], f32) -> f32,
+    {
+        let Some(parameters) = self.parameters.get(step.min(self.len().saturating_sub(1))) else {
+            return Vec::new();
+        };
+        let samples = samples.max(2);
+        (0..samples)
+            .map(|i| {
+                let x = x_min + (x_max - x_min) * i as f32 / (samples - 1) as f32;
+                vec2(x, model(parameters, x))
+            })
+            .collect()
+    }
+}
+
+/// The line `a + b·x` for parameters `[a, b]`
+pub fn line(parameters: &[f32], x: f32) -> f32 {
+    parameters[0] + parameters[1] * x
+}
+
+/// The logistic curve `σ(a + b·x)` for parameters `[a, b]`
+pub fn logistic(parameters: &[f32], x: f32) -> f32 {
+    1.0 / (1.0 + (-line(parameters, x)).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Animation, Easing, MorphCurve};
+    use crate::mobject::Mobject;
+
+    #[test]
+    fn test_descent_and_irls_converge() {
+        let x: Vec<f32> = (0..20).map(|i| i as f32 / 5.0 - 2.0).collect();
+        let y: Vec<f32> = x
+            .iter()
+            .map(|x| 1.5 - 0.5 * x + 0.1 * (x * 3.0).sin())
+            .collect();
+        let path = FitPath::gradient_descent(&x, &y, 0.2, 200);
+        let ols = super::super::fit_linear(&x, &y).unwrap();
+        let last = path.last().unwrap();
+        assert!((last[0] - ols.coefficients[0]).abs() < 1e-3);
+        assert!((last[1] - ols.coefficients[1]).abs() < 1e-3);
+        assert!(path.losses.windows(2).all(|w| w[1] <= w[0] + 1e-6));
+
+        // At the maximum likelihood the score equations hold: Σ(y - p) = Σ(y - p)x = 0
+        let labels: Vec<f32> = x
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                if *x + (i % 3) as f32 - 1.0 > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let path = FitPath::logistic_irls(&x, &labels, 8);
+        let p = path.last().unwrap();
+        let (s0, s1) = x.iter().zip(&labels).fold((0.0, 0.0), |(s0, s1), (x, y)| {
+            let r = y - logistic(p, *x);
+            (s0 + r, s1 + r * x)
+        });
+        assert!(s0.abs() < 1e-4 && s1.abs() < 1e-4, "{} {}", s0, s1);
+        assert_eq!(path.len(), 9);
+    }
+
+    #[test]
+    fn test_morph_steps_through_frames() {
+        let path = FitPath::record(vec![0.0], 2, |p| p[0].abs(), |p| vec![p[0] + 1.0]);
+        let flat = |p: &[f32], _: f32| p[0];
+        let mut curve = path.curve(0, flat, 0.0, 1.0, 3);
+        let morph =
+            MorphCurve::through(curve.id(), path.frames(flat, 0.0, 1.0, 3)).easing(Easing::Linear);
+
+        let height = |t: f32| {
+            let mut curve = curve.clone();
+            morph.apply(&mut curve, t);
+            curve.get_points()[1].y
+        };
+        // Two steps share the duration: 0 → 1 in the first half, 1 → 2 in the second
+        assert!((height(0.25) - 0.5).abs() < 1e-6);
+        assert!((height(0.5) - 1.0).abs() < 1e-6);
+        assert!((height(1.0) - 2.0).abs() < 1e-6);
+
+        // Frames with more points resample the starting curve
+        MorphCurve::new(curve.id(), vec![vec2(0.0, 4.0); 5])
+            .easing(Easing::Linear)
+            .apply(&mut curve, 0.5);
+        assert_eq!(curve.len(), 5);
+        assert!(curve.get_points().iter().all(|p| (p.y - 2.0).abs() < 1e-6));
+    }
+}
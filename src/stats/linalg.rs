@@ -0,0 +1,108 @@
+//! Small dense linear algebra for model fitting. Matrices are row-major `Vec<Vec<f64>>`.
+
+/// Lower-triangular Cholesky factor of a symmetric positive definite matrix,
+/// or None if the matrix is (numerically) singular
+pub(crate) fn cholesky(a: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let tolerance = 1e-12 * (0..n).map(|i| a[i][i].abs()).fold(0.0, f64::max);
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let pivot = a[i][i] - sum;
+                if pivot <= tolerance {
+                    return None;
+                }
+                l[i][i] = pivot.sqrt();
+            } else {
+                l[i][j] = (a[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solve `L Lᵀ x = b` given the Cholesky factor `L`
+pub(crate) fn cholesky_solve(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = l.len();
+    let mut y = vec![0.0; n];
+    for i in 0..n {
+        let sum: f64 = (0..i).map(|k| l[i][k] * y[k]).sum();
+        y[i] = (b[i] - sum) / l[i][i];
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|k| l[k][i] * x[k]).sum();
+        x[i] = (y[i] - sum) / l[i][i];
+    }
+    x
+}
+
+/// Inverse of a symmetric positive definite matrix from its Cholesky factor
+pub(crate) fn cholesky_inverse(l: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = l.len();
+    let columns: Vec<Vec<f64>> = (0..n)
+        .map(|j| {
+            let unit: Vec<f64> = (0..n).map(|i| if i == j { 1.0 } else { 0.0 }).collect();
+            cholesky_solve(l, &unit)
+        })
+        .collect();
+    (0..n)
+        .map(|i| (0..n).map(|j| columns[j][i]).collect())
+        .collect()
+}
+
+/// `XᵀX` and `Xᵀy` for a design matrix given as rows
+pub(crate) fn normal_equations(rows: &[Vec<f64>], y: &[f64]) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let p = rows.first().map_or(0, Vec::len);
+    let mut xtx = vec![vec![0.0; p]; p];
+    let mut xty = vec![0.0; p];
+    for (row, &yi) in rows.iter().zip(y) {
+        for ((xtx_i, xty_i), &ri) in xtx.iter_mut().zip(&mut xty).zip(row) {
+            *xty_i += ri * yi;
+            for (cell, &rj) in xtx_i.iter_mut().zip(row) {
+                *cell += ri * rj;
+            }
+        }
+    }
+    (xtx, xty)
+}
+
+/// Quadratic form `vᵀ A v`
+pub(crate) fn quadratic_form(a: &[Vec<f64>], v: &[f64]) -> f64 {
+    a.iter()
+        .zip(v)
+        .map(|(row, vi)| vi * row.iter().zip(v).map(|(aij, vj)| aij * vj).sum::<f64>())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cholesky_solve_and_inverse() {
+        let a = vec![
+            vec![4.0, 2.0, 0.6],
+            vec![2.0, 5.0, 1.0],
+            vec![0.6, 1.0, 3.0],
+        ];
+        let l = cholesky(&a).unwrap();
+        let x = cholesky_solve(&l, &[1.0, 2.0, 3.0]);
+        for (row, b) in a.iter().zip([1.0, 2.0, 3.0]) {
+            let ax: f64 = row.iter().zip(&x).map(|(a, x)| a * x).sum();
+            assert!((ax - b).abs() < 1e-12);
+        }
+
+        let inverse = cholesky_inverse(&l);
+        // The inverse is symmetric, so (A·A⁻¹)ᵢⱼ is row i of A dotted with row j of A⁻¹
+        for (i, a_row) in a.iter().enumerate() {
+            for (j, inv_row) in inverse.iter().enumerate() {
+                let product: f64 = a_row.iter().zip(inv_row).map(|(a, b)| a * b).sum();
+                assert!((product - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+            }
+        }
+        assert!(cholesky(&[vec![1.0, 1.0], vec![1.0, 1.0]]).is_none());
+    }
+}
@@ -0,0 +1,293 @@
+//! LOESS: locally weighted polynomial regression
+
+use super::FitError;
+use super::describe::{self, QuantileMethod};
+use super::linalg::{cholesky, cholesky_solve, normal_equations};
+use super::rng::Rng;
+use crate::mobject::{ConfidenceBand, Curve, ScatterPlot};
+use crate::state::Dataset;
+use macroquad::prelude::*;
+
+/// Settings for a LOESS smoother. Each fitted value is a weighted polynomial
+/// fit to the nearest `span` fraction of the points, weighted by the tricube
+/// of their distance; robustness iterations then down-weight outliers.
+///
+/// ```ignore
+/// let fit = Loess::new().span(0.5).robustness_iterations(2).fit_scatter(&scatter)?;
+/// scene.add(fit.curve(x_min, x_max, 100).on_axes(&axes));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loess {
+    span: f32,
+    degree: usize,
+    iterations: usize,
+}
+
+impl Loess {
+    pub fn new() -> Self {
+        Self {
+            span: 0.75,
+            degree: 2,
+            iterations: 0,
+        }
+    }
+
+    /// Fraction of the points in each local fit; larger is smoother
+    pub fn span(mut self, span: f32) -> Self {
+        self.span = span.max(f32::EPSILON);
+        self
+    }
+
+    /// Degree of the local polynomials: 0 (local mean), 1 or 2
+    pub fn degree(mut self, degree: usize) -> Self {
+        self.degree = degree.min(2);
+        self
+    }
+
+    /// Rounds of bisquare reweighting by residual size; 0 for plain LOESS,
+    /// 3 for classic robust LOWESS
+    pub fn robustness_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Smooth of a scatter plot's points
+    pub fn fit_scatter(&self, scatter: &ScatterPlot) -> Result<LoessFit, FitError> {
+        self.fit_points(scatter.get_points())
+    }
+
+    /// Smooth of a dataset's y against its x
+    pub fn fit_dataset(&self, data: &Dataset) -> Result<LoessFit, FitError> {
+        self.fit_points(&data.as_2d())
+    }
+
+    pub fn fit_points(&self, points: &[Vec2]) -> Result<LoessFit, FitError> {
+        let (x, y): (Vec<f32>, Vec<f32>) = points.iter().map(|p| (p.x, p.y)).unzip();
+        self.fit(&x, &y)
+    }
+
+    pub fn fit(&self, x: &[f32], y: &[f32]) -> Result<LoessFit, FitError> {
+        if x.len() != y.len() {
+            return Err(FitError::LengthMismatch {
+                expected: y.len(),
+                found: x.len(),
+            });
+        }
+        let (x, y): (Vec<f64>, Vec<f64>) = x
+            .iter()
+            .zip(y)
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|(&x, &y)| (x as f64, y as f64))
+            .unzip();
+        if x.len() <= self.degree {
+            return Err(FitError::TooFewPoints {
+                needed: self.degree + 1,
+                found: x.len(),
+            });
+        }
+        Ok(self.fit_unchecked(x, y))
+    }
+
+    fn fit_unchecked(&self, x: Vec<f64>, y: Vec<f64>) -> LoessFit {
+        let mut fit = LoessFit {
+            settings: self.clone(),
+            robustness: vec![1.0; x.len()],
+            x,
+            y,
+        };
+        for _ in 0..self.iterations {
+            let residuals: Vec<f64> = (0..fit.x.len())
+                .map(|i| (fit.y[i] - fit.local_fit(fit.x[i])).abs())
+                .collect();
+            let residuals_f32: Vec<f32> = residuals.iter().map(|&r| r as f32).collect();
+            let scale = 6.0 * describe::median(&residuals_f32) as f64;
+            if scale.is_nan() || scale <= 0.0 {
+                break;
+            }
+            fit.robustness = residuals
+                .iter()
+                .map(|r| (1.0 - (r / scale).powi(2)).max(0.0).powi(2))
+                .collect();
+        }
+        fit
+    }
+}
+
+impl Default for Loess {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fitted LOESS smooth
+#[derive(Debug, Clone)]
+pub struct LoessFit {
+    settings: Loess,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// Bisquare weight of each point from the robustness iterations
+    robustness: Vec<f64>,
+}
+
+impl LoessFit {
+    /// Smoothed value at `x`
+    pub fn predict(&self, x: f32) -> f32 {
+        self.local_fit(x as f64) as f32
+    }
+
+    /// The smooth over `x_min..x_max`
+    pub fn curve(&self, x_min: f32, x_max: f32, samples: usize) -> Curve {
+        Curve::from_function(|x| self.predict(x), x_min, x_max, samples.max(2))
+    }
+
+    /// Pointwise percentile band at `level` (e.g. 0.95) from a residual
+    /// bootstrap: `resamples` refits to the smooth plus resampled residuals.
+    /// The same `seed` always gives the same band.
+    pub fn bootstrap_band(
+        &self,
+        x_min: f32,
+        x_max: f32,
+        samples: usize,
+        level: f32,
+        resamples: usize,
+        seed: u64,
+    ) -> ConfidenceBand {
+        let samples = samples.max(2);
+        let grid: Vec<f32> = (0..samples)
+            .map(|i| x_min + (x_max - x_min) * i as f32 / (samples - 1) as f32)
+            .collect();
+        let fitted: Vec<f64> = self.x.iter().map(|&x| self.local_fit(x)).collect();
+        let residuals: Vec<f64> = self.y.iter().zip(&fitted).map(|(y, f)| y - f).collect();
+
+        let mut rng = Rng::new(seed);
+        let mut draws = vec![Vec::with_capacity(resamples); samples];
+        for _ in 0..resamples {
+            let y = fitted
+                .iter()
+                .map(|f| f + residuals[rng.below(residuals.len())])
+                .collect();
+            let refit = self.settings.fit_unchecked(self.x.clone(), y);
+            for (draw, &x) in draws.iter_mut().zip(&grid) {
+                draw.push(refit.predict(x));
+            }
+        }
+
+        let alpha = (1.0 - level.clamp(0.0, 1.0)) / 2.0;
+        let (lower, upper) = grid
+            .iter()
+            .zip(&draws)
+            .map(|(&x, draw)| {
+                if draw.is_empty() {
+                    let y = self.predict(x);
+                    return (vec2(x, y), vec2(x, y));
+                }
+                let bounds =
+                    describe::quantiles(draw, &[alpha, 1.0 - alpha], QuantileMethod::Linear);
+                (vec2(x, bounds[0]), vec2(x, bounds[1]))
+            })
+            .unzip();
+        ConfidenceBand::from_bounds(lower, upper)
+    }
+
+    /// Weighted local polynomial fit evaluated at `x0`
+    fn local_fit(&self, x0: f64) -> f64 {
+        let n = self.x.len();
+        let distances: Vec<f64> = self.x.iter().map(|x| (x - x0).abs()).collect();
+        let span = self.settings.span as f64;
+        let q = ((span * n as f64).ceil() as usize).clamp(self.settings.degree + 1, n);
+        let mut sorted = distances.clone();
+        let (_, &mut kth, _) = sorted.select_nth_unstable_by(q - 1, f64::total_cmp);
+        // Spans beyond 1 widen the window past the farthest point
+        let radius = kth * span.max(1.0) * 1.000_001;
+
+        let weights: Vec<f64> = distances
+            .iter()
+            .zip(&self.robustness)
+            .map(|(d, r)| {
+                let u = if radius > 0.0 { d / radius } else { 0.0 };
+                (1.0 - u.powi(3)).max(0.0).powi(3) * r
+            })
+            .collect();
+
+        // Lower the degree until the weighted fit is well determined
+        for degree in (0..=self.settings.degree).rev() {
+            let (rows, y): (Vec<Vec<f64>>, Vec<f64>) = self
+                .x
+                .iter()
+                .zip(&self.y)
+                .zip(&weights)
+                .filter(|(_, w)| **w > 0.0)
+                .map(|((x, y), w)| {
+                    let root = w.sqrt();
+                    let row = (0..=degree)
+                        .map(|k| root * (x - x0).powi(k as i32))
+                        .collect();
+                    (row, root * y)
+                })
+                .unzip();
+            let (xtx, xty) = normal_equations(&rows, &y);
+            if let Some(factor) = cholesky(&xtx) {
+                return cholesky_solve(&factor, &xty)[0];
+            }
+        }
+        // Every point has zero weight: fall back to the nearest one
+        distances
+            .iter()
+            .zip(&self.y)
+            .min_by(|a, b| a.0.total_cmp(b.0))
+            .map_or(0.0, |(_, &y)| y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_fits_reproduce_polynomials() {
+        // Local quadratics are exact for quadratic data, whatever the span
+        let x: Vec<f32> = (0..25).map(|i| i as f32 * 0.4).collect();
+        let y: Vec<f32> = x.iter().map(|x| 2.0 - x + 0.3 * x * x).collect();
+        let fit = Loess::new().span(0.3).fit(&x, &y).unwrap();
+        for x in [0.0, 3.3, 9.6] {
+            assert!((fit.predict(x) - (2.0 - x + 0.3 * x * x)).abs() < 1e-3);
+        }
+
+        // Degree 0 with a span covering everything is a weighted mean
+        let flat = Loess::new()
+            .degree(0)
+            .span(10.0)
+            .fit(&[0.0, 1.0], &[1.0, 3.0])
+            .unwrap();
+        assert!((flat.predict(0.5) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_robustness_and_bootstrap() {
+        let x: Vec<f32> = (0..40).map(|i| i as f32).collect();
+        let mut y: Vec<f32> = x.iter().map(|x| 0.5 * x + ((x * 1.7).sin())).collect();
+        y[20] += 40.0;
+
+        let plain = Loess::new().degree(1).span(0.3).fit(&x, &y).unwrap();
+        let robust = plain
+            .settings
+            .clone()
+            .robustness_iterations(3)
+            .fit(&x, &y)
+            .unwrap();
+        assert!((plain.predict(20.0) - 10.0).abs() > 2.0);
+        assert!((robust.predict(20.0) - 10.0).abs() < 1.0);
+        assert!(robust.robustness[20] < 1e-6);
+
+        // The band brackets the smooth, and a fixed seed makes it reproducible
+        let band = |seed| robust.bootstrap_band(0.0, 39.0, 20, 0.9, 50, seed);
+        let curve = robust.curve(0.0, 39.0, 20);
+        let seven = band(7);
+        let (lower, upper) = seven.get_bounds();
+        for ((low, high), p) in lower.iter().zip(upper).zip(curve.get_points()) {
+            assert!(low.y <= p.y + 1e-4 && p.y <= high.y + 1e-4 && high.y - low.y < 3.0);
+        }
+        assert_eq!(band(7).get_bounds(), band(7).get_bounds());
+        assert_ne!(band(7).get_bounds(), band(8).get_bounds());
+    }
+}
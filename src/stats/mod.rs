@@ -0,0 +1,50 @@
+//! Statistical models and summaries that produce plottable mobjects
+
+pub mod describe;
+pub mod dist;
+pub mod gam;
+pub mod iterative;
+pub(crate) mod linalg;
+pub mod loess;
+pub mod regression;
+pub mod rng;
+pub(crate) mod special;
+
+pub use describe::{Correlation, QuantileMethod, Summary};
+pub use dist::{
+    Beta, Binomial, ChiSquared, Continuous, Discrete, FisherF, Gamma, Normal, Poisson, StudentT,
+};
+pub use gam::{Gam, GamFit, Smoothing};
+pub use iterative::FitPath;
+pub use loess::{Loess, LoessFit};
+pub use regression::{OlsFit, fit_dataset, fit_linear, fit_multiple, fit_polynomial};
+pub use rng::Rng;
+
+use std::fmt;
+
+/// Why a model could not be fitted
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitError {
+    /// Predictor and response lengths differ
+    LengthMismatch { expected: usize, found: usize },
+    /// Fewer observations than the model needs
+    TooFewPoints { needed: usize, found: usize },
+    /// The predictors are collinear (or constant), so the fit is not unique
+    Singular,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::LengthMismatch { expected, found } => {
+                write!(f, "expected {} values, found {}", expected, found)
+            }
+            FitError::TooFewPoints { needed, found } => {
+                write!(f, "need at least {} points, found {}", needed, found)
+            }
+            FitError::Singular => write!(f, "predictors are collinear"),
+        }
+    }
+}
+
+impl std::error::Error for FitError {}
@@ -0,0 +1,344 @@
+//! Ordinary least squares: simple, multiple and polynomial regression
+
+use super::FitError;
+use super::linalg::{cholesky, cholesky_inverse, cholesky_solve, normal_equations, quadratic_form};
+use super::special::{student_t_cdf, student_t_quantile};
+use crate::mobject::{ConfidenceBand, Curve};
+use crate::state::Dataset;
+use macroquad::prelude::*;
+
+/// How the predictors enter the design matrix (after the intercept column)
+#[derive(Debug, Clone, PartialEq)]
+enum Terms {
+    /// One column per predictor
+    Linear,
+    /// x, x², ..., x^degree of a single predictor
+    Polynomial(usize),
+}
+
+impl Terms {
+    fn row(&self, predictors: &[f32]) -> Vec<f64> {
+        match self {
+            Terms::Linear => std::iter::once(1.0)
+                .chain(predictors.iter().map(|&x| x as f64))
+                .collect(),
+            Terms::Polynomial(degree) => {
+                let x = predictors.first().copied().unwrap_or(0.0) as f64;
+                (0..=*degree).map(|k| x.powi(k as i32)).collect()
+            }
+        }
+    }
+}
+
+/// An ordinary least squares fit of `y = Xβ + ε`
+#[derive(Debug, Clone)]
+pub struct OlsFit {
+    /// Intercept first, then one coefficient per predictor (or per power of x)
+    pub coefficients: Vec<f32>,
+    /// Standard error of each coefficient
+    pub std_errors: Vec<f32>,
+    pub r_squared: f32,
+    /// R² penalized for the number of coefficients
+    pub adj_r_squared: f32,
+    /// Residual standard error
+    pub sigma: f32,
+    /// Observations used in the fit
+    pub n: usize,
+    /// Residual degrees of freedom, n minus the number of coefficients
+    pub df_residual: usize,
+    terms: Terms,
+    /// Covariance matrix of the coefficients, σ²(XᵀX)⁻¹
+    covariance: Vec<Vec<f64>>,
+    /// Mean of each predictor, held fixed when plotting against the first one
+    means: Vec<f32>,
+}
+
+/// Fit `y = a + b·x`
+pub fn fit_linear(x: &[f32], y: &[f32]) -> Result<OlsFit, FitError> {
+    fit_polynomial(x, y, 1)
+}
+
+/// Fit `y = a + b₁x + b₂x² + ... + b_d·x^d`
+pub fn fit_polynomial(x: &[f32], y: &[f32], degree: usize) -> Result<OlsFit, FitError> {
+    let rows: Vec<Vec<f32>> = x.iter().map(|&x| vec![x]).collect();
+    fit(Terms::Polynomial(degree), &rows, y)
+}
+
+/// Fit `y = a + b₁x₁ + ... + b_k·x_k` with one slice per predictor
+pub fn fit_multiple(predictors: &[&[f32]], y: &[f32]) -> Result<OlsFit, FitError> {
+    if let Some(column) = predictors.iter().find(|c| c.len() != y.len()) {
+        return Err(FitError::LengthMismatch {
+            expected: y.len(),
+            found: column.len(),
+        });
+    }
+    let rows: Vec<Vec<f32>> = (0..y.len())
+        .map(|i| predictors.iter().map(|column| column[i]).collect())
+        .collect();
+    fit(Terms::Linear, &rows, y)
+}
+
+/// Polynomial fit of a dataset's y against its x (`degree` 1 for a straight line)
+pub fn fit_dataset(data: &Dataset, degree: usize) -> Result<OlsFit, FitError> {
+    let (x, y): (Vec<f32>, Vec<f32>) = data.as_2d().iter().map(|p| (p.x, p.y)).unzip();
+    fit_polynomial(&x, &y, degree)
+}
+
+fn fit(terms: Terms, predictors: &[Vec<f32>], y: &[f32]) -> Result<OlsFit, FitError> {
+    if predictors.len() != y.len() {
+        return Err(FitError::LengthMismatch {
+            expected: y.len(),
+            found: predictors.len(),
+        });
+    }
+    // Rows with a missing (non-finite) value are dropped before counting
+    let (predictors, y): (Vec<&[f32]>, Vec<f32>) = predictors
+        .iter()
+        .zip(y)
+        .filter(|(row, y)| y.is_finite() && row.iter().all(|v| v.is_finite()))
+        .map(|(row, &y)| (row.as_slice(), y))
+        .unzip();
+    let rows: Vec<Vec<f64>> = predictors.iter().map(|p| terms.row(p)).collect();
+    let p = terms.row(predictors.first().copied().unwrap_or(&[])).len();
+    let n = y.len();
+    if n <= p {
+        return Err(FitError::TooFewPoints {
+            needed: p + 1,
+            found: n,
+        });
+    }
+
+    // Scale each column to at most 1 in magnitude, which keeps XᵀX well
+    // conditioned for higher polynomial degrees
+    let scales: Vec<f64> = (0..p)
+        .map(|j| {
+            let max = rows.iter().fold(0.0, |acc: f64, row| acc.max(row[j].abs()));
+            if max > 0.0 { max } else { 1.0 }
+        })
+        .collect();
+    let scaled: Vec<Vec<f64>> = rows
+        .iter()
+        .map(|row| row.iter().zip(&scales).map(|(x, s)| x / s).collect())
+        .collect();
+    let y64: Vec<f64> = y.iter().map(|&v| v as f64).collect();
+    let (xtx, xty) = normal_equations(&scaled, &y64);
+    let factor = cholesky(&xtx).ok_or(FitError::Singular)?;
+    let beta: Vec<f64> = cholesky_solve(&factor, &xty)
+        .iter()
+        .zip(&scales)
+        .map(|(b, s)| b / s)
+        .collect();
+
+    let mean_y = y64.iter().sum::<f64>() / n as f64;
+    let (rss, tss) = rows
+        .iter()
+        .zip(&y64)
+        .fold((0.0, 0.0), |(rss, tss), (row, yi)| {
+            let fitted: f64 = row.iter().zip(&beta).map(|(x, b)| x * b).sum();
+            (rss + (yi - fitted).powi(2), tss + (yi - mean_y).powi(2))
+        });
+    let df_residual = n - p;
+    let variance = rss / df_residual as f64;
+    let r_squared = if tss > 0.0 { 1.0 - rss / tss } else { 1.0 };
+    let adj_r_squared = 1.0 - (1.0 - r_squared) * (n - 1) as f64 / df_residual as f64;
+
+    let inverse = cholesky_inverse(&factor);
+    let covariance: Vec<Vec<f64>> = (0..p)
+        .map(|i| {
+            (0..p)
+                .map(|j| variance * inverse[i][j] / (scales[i] * scales[j]))
+                .collect()
+        })
+        .collect();
+
+    let width = predictors.first().map_or(0, |row| row.len());
+    let means = (0..width)
+        .map(|j| predictors.iter().map(|row| row[j]).sum::<f32>() / n as f32)
+        .collect();
+
+    Ok(OlsFit {
+        coefficients: beta.iter().map(|&b| b as f32).collect(),
+        std_errors: (0..p).map(|i| covariance[i][i].sqrt() as f32).collect(),
+        r_squared: r_squared as f32,
+        adj_r_squared: adj_r_squared as f32,
+        sigma: variance.sqrt() as f32,
+        n,
+        df_residual,
+        terms,
+        covariance,
+        means,
+    })
+}
+
+impl OlsFit {
+    /// Two-sided p-value of each coefficient being zero
+    pub fn p_values(&self) -> Vec<f32> {
+        let df = self.df_residual as f64;
+        self.coefficients
+            .iter()
+            .zip(&self.std_errors)
+            .map(|(&b, &se)| {
+                let t = (b / se) as f64;
+                (2.0 * student_t_cdf(-t.abs(), df)) as f32
+            })
+            .collect()
+    }
+
+    /// Fitted value for one observation's predictors
+    pub fn predict_row(&self, predictors: &[f32]) -> f32 {
+        let row = self.terms.row(predictors);
+        row.iter()
+            .zip(&self.coefficients)
+            .map(|(x, &b)| x * b as f64)
+            .sum::<f64>() as f32
+    }
+
+    /// Fitted value at `x`. With several predictors, `x` is the first one
+    /// and the others are held at their means.
+    pub fn predict(&self, x: f32) -> f32 {
+        self.predict_row(&self.row_at(x))
+    }
+
+    /// Standard error of the fitted mean at `x`
+    pub fn mean_std_error(&self, x: f32) -> f32 {
+        let row = self.terms.row(&self.row_at(x));
+        quadratic_form(&self.covariance, &row).max(0.0).sqrt() as f32
+    }
+
+    /// The fitted line or polynomial over `x_min..x_max`
+    pub fn curve(&self, x_min: f32, x_max: f32, samples: usize) -> Curve {
+        Curve::from_function(|x| self.predict(x), x_min, x_max, samples.max(2))
+    }
+
+    /// Pointwise confidence interval for the mean response at `level` (e.g. 0.95)
+    pub fn confidence_band(
+        &self,
+        x_min: f32,
+        x_max: f32,
+        samples: usize,
+        level: f32,
+    ) -> ConfidenceBand {
+        self.band(x_min, x_max, samples, level, 0.0)
+    }
+
+    /// Pointwise prediction interval for new observations at `level` (e.g. 0.95);
+    /// wider than the confidence band by the residual scatter
+    pub fn prediction_band(
+        &self,
+        x_min: f32,
+        x_max: f32,
+        samples: usize,
+        level: f32,
+    ) -> ConfidenceBand {
+        self.band(x_min, x_max, samples, level, self.sigma)
+    }
+
+    fn band(
+        &self,
+        x_min: f32,
+        x_max: f32,
+        samples: usize,
+        level: f32,
+        noise: f32,
+    ) -> ConfidenceBand {
+        let t = self.critical_value(level);
+        let samples = samples.max(2);
+        let (centers, errors): (Vec<Vec2>, Vec<f32>) = (0..samples)
+            .map(|i| {
+                let x = x_min + (x_max - x_min) * i as f32 / (samples - 1) as f32;
+                let se = self.mean_std_error(x).hypot(noise);
+                (vec2(x, self.predict(x)), t * se)
+            })
+            .unzip();
+        ConfidenceBand::from_curve_with_error(&centers, &errors)
+    }
+
+    /// Two-sided Student-t critical value for `level`
+    fn critical_value(&self, level: f32) -> f32 {
+        let alpha = 1.0 - level.clamp(0.0, 0.999_999) as f64;
+        student_t_quantile(1.0 - alpha / 2.0, self.df_residual as f64) as f32
+    }
+
+    /// Predictors with the first set to `x` and the rest at their means
+    fn row_at(&self, x: f32) -> Vec<f32> {
+        let mut row = self.means.clone();
+        match row.first_mut() {
+            Some(first) => *first = x,
+            None => row.push(x),
+        }
+        row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mobject::Mobject;
+
+    #[test]
+    fn test_simple_regression_matches_textbook() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [2.0, 4.0, 5.0, 4.0, 5.0];
+        let fit = fit_linear(&x, &y).unwrap();
+
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(close(fit.coefficients[0], 2.2) && close(fit.coefficients[1], 0.6));
+        assert!(close(fit.r_squared, 0.6));
+        assert!(close(fit.std_errors[1], 0.8f32.sqrt() / 10f32.sqrt()));
+        assert!(close(fit.std_errors[0], 0.88f32.sqrt()));
+        assert_eq!(fit.df_residual, 3);
+
+        // Confidence half-width at x̄ is t·σ/√n, and prediction adds σ
+        let t = 3.182_446;
+        let confidence = fit.confidence_band(3.0, 3.0, 2, 0.95);
+        let prediction = fit.prediction_band(3.0, 3.0, 2, 0.95);
+        let half_width = |band: &ConfidenceBand| band.bounding_box().h / 2.0;
+        assert!((half_width(&confidence) - t * fit.sigma / 5f32.sqrt()).abs() < 1e-3);
+        assert!((half_width(&prediction) - t * fit.sigma * 1.2f32.sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_polynomial_and_multiple_recover_exact_models() {
+        let x: Vec<f32> = (0..20).map(|i| i as f32 * 10.0).collect();
+        let y: Vec<f32> = x.iter().map(|x| 1.0 - 0.5 * x + 0.01 * x * x).collect();
+        let fit = fit_polynomial(&x, &y, 2).unwrap();
+        for (c, expected) in fit.coefficients.iter().zip([1.0, -0.5, 0.01]) {
+            assert!((c - expected).abs() < 1e-3, "{:?}", fit.coefficients);
+        }
+        assert!(fit.r_squared > 0.9999);
+
+        let x2: Vec<f32> = (0..20).map(|i| ((i * 7) % 5) as f32).collect();
+        let y: Vec<f32> = x.iter().zip(&x2).map(|(a, b)| 3.0 + 0.2 * a - b).collect();
+        let fit = fit_multiple(&[&x, &x2], &y).unwrap();
+        assert!((fit.predict_row(&[50.0, 2.0]) - 11.0).abs() < 1e-3);
+
+        assert_eq!(
+            fit_linear(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]).unwrap_err(),
+            FitError::Singular
+        );
+        assert!(matches!(
+            fit_polynomial(&[1.0, 2.0], &[1.0, 2.0], 2),
+            Err(FitError::TooFewPoints {
+                needed: 4,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rows_with_missing_values_are_dropped() {
+        let x = [1.0, 2.0, f32::NAN, 3.0, 4.0, 5.0];
+        let y = [2.0, 4.0, 1.0, 5.0, f32::INFINITY, 7.0];
+        let fit = fit_linear(&x, &y).unwrap();
+        assert_eq!((fit.n, fit.df_residual), (4, 2));
+        assert!(fit.coefficients.iter().all(|c| c.is_finite()));
+
+        assert!(matches!(
+            fit_linear(&[1.0, f32::NAN, 3.0], &[1.0, 2.0, f32::NAN]),
+            Err(FitError::TooFewPoints {
+                needed: 3,
+                found: 1
+            })
+        ));
+    }
+}
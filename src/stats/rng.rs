@@ -0,0 +1,259 @@
+//! A small seedable pseudo-random generator, so simulated data and
+//! resampling are reproducible
+
+use super::linalg::cholesky;
+
+/// SplitMix64: fast, statistically solid for simulation, not for cryptography.
+/// The same seed always gives the same sequence on every platform.
+///
+/// ```ignore
+/// let mut rng = Rng::new(7);
+/// let data = Dataset::generate_normal(200, 0.0, 1.0, &mut rng);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `[low, high)`
+    pub fn range(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.uniform() as f32
+    }
+
+    /// Uniform index in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.uniform() * n as f64) as usize
+    }
+
+    /// Standard normal, by the Marsaglia polar method
+    pub fn normal(&mut self) -> f64 {
+        loop {
+            let u = 2.0 * self.uniform() - 1.0;
+            let v = 2.0 * self.uniform() - 1.0;
+            let s = u * u + v * v;
+            if s > 0.0 && s < 1.0 {
+                return u * (-2.0 * s.ln() / s).sqrt();
+            }
+        }
+    }
+
+    /// Exponential with the given rate (mean `1 / rate`); NaN for a negative rate
+    pub fn exponential(&mut self, rate: f64) -> f64 {
+        if rate.is_nan() || rate < 0.0 {
+            return f64::NAN;
+        }
+        -(1.0 - self.uniform()).ln() / rate
+    }
+
+    /// Gamma with the given shape and unit scale (Marsaglia and Tsang).
+    /// NaN for a negative or NaN shape, infinite for an infinite one.
+    pub fn gamma(&mut self, shape: f64) -> f64 {
+        if shape.is_nan() || shape < 0.0 {
+            return f64::NAN;
+        }
+        if shape == 0.0 || shape == f64::INFINITY {
+            return shape;
+        }
+        if shape < 1.0 {
+            // Boost: X·U^(1/a) is Gamma(a) when X is Gamma(a + 1)
+            let boost = self.uniform().powf(1.0 / shape);
+            return self.gamma(shape + 1.0) * boost;
+        }
+        let d = shape - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let z = self.normal();
+            let v = (1.0 + c * z).powi(3);
+            if v <= 0.0 {
+                continue;
+            }
+            let u = self.uniform();
+            if u.ln() < 0.5 * z * z + d - d * v + d * v.ln() {
+                return d * v;
+            }
+        }
+    }
+
+    /// Beta on [0, 1]; an infinite parameter pins the draw to the matching end
+    pub fn beta(&mut self, a: f64, b: f64) -> f64 {
+        match (a == f64::INFINITY, b == f64::INFINITY) {
+            (true, true) => f64::NAN,
+            (true, false) if !b.is_nan() && b >= 0.0 => 1.0,
+            (false, true) if !a.is_nan() && a >= 0.0 => 0.0,
+            _ => {
+                let x = self.gamma(a);
+                x / (x + self.gamma(b))
+            }
+        }
+    }
+
+    pub fn chi_squared(&mut self, df: f64) -> f64 {
+        2.0 * self.gamma(df / 2.0)
+    }
+
+    /// Chi-squared divided by its degrees of freedom, which is exactly 1 in
+    /// the limit of infinite `df`
+    pub(crate) fn chi_squared_mean(&mut self, df: f64) -> f64 {
+        if df == f64::INFINITY {
+            return 1.0;
+        }
+        self.chi_squared(df) / df
+    }
+
+    /// Student's t with `df` degrees of freedom; standard normal for infinite `df`
+    pub fn student_t(&mut self, df: f64) -> f64 {
+        if df.is_nan() || df <= 0.0 {
+            return f64::NAN;
+        }
+        self.normal() / self.chi_squared_mean(df).sqrt()
+    }
+
+    /// Poisson with mean `lambda`. A NaN or non-positive mean gives 0 and an
+    /// infinite one `u64::MAX`.
+    pub fn poisson(&mut self, lambda: f64) -> u64 {
+        if lambda.is_nan() || lambda <= 0.0 {
+            return 0;
+        }
+        if lambda == f64::INFINITY {
+            return u64::MAX;
+        }
+        if lambda > 30.0 {
+            // Split off a Gamma-distributed waiting time so the product loop
+            // below only ever sees a small mean
+            let m = (0.875 * lambda).floor();
+            let wait = self.gamma(m);
+            return if wait > lambda {
+                self.binomial(m as u64 - 1, lambda / wait)
+            } else {
+                m as u64 + self.poisson(lambda - wait)
+            };
+        }
+        let limit = (-lambda).exp();
+        let mut count = 0;
+        let mut product = self.uniform();
+        while product > limit {
+            count += 1;
+            product *= self.uniform();
+        }
+        count
+    }
+
+    /// Successes in `trials` independent trials with probability `p`
+    pub fn binomial(&mut self, trials: u64, p: f64) -> u64 {
+        let (mut n, mut p) = (trials, p.clamp(0.0, 1.0));
+        let mut successes = 0;
+        // Halve the problem through the order statistics of uniforms (Knuth)
+        while n > 16 {
+            let a = n / 2 + 1;
+            let b = n - a + 1;
+            let x = self.beta(a as f64, b as f64);
+            if x >= p {
+                n = a - 1;
+                p /= x;
+            } else {
+                successes += a;
+                n = b - 1;
+                p = (p - x) / (1.0 - x);
+            }
+        }
+        successes + (0..n).filter(|_| self.uniform() < p).count() as u64
+    }
+
+    /// A draw from the multivariate normal with `mean` and covariance matrix
+    /// `covariance`, or `None` unless the covariance is a symmetric positive
+    /// definite matrix matching `mean`
+    pub fn multivariate_normal(
+        &mut self,
+        mean: &[f64],
+        covariance: &[Vec<f64>],
+    ) -> Option<Vec<f64>> {
+        let factor = covariance_factor(mean.len(), covariance)?;
+        Some(self.correlated_normal(mean, &factor))
+    }
+
+    /// `mean + L·z` for a lower Cholesky factor `L` and standard normal `z`
+    pub(crate) fn correlated_normal(&mut self, mean: &[f64], factor: &[Vec<f64>]) -> Vec<f64> {
+        let z: Vec<f64> = mean.iter().map(|_| self.normal()).collect();
+        mean.iter()
+            .zip(factor)
+            .map(|(m, row)| m + row.iter().zip(&z).map(|(l, z)| l * z).sum::<f64>())
+            .collect()
+    }
+}
+
+/// Lower Cholesky factor of a `dim` x `dim` covariance matrix, or `None`
+/// unless it is square, symmetric and positive definite
+pub(crate) fn covariance_factor(dim: usize, covariance: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    if covariance.len() != dim || covariance.iter().any(|row| row.len() != dim) {
+        return None;
+    }
+    let symmetric = (0..dim).all(|i| {
+        (0..i).all(|j| {
+            let (a, b) = (covariance[i][j], covariance[j][i]);
+            (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0)
+        })
+    });
+    if !symmetric {
+        return None;
+    }
+    cholesky(covariance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degenerate_parameters_terminate() {
+        let mut rng = Rng::new(1);
+        assert!(rng.gamma(f64::NAN).is_nan());
+        assert!(rng.gamma(-1.0).is_nan());
+        assert_eq!(rng.gamma(f64::INFINITY), f64::INFINITY);
+        assert_eq!(rng.chi_squared(f64::INFINITY), f64::INFINITY);
+        assert!(rng.student_t(f64::NAN).is_nan());
+        assert!(rng.student_t(-2.0).is_nan());
+        assert!(rng.exponential(-1.0).is_nan());
+        assert_eq!(rng.beta(f64::INFINITY, 2.0), 1.0);
+        assert_eq!(rng.beta(2.0, f64::INFINITY), 0.0);
+        assert_eq!(rng.poisson(f64::INFINITY), u64::MAX);
+        assert_eq!(rng.poisson(f64::NAN), 0);
+
+        // Infinite degrees of freedom reduce t to the standard normal
+        let mut normal = Rng::new(7);
+        let mut t = Rng::new(7);
+        assert_eq!(t.student_t(f64::INFINITY), normal.normal());
+    }
+
+    #[test]
+    fn test_multivariate_normal_checks_covariance() {
+        let mut rng = Rng::new(3);
+        let mean = [0.0, 0.0];
+        let ragged = vec![vec![1.0, 0.5], vec![0.5]];
+        let lopsided = vec![vec![1.0, 99.0], vec![0.5, 1.0]];
+        assert!(rng.multivariate_normal(&mean, &ragged).is_none());
+        assert!(rng.multivariate_normal(&mean, &lopsided).is_none());
+        let valid = vec![vec![1.0, 0.5], vec![0.5, 1.0]];
+        assert_eq!(
+            rng.multivariate_normal(&mean, &valid).map(|x| x.len()),
+            Some(2)
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::animation::{Animation, AnimationEntry};
-use crate::mobject::MobjectId;
+use crate::mobject::{Mobject, MobjectId};
 
 /// Timeline manages the sequencing of animations
 #[derive(Clone, Default)]
@@ -81,14 +81,42 @@ impl Timeline {
         }
     }
 
+    /// Apply every animation targeting `mobject` that has started by `time`, in timeline order.
+    /// `mobject` must be in its initial state: the result depends only on that state,
+    /// the animations and `time`, so frames can be evaluated in any order.
+    pub fn apply_at(&self, mobject: &mut dyn Mobject, time: f32) {
+        let id = mobject.id();
+        for entry in &self.entries {
+            if entry.animation.target_id() == id && time >= entry.start_time {
+                let t = self.animation_progress(entry, time);
+                entry.animation.apply(mobject, t);
+            }
+        }
+    }
+
+    /// Whether a mobject is on screen at `time`.
+    /// Mobjects introduced by their first animation (FadeIn, Create) are hidden until it starts.
+    pub fn is_visible_at(&self, mobject_id: MobjectId, time: f32) -> bool {
+        match self
+            .entries
+            .iter()
+            .find(|e| e.animation.target_id() == mobject_id)
+        {
+            Some(first) if first.animation.introduces() => time >= first.start_time,
+            _ => true,
+        }
+    }
+
     /// Get the draw progress for a mobject at a given time
     /// This is used by Create/Uncreate animations
     pub fn draw_progress_for(&self, mobject_id: MobjectId, time: f32) -> f32 {
-        // Find the most recent Create animation for this mobject
+        // Find the most recently started Create-style animation for this mobject
         for entry in self.entries.iter().rev() {
-            if entry.animation.target_id() == mobject_id {
-                let progress = self.animation_progress(entry, time);
-                return progress;
+            if entry.animation.target_id() == mobject_id && time >= entry.start_time {
+                let t = self.animation_progress(entry, time);
+                if let Some(progress) = entry.animation.draw_progress(t) {
+                    return progress;
+                }
             }
         }
         // Default to fully drawn if no animation found
@@ -99,8 +127,9 @@ impl Timeline {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::animation::FadeIn;
-    use crate::mobject::MobjectId;
+    use crate::animation::{Create, Easing, FadeIn, MoveTo};
+    use crate::mobject::{Circle, MobjectId};
+    use macroquad::prelude::*;
 
     #[test]
     fn test_sequential_animations() {
@@ -124,4 +153,40 @@ mod tests {
 
         assert!((timeline.total_duration() - 2.5).abs() < 0.001);
     }
+
+    #[test]
+    fn test_apply_at_is_independent_of_evaluation_order() {
+        let circle = Circle::new().at(vec2(-100.0, 0.0));
+        let id = circle.id();
+
+        let mut timeline = Timeline::new();
+        timeline.play(MoveTo::new(id, vec2(100.0, 0.0)).easing(Easing::Linear));
+        timeline.play(MoveTo::new(id, vec2(100.0, 100.0)).easing(Easing::Linear));
+
+        let state_at = |time: f32| {
+            let mut state = circle.clone_box();
+            timeline.apply_at(state.as_mut(), time);
+            state.center()
+        };
+
+        let late = state_at(1.5);
+        let early = state_at(0.5);
+        assert!(early.distance(vec2(0.0, 0.0)) < 0.001);
+        assert!(late.distance(vec2(100.0, 50.0)) < 0.001);
+        assert!(state_at(0.5).distance(early) < 0.001);
+    }
+
+    #[test]
+    fn test_draw_progress_ignores_non_create_animations() {
+        let mut timeline = Timeline::new();
+        let id = MobjectId::new();
+
+        timeline.play(Create::new(id).duration(1.0).easing(Easing::Linear));
+        timeline.play(MoveTo::new(id, Vec2::ONE).duration(1.0));
+
+        assert!((timeline.draw_progress_for(id, 0.25) - 0.25).abs() < 0.001);
+        assert!((timeline.draw_progress_for(id, 1.5) - 1.0).abs() < 0.001);
+        assert!(!timeline.is_visible_at(id, -0.1));
+        assert!(timeline.is_visible_at(id, 0.0));
+    }
 }